@@ -18,15 +18,15 @@ extern crate pushrod;
 
 use std::cell::RefCell;
 
-use glfw_window::GlfwWindow;
 use piston::input::*;
-use piston::window::*;
 
+use pushrod::core::builder::*;
 use pushrod::core::callbacks::*;
 use pushrod::core::horizontal_layout_manager::*;
 use pushrod::core::layout_manager::LayoutManagerPadding;
 use pushrod::core::main::*;
 use pushrod::core::point::make_origin_point;
+use pushrod::core::widget_id::WidgetId;
 use pushrod::core::widget_store::*;
 use pushrod::widget::box_widget::*;
 use pushrod::widget::config::*;
@@ -49,7 +49,12 @@ pub struct SimpleWindowEventHandler {
 }
 
 impl PushrodCallbackEvents for SimpleWindowEventHandler {
-    fn widget_clicked(&mut self, widget_id: i32, button: Button, widget_store: &mut WidgetStore) {
+    fn widget_clicked(
+        &mut self,
+        widget_id: WidgetId,
+        button: Button,
+        widget_store: &mut WidgetStore,
+    ) {
         match button {
             Button::Mouse(mouse_button) => {
                 if mouse_button != MouseButton::Left {
@@ -60,7 +65,7 @@ impl PushrodCallbackEvents for SimpleWindowEventHandler {
         }
 
         match widget_store.get_name_for_widget_id(widget_id) {
-            "TopButtonPlus" => {
+            Some("TopButtonPlus") => {
                 self.top_padding += 1;
                 if self.top_padding > 10 {
                     self.top_padding = 10;
@@ -68,7 +73,7 @@ impl PushrodCallbackEvents for SimpleWindowEventHandler {
                 self.refresh_layout(widget_store);
             }
 
-            "TopButtonMinus" => {
+            Some("TopButtonMinus") => {
                 self.top_padding -= 1;
                 if self.top_padding <= 0 {
                     self.top_padding = 0;
@@ -76,7 +81,7 @@ impl PushrodCallbackEvents for SimpleWindowEventHandler {
                 self.refresh_layout(widget_store);
             }
 
-            "LeftButtonPlus" => {
+            Some("LeftButtonPlus") => {
                 self.left_padding += 1;
                 if self.left_padding > 20 {
                     self.left_padding = 20;
@@ -84,7 +89,7 @@ impl PushrodCallbackEvents for SimpleWindowEventHandler {
                 self.refresh_layout(widget_store);
             }
 
-            "LeftButtonMinus" => {
+            Some("LeftButtonMinus") => {
                 self.left_padding -= 1;
                 if self.left_padding <= 0 {
                     self.left_padding = 0;
@@ -92,7 +97,7 @@ impl PushrodCallbackEvents for SimpleWindowEventHandler {
                 self.refresh_layout(widget_store);
             }
 
-            "RightButtonPlus" => {
+            Some("RightButtonPlus") => {
                 self.right_padding += 1;
                 if self.right_padding > 20 {
                     self.right_padding = 20;
@@ -100,7 +105,7 @@ impl PushrodCallbackEvents for SimpleWindowEventHandler {
                 self.refresh_layout(widget_store);
             }
 
-            "RightButtonMinus" => {
+            Some("RightButtonMinus") => {
                 self.right_padding -= 1;
                 if self.right_padding <= 0 {
                     self.right_padding = 0;
@@ -108,7 +113,7 @@ impl PushrodCallbackEvents for SimpleWindowEventHandler {
                 self.refresh_layout(widget_store);
             }
 
-            "BottomButtonPlus" => {
+            Some("BottomButtonPlus") => {
                 self.bottom_padding += 1;
                 if self.bottom_padding > 10 {
                     self.bottom_padding = 10;
@@ -116,7 +121,7 @@ impl PushrodCallbackEvents for SimpleWindowEventHandler {
                 self.refresh_layout(widget_store);
             }
 
-            "BottomButtonMinus" => {
+            Some("BottomButtonMinus") => {
                 self.bottom_padding -= 1;
                 if self.bottom_padding <= 0 {
                     self.bottom_padding = 0;
@@ -124,7 +129,7 @@ impl PushrodCallbackEvents for SimpleWindowEventHandler {
                 self.refresh_layout(widget_store);
             }
 
-            "SpacingButtonPlus" => {
+            Some("SpacingButtonPlus") => {
                 self.spacing += 1;
                 if self.spacing > 12 {
                     self.spacing = 12;
@@ -132,7 +137,7 @@ impl PushrodCallbackEvents for SimpleWindowEventHandler {
                 self.refresh_layout(widget_store);
             }
 
-            "SpacingButtonMinus" => {
+            Some("SpacingButtonMinus") => {
                 self.spacing -= 1;
                 if self.spacing <= 0 {
                     self.spacing = 0;
@@ -171,6 +176,7 @@ impl SimpleWindowEventHandler {
 
         widget_store
             .get_widget_for_name("TopButtonText")
+            .unwrap()
             .borrow_mut()
             .set_config(
                 CONFIG_DISPLAY_TEXT,
@@ -179,6 +185,7 @@ impl SimpleWindowEventHandler {
 
         widget_store
             .get_widget_for_name("LeftButtonText")
+            .unwrap()
             .borrow_mut()
             .set_config(
                 CONFIG_DISPLAY_TEXT,
@@ -187,6 +194,7 @@ impl SimpleWindowEventHandler {
 
         widget_store
             .get_widget_for_name("RightButtonText")
+            .unwrap()
             .borrow_mut()
             .set_config(
                 CONFIG_DISPLAY_TEXT,
@@ -195,6 +203,7 @@ impl SimpleWindowEventHandler {
 
         widget_store
             .get_widget_for_name("BottomButtonText")
+            .unwrap()
             .borrow_mut()
             .set_config(
                 CONFIG_DISPLAY_TEXT,
@@ -203,6 +212,7 @@ impl SimpleWindowEventHandler {
 
         widget_store
             .get_widget_for_name("SpacingButtonText")
+            .unwrap()
             .borrow_mut()
             .set_config(
                 CONFIG_DISPLAY_TEXT,
@@ -234,11 +244,15 @@ impl SimpleWindow {
         base_widget.set_color(CONFIG_MAIN_COLOR, [1.0, 1.0, 1.0, 1.0]);
         base_widget.set_numeric(CONFIG_BORDER_WIDTH, 1);
 
-        let base_widget_id = self.pushrod.borrow_mut().add_widget_to_parent_by_name(
-            "MainContainerWidget",
-            "HorizontalManagerWidget1",
-            Box::new(base_widget),
-        );
+        let base_widget_id = self
+            .pushrod
+            .borrow_mut()
+            .add_widget_to_parent_by_name(
+                "MainContainerWidget",
+                "HorizontalManagerWidget1",
+                Box::new(base_widget),
+            )
+            .unwrap();
 
         self.layout_id =
             self.pushrod
@@ -261,12 +275,16 @@ impl SimpleWindow {
         box_widget.set_color(CONFIG_MAIN_COLOR, [0.0, 1.0, 0.0, 1.0]);
         box_widget.set_numeric(CONFIG_BORDER_WIDTH, 4);
         box_widget.set_color(CONFIG_BORDER_COLOR, [1.0, 0.0, 0.0, 1.0]);
-        let _box_widget_id = self.pushrod.borrow_mut().add_widget_to_layout_manager(
-            "BoxInLayoutWidget1",
-            Box::new(box_widget),
-            self.layout_id,
-            make_origin_point(),
-        );
+        let _box_widget_id = self
+            .pushrod
+            .borrow_mut()
+            .add_widget_to_layout_manager(
+                "BoxInLayoutWidget1",
+                Box::new(box_widget),
+                self.layout_id,
+                make_origin_point(),
+            )
+            .unwrap();
 
         let mut box_1 = BoxWidget::new();
         box_1.set_point(CONFIG_ORIGIN, 480, 80);
@@ -274,12 +292,16 @@ impl SimpleWindow {
         box_1.set_color(CONFIG_MAIN_COLOR, [0.5, 0.5, 1.0, 1.0]);
         box_1.set_numeric(CONFIG_BORDER_WIDTH, 2);
         box_1.set_color(CONFIG_BORDER_COLOR, [0.0, 0.0, 1.0, 1.0]);
-        let _box_1_id = self.pushrod.borrow_mut().add_widget_to_layout_manager(
-            "BoxInLayoutWidget2",
-            Box::new(box_1),
-            self.layout_id,
-            make_origin_point(),
-        );
+        let _box_1_id = self
+            .pushrod
+            .borrow_mut()
+            .add_widget_to_layout_manager(
+                "BoxInLayoutWidget2",
+                Box::new(box_1),
+                self.layout_id,
+                make_origin_point(),
+            )
+            .unwrap();
 
         let mut box_2 = BoxWidget::new();
         box_2.set_point(CONFIG_ORIGIN, 480, 80);
@@ -295,12 +317,15 @@ impl SimpleWindow {
         );
         box_2.set_numeric(CONFIG_BORDER_WIDTH, 1);
         box_2.set_color(CONFIG_BORDER_COLOR, [0.0, 0.0, 0.0, 1.0]);
-        self.pushrod.borrow_mut().add_widget_to_layout_manager(
-            "BoxInLayoutWidget3",
-            Box::new(box_2),
-            self.layout_id,
-            make_origin_point(),
-        );
+        self.pushrod
+            .borrow_mut()
+            .add_widget_to_layout_manager(
+                "BoxInLayoutWidget3",
+                Box::new(box_2),
+                self.layout_id,
+                make_origin_point(),
+            )
+            .unwrap();
     }
 
     fn add_horizontal_layout_buttons(&mut self) {
@@ -628,11 +653,13 @@ impl SimpleWindow {
 }
 
 fn main() {
-    let window: GlfwWindow = WindowSettings::new("Horizontal Layout Example", [800, 370])
+    let pushrod = PushrodBuilder::new()
+        .title("Horizontal Layout Example")
+        .size(800, 370)
         .resizable(false)
         .build()
-        .unwrap_or_else(|error| panic!("Failed to build PistonWindow: {}", error));
-    let mut app_window = SimpleWindow::new(Pushrod::new(window));
+        .unwrap_or_else(|error| panic!("Failed to create window: {}", error));
+    let mut app_window = SimpleWindow::new(pushrod);
 
     app_window.run();
 }
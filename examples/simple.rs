@@ -18,16 +18,16 @@ extern crate pushrod;
 
 use std::cell::RefCell;
 
-use glfw_window::GlfwWindow;
 use piston::input::*;
-use piston::window::*;
 
+use pushrod::core::builder::*;
 use pushrod::core::callbacks::*;
 use pushrod::core::horizontal_layout_manager::*;
 use pushrod::core::layout_manager::LayoutManagerPadding;
 use pushrod::core::main::*;
-use pushrod::core::point::{make_origin_point, Point, Size};
+use pushrod::core::point::{make_origin_point, Size};
 use pushrod::core::vertical_layout_manager::*;
+use pushrod::core::widget_id::WidgetId;
 use pushrod::core::widget_store::*;
 use pushrod::widget::box_widget::*;
 use pushrod::widget::checkbox_widget::*;
@@ -35,6 +35,7 @@ use pushrod::widget::config::*;
 use pushrod::widget::image_button_widget::*;
 use pushrod::widget::progress_widget::*;
 use pushrod::widget::push_button_widget::*;
+use pushrod::widget::text_input_widget::*;
 use pushrod::widget::text_widget::*;
 use pushrod::widget::timer_widget::*;
 use pushrod::widget::toggle_button_widget::*;
@@ -55,9 +56,9 @@ pub struct SimpleWindowEventHandler {
 }
 
 impl PushrodCallbackEvents for SimpleWindowEventHandler {
-    fn timer_triggered(&mut self, widget_id: i32, widget_store: &mut WidgetStore) {
+    fn timer_triggered(&mut self, widget_id: WidgetId, widget_store: &mut WidgetStore) {
         match widget_store.get_name_for_widget_id(widget_id) {
-            "HelloWorldTimer" => {
+            Some("HelloWorldTimer") => {
                 if self.manipulated_color == 1 {
                     if self.color_direction == 1 {
                         if self.red_value == 255 {
@@ -101,6 +102,7 @@ impl PushrodCallbackEvents for SimpleWindowEventHandler {
 
                 widget_store
                     .get_widget_for_name("TextWidget")
+                    .unwrap()
                     .borrow_mut()
                     .set_color(
                         CONFIG_TEXT_COLOR,
@@ -113,7 +115,7 @@ impl PushrodCallbackEvents for SimpleWindowEventHandler {
                     );
             }
 
-            "TimerWidget1" => {
+            Some("TimerWidget1") => {
                 if self.animated {
                     self.progress += 1;
 
@@ -123,11 +125,13 @@ impl PushrodCallbackEvents for SimpleWindowEventHandler {
 
                     widget_store
                         .get_widget_for_name("ProgressWidget")
+                        .unwrap()
                         .borrow_mut()
                         .set_config(CONFIG_PROGRESS, Config::Numeric(self.progress as u64));
 
                     widget_store
                         .get_widget_for_name("ProgressText1")
+                        .unwrap()
                         .borrow_mut()
                         .set_text(CONFIG_DISPLAY_TEXT, format!("{} %", self.progress));
                 }
@@ -137,23 +141,29 @@ impl PushrodCallbackEvents for SimpleWindowEventHandler {
         };
     }
 
-    fn mouse_entered(&mut self, widget_id: i32, widget_store: &mut WidgetStore) {
+    fn mouse_entered(&mut self, widget_id: WidgetId, widget_store: &mut WidgetStore) {
         // When a mouse enters a widget, the ID will get modified; modify the debug widget
         // with the ID that was specified.
-        let widget_name = String::from(widget_store.get_name_for_widget_id(widget_id));
+        let widget_name = match widget_store.get_name_for_widget_id(widget_id) {
+            Some(widget_name) => String::from(widget_name),
+            None => return,
+        };
         let widget_point = widget_store
             .get_widget_for_id(widget_id)
+            .unwrap()
             .borrow_mut()
             .config()
             .get_point(CONFIG_ORIGIN);
         let widget_size = widget_store
             .get_widget_for_id(widget_id)
+            .unwrap()
             .borrow_mut()
             .config()
             .get_size(CONFIG_BODY_SIZE);
 
         widget_store
             .get_widget_for_name("DebugText1")
+            .unwrap()
             .borrow_mut()
             .set_config(
                 CONFIG_DISPLAY_TEXT,
@@ -162,6 +172,7 @@ impl PushrodCallbackEvents for SimpleWindowEventHandler {
 
         widget_store
             .get_widget_for_name("DebugText2")
+            .unwrap()
             .borrow_mut()
             .set_config(
                 CONFIG_DISPLAY_TEXT,
@@ -175,23 +186,25 @@ impl PushrodCallbackEvents for SimpleWindowEventHandler {
 
     fn widget_selected(
         &mut self,
-        widget_id: i32,
+        widget_id: WidgetId,
         _button: Button,
         selected: bool,
         widget_store: &mut WidgetStore,
     ) {
         match widget_store.get_name_for_widget_id(widget_id) {
-            "AnimateButton1" => {
+            Some("AnimateButton1") => {
                 self.animated = selected;
             }
 
-            "DebugCheck1" => {
+            Some("DebugCheck1") => {
                 widget_store
                     .get_widget_for_name("DebugText1")
+                    .unwrap()
                     .borrow_mut()
                     .set_toggle(CONFIG_WIDGET_HIDDEN, !selected);
                 widget_store
                     .get_widget_for_name("DebugText2")
+                    .unwrap()
                     .borrow_mut()
                     .set_toggle(CONFIG_WIDGET_HIDDEN, !selected);
             }
@@ -199,81 +212,12 @@ impl PushrodCallbackEvents for SimpleWindowEventHandler {
         }
     }
 
-    fn widget_moved(&mut self, widget_id: i32, point: Point, widget_store: &mut WidgetStore) {
-        match widget_store.get_name_for_widget_id(widget_id) {
-            "BoxInLayoutWidget3" => {
-                eprintln!("Reposition text inside BoxInLayoutWidget1");
-
-                widget_store
-                    .get_widget_for_name("LeftJustifiedText")
-                    .borrow_mut()
-                    .set_point(CONFIG_ORIGIN, point.x + 16, point.y + 10);
-
-                widget_store
-                    .get_widget_for_name("CenterJustifiedText")
-                    .borrow_mut()
-                    .set_point(CONFIG_ORIGIN, point.x + 16, point.y + 100 - 24);
-
-                widget_store
-                    .get_widget_for_name("RightJustifiedText")
-                    .borrow_mut()
-                    .set_point(CONFIG_ORIGIN, point.x + 16, point.y + 200 - 54);
-
-                let layout_size2 = widget_store
-                    .get_widget_for_name("BoxInLayoutWidget2")
-                    .borrow_mut()
-                    .config()
-                    .get_size(CONFIG_BODY_SIZE);
-
-                eprintln!("Reposition text inside BoxInLayoutWidget2");
-
-                let box2_point = widget_store
-                    .get_widget_for_name("BoxInLayoutWidget2")
-                    .borrow_mut()
-                    .config()
-                    .get_point(CONFIG_ORIGIN);
-
-                widget_store
-                    .get_widget_for_name("MiniBox1")
-                    .borrow_mut()
-                    .set_point(CONFIG_ORIGIN, box2_point.x + 10, box2_point.y + 10);
-
-                widget_store
-                    .get_widget_for_name("MiniBox2")
-                    .borrow_mut()
-                    .set_point(
-                        CONFIG_ORIGIN,
-                        box2_point.x + (layout_size2.w / 2) + 4,
-                        box2_point.y + 10,
-                    );
-
-                widget_store
-                    .get_widget_for_name("MiniBox3")
-                    .borrow_mut()
-                    .set_point(
-                        CONFIG_ORIGIN,
-                        box2_point.x + 10,
-                        box2_point.y + (layout_size2.h / 2) + 4,
-                    );
-
-                widget_store
-                    .get_widget_for_name("MiniBox4")
-                    .borrow_mut()
-                    .set_point(
-                        CONFIG_ORIGIN,
-                        box2_point.x + (layout_size2.w / 2) + 4,
-                        box2_point.y + (layout_size2.h / 2) + 4,
-                    );
-            }
-            _ => (),
-        }
-    }
-
-    fn widget_resized(&mut self, widget_id: i32, _size: Size, widget_store: &mut WidgetStore) {
+    fn widget_resized(&mut self, widget_id: WidgetId, _size: Size, widget_store: &mut WidgetStore) {
         match widget_store.get_name_for_widget_id(widget_id) {
-            "BoxInLayoutWidget3" => {
+            Some("BoxInLayoutWidget3") => {
                 let layout_size = widget_store
                     .get_widget_for_name("BoxInLayoutWidget1")
+                    .unwrap()
                     .borrow_mut()
                     .config()
                     .get_size(CONFIG_BODY_SIZE);
@@ -282,21 +226,25 @@ impl PushrodCallbackEvents for SimpleWindowEventHandler {
 
                 widget_store
                     .get_widget_for_name("LeftJustifiedText")
+                    .unwrap()
                     .borrow_mut()
                     .set_size(CONFIG_BODY_SIZE, layout_size.w - 32, 32);
 
                 widget_store
                     .get_widget_for_name("CenterJustifiedText")
+                    .unwrap()
                     .borrow_mut()
                     .set_size(CONFIG_BODY_SIZE, layout_size.w - 32, 32);
 
                 widget_store
                     .get_widget_for_name("RightJustifiedText")
+                    .unwrap()
                     .borrow_mut()
                     .set_size(CONFIG_BODY_SIZE, layout_size.w - 32, 32);
 
                 let layout_size2 = widget_store
                     .get_widget_for_name("BoxInLayoutWidget2")
+                    .unwrap()
                     .borrow_mut()
                     .config()
                     .get_size(CONFIG_BODY_SIZE);
@@ -305,6 +253,7 @@ impl PushrodCallbackEvents for SimpleWindowEventHandler {
 
                 widget_store
                     .get_widget_for_name("MiniBox1")
+                    .unwrap()
                     .borrow_mut()
                     .set_size(
                         CONFIG_BODY_SIZE,
@@ -314,6 +263,7 @@ impl PushrodCallbackEvents for SimpleWindowEventHandler {
 
                 widget_store
                     .get_widget_for_name("MiniBox2")
+                    .unwrap()
                     .borrow_mut()
                     .set_size(
                         CONFIG_BODY_SIZE,
@@ -321,8 +271,15 @@ impl PushrodCallbackEvents for SimpleWindowEventHandler {
                         (layout_size2.h / 2) - 12,
                     );
 
+                widget_store
+                    .get_widget_for_name("MiniBox2")
+                    .unwrap()
+                    .borrow_mut()
+                    .set_point(CONFIG_ORIGIN, (layout_size2.w / 2) + 4, 10);
+
                 widget_store
                     .get_widget_for_name("MiniBox3")
+                    .unwrap()
                     .borrow_mut()
                     .set_size(
                         CONFIG_BODY_SIZE,
@@ -330,14 +287,31 @@ impl PushrodCallbackEvents for SimpleWindowEventHandler {
                         (layout_size2.h / 2) - 12,
                     );
 
+                widget_store
+                    .get_widget_for_name("MiniBox3")
+                    .unwrap()
+                    .borrow_mut()
+                    .set_point(CONFIG_ORIGIN, 10, (layout_size2.h / 2) + 4);
+
                 widget_store
                     .get_widget_for_name("MiniBox4")
+                    .unwrap()
                     .borrow_mut()
                     .set_size(
                         CONFIG_BODY_SIZE,
                         (layout_size2.w / 2) - 12,
                         (layout_size2.h / 2) - 12,
                     );
+
+                widget_store
+                    .get_widget_for_name("MiniBox4")
+                    .unwrap()
+                    .borrow_mut()
+                    .set_point(
+                        CONFIG_ORIGIN,
+                        (layout_size2.w / 2) + 4,
+                        (layout_size2.h / 2) + 4,
+                    );
             }
             _ => (),
         }
@@ -378,21 +352,47 @@ impl SimpleWindow {
         text_widget.set_color(CONFIG_MAIN_COLOR, [1.0; 4]);
         text_widget.set_color(CONFIG_TEXT_COLOR, [0.75, 0.25, 1.0, 1.0]);
 
-        self.pushrod.borrow_mut().add_widget_to_parent_by_name(
-            "MainContainerWidget",
-            "TextWidget",
-            Box::new(text_widget),
-        );
+        self.pushrod
+            .borrow_mut()
+            .add_widget_to_parent_by_name(
+                "MainContainerWidget",
+                "TextWidget",
+                Box::new(text_widget),
+            )
+            .unwrap();
 
         let mut timer = TimerWidget::new();
 
         timer.set_numeric(CONFIG_TIMER_TIMEOUT, 10);
         timer.set_toggle(CONFIG_TIMER_ENABLED, true);
-        self.pushrod.borrow_mut().add_widget_to_parent_by_name(
-            "MainContainerWidget",
-            "HelloWorldTimer",
-            Box::new(timer),
+        self.pushrod
+            .borrow_mut()
+            .add_widget_to_parent_by_name("MainContainerWidget", "HelloWorldTimer", Box::new(timer))
+            .unwrap();
+    }
+
+    fn add_text_input(&mut self) {
+        let mut text_input = TextInputWidget::new(
+            "assets/OpenSans-Regular.ttf".to_string(),
+            "".to_string(),
+            18,
         );
+
+        text_input.set_point(CONFIG_ORIGIN, 440, 20);
+        text_input.set_size(CONFIG_BODY_SIZE, 340, 32);
+        text_input.set_text(CONFIG_PLACEHOLDER_TEXT, "Type something here".to_string());
+        text_input
+            .get_callbacks()
+            .on_text_submitted(|_, text, widgets| {
+                get_widget_by_name(widgets, "DebugText2".to_string())
+                    .unwrap()
+                    .set_text(CONFIG_DISPLAY_TEXT, format!("Submitted: {}", text));
+            });
+
+        self.pushrod
+            .borrow_mut()
+            .add_widget_to_parent_by_name("MainContainerWidget", "TextInput1", Box::new(text_input))
+            .unwrap();
     }
 
     fn add_horizontal_layout(&mut self) {
@@ -402,11 +402,15 @@ impl SimpleWindow {
         base_widget.set_size(CONFIG_BODY_SIZE, 760, 200);
         base_widget.set_color(CONFIG_MAIN_COLOR, [1.0, 1.0, 1.0, 1.0]);
 
-        let base_widget_id = self.pushrod.borrow_mut().add_widget_to_parent_by_name(
-            "MainContainerWidget",
-            "HorizontalManagerWidget1",
-            Box::new(base_widget),
-        );
+        let base_widget_id = self
+            .pushrod
+            .borrow_mut()
+            .add_widget_to_parent_by_name(
+                "MainContainerWidget",
+                "HorizontalManagerWidget1",
+                Box::new(base_widget),
+            )
+            .unwrap();
 
         let base_layout_id =
             self.pushrod
@@ -429,12 +433,16 @@ impl SimpleWindow {
         box_widget.set_color(CONFIG_MAIN_COLOR, [0.0, 1.0, 0.0, 1.0]);
         box_widget.set_numeric(CONFIG_BORDER_WIDTH, 4);
         box_widget.set_color(CONFIG_BORDER_COLOR, [1.0, 0.0, 0.0, 1.0]);
-        let box_widget_id = self.pushrod.borrow_mut().add_widget_to_layout_manager(
-            "BoxInLayoutWidget1",
-            Box::new(box_widget),
-            base_layout_id,
-            make_origin_point(),
-        );
+        let box_widget_id = self
+            .pushrod
+            .borrow_mut()
+            .add_widget_to_layout_manager(
+                "BoxInLayoutWidget1",
+                Box::new(box_widget),
+                base_layout_id,
+                make_origin_point(),
+            )
+            .unwrap();
 
         let mut text_widget2 = TextWidget::new(
             "assets/OpenSans-Regular.ttf".to_string(),
@@ -442,15 +450,14 @@ impl SimpleWindow {
             24,
             TextJustify::Left,
         );
-        text_widget2.set_point(CONFIG_ORIGIN, 265, 100);
+        text_widget2.set_point(CONFIG_ORIGIN, 16, 10);
         text_widget2.set_size(CONFIG_BODY_SIZE, 170, 32);
         text_widget2.set_color(CONFIG_TEXT_COLOR, [0.0, 0.0, 0.0, 1.0]);
         text_widget2.set_color(CONFIG_MAIN_COLOR, [1.0, 1.0, 1.0, 0.0]);
-        self.pushrod.borrow_mut().add_widget_to_parent(
-            "LeftJustifiedText",
-            Box::new(text_widget2),
-            box_widget_id,
-        );
+        self.pushrod
+            .borrow_mut()
+            .add_widget_to_parent("LeftJustifiedText", Box::new(text_widget2), box_widget_id)
+            .unwrap();
 
         let mut text_widget3 = TextWidget::new(
             "assets/OpenSans-Regular.ttf".to_string(),
@@ -458,15 +465,14 @@ impl SimpleWindow {
             24,
             TextJustify::Center,
         );
-        text_widget3.set_point(CONFIG_ORIGIN, 265, 166);
+        text_widget3.set_point(CONFIG_ORIGIN, 16, 76);
         text_widget3.set_size(CONFIG_BODY_SIZE, 170, 32);
         text_widget3.set_color(CONFIG_TEXT_COLOR, [0.0, 0.0, 0.0, 1.0]);
         text_widget3.set_color(CONFIG_MAIN_COLOR, [1.0, 1.0, 1.0, 0.0]);
-        self.pushrod.borrow_mut().add_widget_to_parent(
-            "CenterJustifiedText",
-            Box::new(text_widget3),
-            box_widget_id,
-        );
+        self.pushrod
+            .borrow_mut()
+            .add_widget_to_parent("CenterJustifiedText", Box::new(text_widget3), box_widget_id)
+            .unwrap();
 
         let mut text_widget4 = TextWidget::new(
             "assets/OpenSans-Regular.ttf".to_string(),
@@ -474,15 +480,14 @@ impl SimpleWindow {
             24,
             TextJustify::Right,
         );
-        text_widget4.set_point(CONFIG_ORIGIN, 265, 230);
+        text_widget4.set_point(CONFIG_ORIGIN, 16, 146);
         text_widget4.set_size(CONFIG_BODY_SIZE, 170, 32);
         text_widget4.set_color(CONFIG_TEXT_COLOR, [0.0, 0.0, 0.0, 1.0]);
         text_widget4.set_color(CONFIG_MAIN_COLOR, [1.0, 1.0, 1.0, 0.0]);
-        self.pushrod.borrow_mut().add_widget_to_parent(
-            "RightJustifiedText",
-            Box::new(text_widget4),
-            box_widget_id,
-        );
+        self.pushrod
+            .borrow_mut()
+            .add_widget_to_parent("RightJustifiedText", Box::new(text_widget4), box_widget_id)
+            .unwrap();
 
         let mut box_1 = BoxWidget::new();
         box_1.set_point(CONFIG_ORIGIN, 480, 80);
@@ -490,52 +495,60 @@ impl SimpleWindow {
         box_1.set_color(CONFIG_MAIN_COLOR, [0.5, 0.5, 1.0, 1.0]);
         box_1.set_numeric(CONFIG_BORDER_WIDTH, 2);
         box_1.set_color(CONFIG_BORDER_COLOR, [0.0, 0.0, 1.0, 1.0]);
-        let box_1_id = self.pushrod.borrow_mut().add_widget_to_layout_manager(
-            "BoxInLayoutWidget2",
-            Box::new(box_1),
-            base_layout_id,
-            make_origin_point(),
-        );
+        let box_1_id = self
+            .pushrod
+            .borrow_mut()
+            .add_widget_to_layout_manager(
+                "BoxInLayoutWidget2",
+                Box::new(box_1),
+                base_layout_id,
+                make_origin_point(),
+            )
+            .unwrap();
 
         let mut inner_box_1 = BoxWidget::new();
-        inner_box_1.set_point(CONFIG_ORIGIN, 505, 105);
+        inner_box_1.set_point(CONFIG_ORIGIN, 10, 10);
         inner_box_1.set_size(CONFIG_BODY_SIZE, 70, 60);
         inner_box_1.set_color(CONFIG_MAIN_COLOR, [0.75, 0.75, 1.0, 1.0]);
         inner_box_1.set_numeric(CONFIG_BORDER_WIDTH, 1);
         inner_box_1.set_color(CONFIG_BORDER_COLOR, [1.0, 0.0, 1.0, 1.0]);
         self.pushrod
             .borrow_mut()
-            .add_widget_to_parent("MiniBox1", Box::new(inner_box_1), box_1_id);
+            .add_widget_to_parent("MiniBox1", Box::new(inner_box_1), box_1_id)
+            .unwrap();
 
         let mut inner_box_2 = BoxWidget::new();
-        inner_box_2.set_point(CONFIG_ORIGIN, 585, 105);
+        inner_box_2.set_point(CONFIG_ORIGIN, 104, 10);
         inner_box_2.set_size(CONFIG_BODY_SIZE, 70, 60);
         inner_box_2.set_color(CONFIG_MAIN_COLOR, [0.75, 0.25, 1.0, 1.0]);
         inner_box_2.set_numeric(CONFIG_BORDER_WIDTH, 1);
         inner_box_2.set_color(CONFIG_BORDER_COLOR, [1.0, 1.0, 0.0, 1.0]);
         self.pushrod
             .borrow_mut()
-            .add_widget_to_parent("MiniBox2", Box::new(inner_box_2), box_1_id);
+            .add_widget_to_parent("MiniBox2", Box::new(inner_box_2), box_1_id)
+            .unwrap();
 
         let mut inner_box_3 = BoxWidget::new();
-        inner_box_3.set_point(CONFIG_ORIGIN, 505, 190);
+        inner_box_3.set_point(CONFIG_ORIGIN, 10, 104);
         inner_box_3.set_size(CONFIG_BODY_SIZE, 70, 60);
         inner_box_3.set_color(CONFIG_MAIN_COLOR, [0.25, 0.50, 0.75, 1.0]);
         inner_box_3.set_numeric(CONFIG_BORDER_WIDTH, 1);
         inner_box_3.set_color(CONFIG_BORDER_COLOR, [1.0, 0.50, 1.0, 1.0]);
         self.pushrod
             .borrow_mut()
-            .add_widget_to_parent("MiniBox3", Box::new(inner_box_3), box_1_id);
+            .add_widget_to_parent("MiniBox3", Box::new(inner_box_3), box_1_id)
+            .unwrap();
 
         let mut inner_box_4 = BoxWidget::new();
-        inner_box_4.set_point(CONFIG_ORIGIN, 585, 190);
+        inner_box_4.set_point(CONFIG_ORIGIN, 104, 104);
         inner_box_4.set_size(CONFIG_BODY_SIZE, 70, 60);
         inner_box_4.set_color(CONFIG_MAIN_COLOR, [0.75, 0.50, 0.0, 1.0]);
         inner_box_4.set_numeric(CONFIG_BORDER_WIDTH, 1);
         inner_box_4.set_color(CONFIG_BORDER_COLOR, [0.50, 0.0, 0.25, 1.0]);
         self.pushrod
             .borrow_mut()
-            .add_widget_to_parent("MiniBox4", Box::new(inner_box_4), box_1_id);
+            .add_widget_to_parent("MiniBox4", Box::new(inner_box_4), box_1_id)
+            .unwrap();
 
         let mut box_2 = BoxWidget::new();
         box_2.set_point(CONFIG_ORIGIN, 480, 80);
@@ -551,12 +564,15 @@ impl SimpleWindow {
         );
         box_2.set_numeric(CONFIG_BORDER_WIDTH, 1);
         box_2.set_color(CONFIG_BORDER_COLOR, [0.0, 0.0, 0.0, 1.0]);
-        self.pushrod.borrow_mut().add_widget_to_layout_manager(
-            "BoxInLayoutWidget3",
-            Box::new(box_2),
-            base_layout_id,
-            make_origin_point(),
-        );
+        self.pushrod
+            .borrow_mut()
+            .add_widget_to_layout_manager(
+                "BoxInLayoutWidget3",
+                Box::new(box_2),
+                base_layout_id,
+                make_origin_point(),
+            )
+            .unwrap();
     }
 
     fn add_horizontal_layout_buttons(&mut self) {
@@ -566,11 +582,15 @@ impl SimpleWindow {
         base_widget.set_size(CONFIG_BODY_SIZE, 760, 36);
         base_widget.set_color(CONFIG_MAIN_COLOR, [1.0, 1.0, 1.0, 1.0]);
 
-        let base_widget_id = self.pushrod.borrow_mut().add_widget_to_parent_by_name(
-            "MainContainerWidget",
-            "HorizontalManagerWidget2",
-            Box::new(base_widget),
-        );
+        let base_widget_id = self
+            .pushrod
+            .borrow_mut()
+            .add_widget_to_parent_by_name(
+                "MainContainerWidget",
+                "HorizontalManagerWidget2",
+                Box::new(base_widget),
+            )
+            .unwrap();
 
         let base_layout_id =
             self.pushrod
@@ -599,6 +619,7 @@ impl SimpleWindow {
         button1.set_color(CONFIG_BORDER_COLOR, [0.0, 0.0, 0.0, 1.0]);
         button1.get_callbacks().on_click(|x, widgets| {
             let state = get_widget_by_name(widgets, "BoxInLayoutWidget1".to_string())
+                .unwrap()
                 .config()
                 .get_toggle(CONFIG_WIDGET_HIDDEN);
             let button_text = if state == true {
@@ -610,17 +631,21 @@ impl SimpleWindow {
             x.set_config(CONFIG_DISPLAY_TEXT, Config::Text(button_text));
 
             get_widget_by_name(widgets, "BoxInLayoutWidget1".to_string())
+                .unwrap()
                 .set_toggle(CONFIG_WIDGET_HIDDEN, !state);
 
             invalidate_all_widgets_except(widgets, x.get_widget_id());
         });
 
-        self.pushrod.borrow_mut().add_widget_to_layout_manager(
-            "BoxInLayoutWidgetButton1",
-            Box::new(button1),
-            base_layout_id,
-            make_origin_point(),
-        );
+        self.pushrod
+            .borrow_mut()
+            .add_widget_to_layout_manager(
+                "BoxInLayoutWidgetButton1",
+                Box::new(button1),
+                base_layout_id,
+                make_origin_point(),
+            )
+            .unwrap();
 
         let mut button3 = PushButtonWidget::new(
             "assets/OpenSans-Regular.ttf".to_string(),
@@ -635,6 +660,7 @@ impl SimpleWindow {
         button3.set_color(CONFIG_BORDER_COLOR, [0.0, 0.0, 0.0, 1.0]);
         button3.get_callbacks().on_click(|x, widgets| {
             let state = get_widget_by_name(widgets, "BoxInLayoutWidget2".to_string())
+                .unwrap()
                 .config()
                 .get_toggle(CONFIG_WIDGET_DISABLED);
             let button_text = if state == true {
@@ -646,27 +672,35 @@ impl SimpleWindow {
             x.set_config(CONFIG_DISPLAY_TEXT, Config::Text(button_text));
 
             get_widget_by_name(widgets, "BoxInLayoutWidget2".to_string())
+                .unwrap()
                 .set_toggle(CONFIG_WIDGET_DISABLED, !state);
 
             get_widget_by_name(widgets, "MiniBox1".to_string())
+                .unwrap()
                 .set_toggle(CONFIG_WIDGET_DISABLED, !state);
 
             get_widget_by_name(widgets, "MiniBox2".to_string())
+                .unwrap()
                 .set_toggle(CONFIG_WIDGET_DISABLED, !state);
 
             get_widget_by_name(widgets, "MiniBox3".to_string())
+                .unwrap()
                 .set_toggle(CONFIG_WIDGET_DISABLED, !state);
 
             get_widget_by_name(widgets, "MiniBox4".to_string())
+                .unwrap()
                 .set_toggle(CONFIG_WIDGET_DISABLED, !state);
         });
 
-        self.pushrod.borrow_mut().add_widget_to_layout_manager(
-            "BoxInLayoutWidgetButton2",
-            Box::new(button3),
-            base_layout_id,
-            make_origin_point(),
-        );
+        self.pushrod
+            .borrow_mut()
+            .add_widget_to_layout_manager(
+                "BoxInLayoutWidgetButton2",
+                Box::new(button3),
+                base_layout_id,
+                make_origin_point(),
+            )
+            .unwrap();
 
         let mut button5 = PushButtonWidget::new(
             "assets/OpenSans-Regular.ttf".to_string(),
@@ -680,23 +714,28 @@ impl SimpleWindow {
         button5.set_numeric(CONFIG_BORDER_WIDTH, 2);
         button5.set_color(CONFIG_BORDER_COLOR, [0.0, 0.0, 0.0, 1.0]);
         button5.get_callbacks().on_click(|_x, widgets| {
-            get_widget_by_name(widgets, "BoxInLayoutWidget3".to_string()).set_config(
-                CONFIG_MAIN_COLOR,
-                Config::Color([
-                    (rand::random::<u8>() as f32 / 255.0),
-                    (rand::random::<u8>() as f32 / 255.0),
-                    (rand::random::<u8>() as f32 / 255.0),
-                    1.0,
-                ]),
-            );
+            get_widget_by_name(widgets, "BoxInLayoutWidget3".to_string())
+                .unwrap()
+                .set_config(
+                    CONFIG_MAIN_COLOR,
+                    Config::Color([
+                        (rand::random::<u8>() as f32 / 255.0),
+                        (rand::random::<u8>() as f32 / 255.0),
+                        (rand::random::<u8>() as f32 / 255.0),
+                        1.0,
+                    ]),
+                );
         });
 
-        self.pushrod.borrow_mut().add_widget_to_layout_manager(
-            "BoxInLayoutWidgetButton3",
-            Box::new(button5),
-            base_layout_id,
-            make_origin_point(),
-        );
+        self.pushrod
+            .borrow_mut()
+            .add_widget_to_layout_manager(
+                "BoxInLayoutWidgetButton3",
+                Box::new(button5),
+                base_layout_id,
+                make_origin_point(),
+            )
+            .unwrap();
     }
 
     fn add_vertical_layout(&mut self) {
@@ -706,11 +745,15 @@ impl SimpleWindow {
         base_widget.set_size(CONFIG_BODY_SIZE, 200, 180);
         base_widget.set_color(CONFIG_MAIN_COLOR, [1.0, 1.0, 1.0, 1.0]);
 
-        let base_widget_id = self.pushrod.borrow_mut().add_widget_to_parent_by_name(
-            "MainContainerWidget",
-            "VerticalManagerWidget1",
-            Box::new(base_widget),
-        );
+        let base_widget_id = self
+            .pushrod
+            .borrow_mut()
+            .add_widget_to_parent_by_name(
+                "MainContainerWidget",
+                "VerticalManagerWidget1",
+                Box::new(base_widget),
+            )
+            .unwrap();
 
         let base_layout_id =
             self.pushrod
@@ -733,12 +776,15 @@ impl SimpleWindow {
         progress_widget.set_color(CONFIG_MAIN_COLOR, [1.0, 1.0, 1.0, 1.0]);
         progress_widget.set_color(CONFIG_SECONDARY_COLOR, [0.5, 0.5, 0.5, 1.0]);
         progress_widget.set_numeric(CONFIG_PROGRESS, 50);
-        self.pushrod.borrow_mut().add_widget_to_layout_manager(
-            "ProgressWidget",
-            Box::new(progress_widget),
-            base_layout_id,
-            make_origin_point(),
-        );
+        self.pushrod
+            .borrow_mut()
+            .add_widget_to_layout_manager(
+                "ProgressWidget",
+                Box::new(progress_widget),
+                base_layout_id,
+                make_origin_point(),
+            )
+            .unwrap();
 
         let mut progress_text = TextWidget::new(
             "assets/OpenSans-Regular.ttf".to_string(),
@@ -750,12 +796,15 @@ impl SimpleWindow {
         progress_text.set_point(CONFIG_ORIGIN, 260, 360);
         progress_text.set_size(CONFIG_BODY_SIZE, 50, 32);
         progress_text.set_color(CONFIG_TEXT_COLOR, [0.0, 0.0, 0.0, 1.0]);
-        self.pushrod.borrow_mut().add_widget_to_layout_manager(
-            "ProgressText1",
-            Box::new(progress_text),
-            base_layout_id,
-            make_origin_point(),
-        );
+        self.pushrod
+            .borrow_mut()
+            .add_widget_to_layout_manager(
+                "ProgressText1",
+                Box::new(progress_text),
+                base_layout_id,
+                make_origin_point(),
+            )
+            .unwrap();
 
         let mut button1 = ToggleButtonWidget::new(
             "assets/OpenSans-Regular.ttf".to_string(),
@@ -771,12 +820,15 @@ impl SimpleWindow {
         button1.set_color(CONFIG_BORDER_COLOR, [0.0, 0.0, 0.0, 1.0]);
         button1.set_toggle(CONFIG_SELECTED, true);
 
-        self.pushrod.borrow_mut().add_widget_to_layout_manager(
-            "AnimateButton1",
-            Box::new(button1),
-            base_layout_id,
-            make_origin_point(),
-        );
+        self.pushrod
+            .borrow_mut()
+            .add_widget_to_layout_manager(
+                "AnimateButton1",
+                Box::new(button1),
+                base_layout_id,
+                make_origin_point(),
+            )
+            .unwrap();
 
         let mut button2 = PushButtonWidget::new(
             "assets/OpenSans-Regular.ttf".to_string(),
@@ -791,23 +843,28 @@ impl SimpleWindow {
         button2.set_numeric(CONFIG_BORDER_WIDTH, 2);
         button2.set_color(CONFIG_BORDER_COLOR, [0.0, 0.0, 0.0, 1.0]);
         button2.get_callbacks().on_click(|_x, widgets| {
-            get_widget_by_name(widgets, "ProgressWidget".to_string()).set_config(
-                CONFIG_SECONDARY_COLOR,
-                Config::Color([
-                    (rand::random::<u8>() as f32 / 255.0),
-                    (rand::random::<u8>() as f32 / 255.0),
-                    (rand::random::<u8>() as f32 / 255.0),
-                    1.0,
-                ]),
-            );
+            get_widget_by_name(widgets, "ProgressWidget".to_string())
+                .unwrap()
+                .set_config(
+                    CONFIG_SECONDARY_COLOR,
+                    Config::Color([
+                        (rand::random::<u8>() as f32 / 255.0),
+                        (rand::random::<u8>() as f32 / 255.0),
+                        (rand::random::<u8>() as f32 / 255.0),
+                        1.0,
+                    ]),
+                );
         });
 
-        self.pushrod.borrow_mut().add_widget_to_layout_manager(
-            "RandomColorButton2",
-            Box::new(button2),
-            base_layout_id,
-            make_origin_point(),
-        );
+        self.pushrod
+            .borrow_mut()
+            .add_widget_to_layout_manager(
+                "RandomColorButton2",
+                Box::new(button2),
+                base_layout_id,
+                make_origin_point(),
+            )
+            .unwrap();
     }
 
     fn add_timer(&mut self) {
@@ -815,11 +872,10 @@ impl SimpleWindow {
 
         timer.set_numeric(CONFIG_TIMER_TIMEOUT, 100);
         timer.set_toggle(CONFIG_TIMER_ENABLED, true);
-        self.pushrod.borrow_mut().add_widget_to_parent_by_name(
-            "MainContainerWidget",
-            "TimerWidget1",
-            Box::new(timer),
-        );
+        self.pushrod
+            .borrow_mut()
+            .add_widget_to_parent_by_name("MainContainerWidget", "TimerWidget1", Box::new(timer))
+            .unwrap();
     }
 
     fn add_debugging(&mut self) {
@@ -833,11 +889,14 @@ impl SimpleWindow {
         check_widget.set_point(CONFIG_ORIGIN, 20, 540);
         check_widget.set_size(CONFIG_BODY_SIZE, 500, 28);
         check_widget.set_color(CONFIG_TEXT_COLOR, [0.0, 0.0, 0.0, 1.0]);
-        self.pushrod.borrow_mut().add_widget_to_parent_by_name(
-            "MainContainerWidget",
-            "DebugCheck1",
-            Box::new(check_widget),
-        );
+        self.pushrod
+            .borrow_mut()
+            .add_widget_to_parent_by_name(
+                "MainContainerWidget",
+                "DebugCheck1",
+                Box::new(check_widget),
+            )
+            .unwrap();
 
         let mut text_widget1 = TextWidget::new(
             "assets/OpenSans-Regular.ttf".to_string(),
@@ -848,11 +907,14 @@ impl SimpleWindow {
         text_widget1.set_point(CONFIG_ORIGIN, 20, 570);
         text_widget1.set_size(CONFIG_BODY_SIZE, 500, 28);
         text_widget1.set_color(CONFIG_TEXT_COLOR, [0.0, 0.0, 0.0, 1.0]);
-        self.pushrod.borrow_mut().add_widget_to_parent_by_name(
-            "MainContainerWidget",
-            "DebugText1",
-            Box::new(text_widget1),
-        );
+        self.pushrod
+            .borrow_mut()
+            .add_widget_to_parent_by_name(
+                "MainContainerWidget",
+                "DebugText1",
+                Box::new(text_widget1),
+            )
+            .unwrap();
 
         let mut text_widget2 = TextWidget::new(
             "assets/OpenSans-Regular.ttf".to_string(),
@@ -863,11 +925,14 @@ impl SimpleWindow {
         text_widget2.set_point(CONFIG_ORIGIN, 20, 600);
         text_widget2.set_size(CONFIG_BODY_SIZE, 500, 28);
         text_widget2.set_color(CONFIG_TEXT_COLOR, [0.0, 0.0, 0.0, 1.0]);
-        self.pushrod.borrow_mut().add_widget_to_parent_by_name(
-            "MainContainerWidget",
-            "DebugText2",
-            Box::new(text_widget2),
-        );
+        self.pushrod
+            .borrow_mut()
+            .add_widget_to_parent_by_name(
+                "MainContainerWidget",
+                "DebugText2",
+                Box::new(text_widget2),
+            )
+            .unwrap();
     }
 
     fn add_powered_by(&mut self) {
@@ -885,15 +950,19 @@ impl SimpleWindow {
         image_widget.set_numeric(CONFIG_BORDER_WIDTH, 1);
         image_widget.set_color(CONFIG_BORDER_COLOR, [0.0, 0.0, 0.0, 1.0]);
 
-        self.pushrod.borrow_mut().add_widget_to_parent_by_name(
-            "MainContainerWidget",
-            "RustImageButton",
-            Box::new(image_widget),
-        );
+        self.pushrod
+            .borrow_mut()
+            .add_widget_to_parent_by_name(
+                "MainContainerWidget",
+                "RustImageButton",
+                Box::new(image_widget),
+            )
+            .unwrap();
     }
 
     fn build(&mut self) {
         self.add_hello_world();
+        self.add_text_input();
         self.add_horizontal_layout();
         self.add_horizontal_layout_buttons();
         self.add_vertical_layout();
@@ -915,11 +984,13 @@ impl SimpleWindow {
 }
 
 fn main() {
-    let window: GlfwWindow = WindowSettings::new("Pushrod Window", [800, 640])
+    let pushrod = PushrodBuilder::new()
+        .title("Pushrod Window")
+        .size(800, 640)
         .resizable(true)
         .build()
-        .unwrap_or_else(|error| panic!("Failed to build PistonWindow: {}", error));
-    let mut app_window = SimpleWindow::new(Pushrod::new(window));
+        .unwrap_or_else(|error| panic!("Failed to create window: {}", error));
+    let mut app_window = SimpleWindow::new(pushrod);
 
     app_window.run();
 }
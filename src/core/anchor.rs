@@ -0,0 +1,297 @@
+// Widget Anchors
+// Keeps Widgets attached to the edges of their parent when the parent is resized
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::core::point::{Point, Size};
+
+/// This describes how one edge of a `Widget` follows the same edge of its parent when the parent
+/// is resized.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum AnchorEdge {
+    /// The edge is not attached to the parent.  If the opposite edge is attached, the `Widget`
+    /// keeps its size and moves with that edge.  If neither edge is attached, the `Widget` keeps
+    /// its size, and its center stays at the same proportion of the parent.
+    Free,
+
+    /// The edge keeps its distance from the same edge of the parent.
+    Fixed,
+
+    /// The edge stays at the same proportion of the size of the parent.  For instance, the left
+    /// edge of a `Widget` that starts halfway across its parent stays halfway across it.
+    Proportional,
+}
+
+/// This describes how a `Widget` is moved and resized when its parent is resized, by the
+/// window being resized, or by a `LayoutManager`.  It is set on a `Widget` with `CONFIG_ANCHOR`.
+/// `Widget`s that are positioned by a `LayoutManager` ignore their anchor.
+///
+/// When both the left and right edges (or top and bottom edges) are attached, the `Widget`
+/// stretches along with its parent.  `Widget`s without an anchor use `Anchor::default`, which
+/// attaches the left and top edges, so they keep their origin and size.
+///
+/// Example:
+/// ```no_run
+/// # use pushrod::core::anchor::*;
+/// # use pushrod::widget::box_widget::*;
+/// # use pushrod::widget::config::*;
+/// # use pushrod::widget::widget::*;
+/// // A status bar that spans the bottom of the window.
+/// let mut status_bar = BoxWidget::new();
+///
+/// status_bar.set_point(CONFIG_ORIGIN, 0, 576);
+/// status_bar.set_size(CONFIG_BODY_SIZE, 800, 24);
+/// status_bar.set_anchor(Anchor::new(
+///     AnchorEdge::Fixed,
+///     AnchorEdge::Free,
+///     AnchorEdge::Fixed,
+///     AnchorEdge::Fixed,
+/// ));
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Anchor {
+    pub left: AnchorEdge,
+    pub top: AnchorEdge,
+    pub right: AnchorEdge,
+    pub bottom: AnchorEdge,
+}
+
+impl Anchor {
+    /// Creates an anchor from the setting of each edge, in the order left, top, right, bottom.
+    pub fn new(left: AnchorEdge, top: AnchorEdge, right: AnchorEdge, bottom: AnchorEdge) -> Self {
+        Self {
+            left,
+            top,
+            right,
+            bottom,
+        }
+    }
+
+    /// Creates an anchor that attaches all edges at a fixed distance, so that the `Widget`
+    /// stretches in both directions along with its parent.
+    pub fn fill() -> Self {
+        Self::new(
+            AnchorEdge::Fixed,
+            AnchorEdge::Fixed,
+            AnchorEdge::Fixed,
+            AnchorEdge::Fixed,
+        )
+    }
+
+    /// Creates an anchor that keeps all edges at the same proportion of the parent, so that the
+    /// `Widget` is scaled along with its parent.
+    pub fn proportional() -> Self {
+        Self::new(
+            AnchorEdge::Proportional,
+            AnchorEdge::Proportional,
+            AnchorEdge::Proportional,
+            AnchorEdge::Proportional,
+        )
+    }
+
+    /// Computes the new origin and size of a `Widget` with this anchor, given its current origin
+    /// and size, and the size of its parent before and after it was resized.
+    pub fn apply(
+        &self,
+        origin: &Point,
+        size: &Size,
+        old_parent_size: &Size,
+        new_parent_size: &Size,
+    ) -> (Point, Size) {
+        let (x, w) = Self::apply_axis(
+            self.left,
+            self.right,
+            origin.x,
+            size.w,
+            old_parent_size.w,
+            new_parent_size.w,
+        );
+        let (y, h) = Self::apply_axis(
+            self.top,
+            self.bottom,
+            origin.y,
+            size.h,
+            old_parent_size.h,
+            new_parent_size.h,
+        );
+
+        (Point { x, y }, Size { w, h })
+    }
+
+    /// Computes the new offset and length of a `Widget` along one axis.
+    fn apply_axis(
+        start_edge: AnchorEdge,
+        end_edge: AnchorEdge,
+        offset: i32,
+        length: i32,
+        old_parent: i32,
+        new_parent: i32,
+    ) -> (i32, i32) {
+        // Proportional edges of a parent that had no size cannot be scaled, so they are fixed.
+        let scale = |position: i32| {
+            if old_parent > 0 {
+                (f64::from(position) * f64::from(new_parent) / f64::from(old_parent)).round() as i32
+            } else {
+                position
+            }
+        };
+        let start = match start_edge {
+            AnchorEdge::Free => None,
+            AnchorEdge::Fixed => Some(offset),
+            AnchorEdge::Proportional => Some(scale(offset)),
+        };
+        let end = match end_edge {
+            AnchorEdge::Free => None,
+            AnchorEdge::Fixed => Some(new_parent - (old_parent - (offset + length))),
+            AnchorEdge::Proportional => Some(scale(offset + length)),
+        };
+
+        match (start, end) {
+            (Some(start), Some(end)) => (start, (end - start).max(0)),
+            (Some(start), None) => (start, length),
+            (None, Some(end)) => (end - length, length),
+            (None, None) => (scale(offset * 2 + length) / 2 - length / 2, length),
+        }
+    }
+}
+
+impl Default for Anchor {
+    fn default() -> Self {
+        Self::new(
+            AnchorEdge::Fixed,
+            AnchorEdge::Fixed,
+            AnchorEdge::Free,
+            AnchorEdge::Free,
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use AnchorEdge::*;
+
+    // A pair of edges, with the expected (offset, length) along the x axis when the parent grows
+    // from 100 to 200, and along the y axis when it shrinks from 100 to 50, for a `Widget` at
+    // offset 10, with a length of 20.
+    type EdgeCombination = (AnchorEdge, AnchorEdge, (i32, i32), (i32, i32));
+
+    const EDGE_COMBINATIONS: [EdgeCombination; 9] = [
+        (Fixed, Free, (10, 20), (10, 20)),
+        (Fixed, Fixed, (10, 120), (10, 0)),
+        (Fixed, Proportional, (10, 50), (10, 5)),
+        (Free, Fixed, (110, 20), (-40, 20)),
+        (Free, Free, (30, 20), (0, 20)),
+        (Free, Proportional, (40, 20), (-5, 20)),
+        (Proportional, Free, (20, 20), (5, 20)),
+        (Proportional, Fixed, (20, 110), (5, 0)),
+        (Proportional, Proportional, (20, 40), (5, 10)),
+    ];
+
+    fn apply(anchor: Anchor, old_parent_size: Size, new_parent_size: Size) -> (Point, Size) {
+        anchor.apply(
+            &Point { x: 10, y: 10 },
+            &Size { w: 20, h: 20 },
+            &old_parent_size,
+            &new_parent_size,
+        )
+    }
+
+    #[test]
+    fn horizontal_edges() {
+        for (start, end, (x, w), _) in EDGE_COMBINATIONS.iter() {
+            let anchor = Anchor::new(*start, Fixed, *end, Free);
+
+            assert_eq!(
+                apply(anchor, Size { w: 100, h: 100 }, Size { w: 200, h: 100 }),
+                (Point { x: *x, y: 10 }, Size { w: *w, h: 20 }),
+                "left {:?}, right {:?}",
+                start,
+                end
+            );
+        }
+    }
+
+    #[test]
+    fn vertical_edges() {
+        for (start, end, _, (y, h)) in EDGE_COMBINATIONS.iter() {
+            let anchor = Anchor::new(Fixed, *start, Free, *end);
+
+            assert_eq!(
+                apply(anchor, Size { w: 100, h: 100 }, Size { w: 100, h: 50 }),
+                (Point { x: 10, y: *y }, Size { w: 20, h: *h }),
+                "top {:?}, bottom {:?}",
+                start,
+                end
+            );
+        }
+    }
+
+    #[test]
+    fn both_axes() {
+        for (start, end, (x, w), (y, h)) in EDGE_COMBINATIONS.iter() {
+            let anchor = Anchor::new(*start, *start, *end, *end);
+
+            assert_eq!(
+                apply(anchor, Size { w: 100, h: 100 }, Size { w: 200, h: 50 }),
+                (Point { x: *x, y: *y }, Size { w: *w, h: *h }),
+                "start {:?}, end {:?}",
+                start,
+                end
+            );
+        }
+    }
+
+    #[test]
+    fn default_anchor_keeps_origin_and_size() {
+        assert_eq!(
+            apply(
+                Anchor::default(),
+                Size { w: 100, h: 100 },
+                Size { w: 300, h: 40 }
+            ),
+            (Point { x: 10, y: 10 }, Size { w: 20, h: 20 })
+        );
+    }
+
+    #[test]
+    fn proportional_edges_of_empty_parent() {
+        // A parent that had no size cannot be scaled from, so proportional edges stay in place.
+        assert_eq!(
+            apply(
+                Anchor::proportional(),
+                Size { w: 0, h: 0 },
+                Size { w: 200, h: 50 }
+            ),
+            (Point { x: 10, y: 10 }, Size { w: 20, h: 20 })
+        );
+    }
+
+    #[test]
+    fn round_trip() {
+        let parent_size = Size { w: 100, h: 100 };
+        let resized = Size { w: 250, h: 80 };
+
+        for anchor in [Anchor::default(), Anchor::fill(), Anchor::proportional()].iter() {
+            let (origin, size) = apply(*anchor, parent_size.clone(), resized.clone());
+
+            assert_eq!(
+                anchor.apply(&origin, &size, &resized, &parent_size),
+                (Point { x: 10, y: 10 }, Size { w: 20, h: 20 }),
+                "{:?}",
+                anchor
+            );
+        }
+    }
+}
@@ -0,0 +1,189 @@
+// Window Backends
+// Describes the windows that can drive a Pushrod run loop
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::VecDeque;
+use std::thread::sleep;
+use std::time::Duration;
+
+use glfw_window::GlfwWindow;
+use piston::input::*;
+use piston::window::*;
+
+/// How long `HeadlessWindow` sleeps while the run loop waits for input that never arrives.
+const IDLE_WAIT: Duration = Duration::from_millis(10);
+
+/// Describes a window backend that can drive a `Pushrod` run loop.  Any Piston `Window` can be
+/// used as a backend, as long as it indicates whether or not it provides an OpenGL context that
+/// `Pushrod` can draw into.
+pub trait PushrodWindow: Window {
+    /// Indicates whether or not this backend provides an OpenGL context.  If `false`, the run loop
+    /// still processes events, timers, and custom `Widget` events, but never creates any OpenGL
+    /// resources, and never draws anything.
+    fn has_graphics(&self) -> bool {
+        true
+    }
+
+    /// Stops the window from closing by itself when the user asks to close it.  `Pushrod` handles
+    /// close requests itself, so that they can be vetoed by the application.  Backends that never
+    /// close by themselves do not need to implement this.
+    fn disable_automatic_close(&mut self) {}
+}
+
+impl PushrodWindow for GlfwWindow {
+    fn disable_automatic_close(&mut self) {
+        self.set_automatic_close(false);
+    }
+}
+
+/// This is a window backend that has no display attached to it.  It has a fixed draw size, never
+/// receives any input from the OS, and does not require an OpenGL context, so it can be used to
+/// run a `Pushrod` loop on a machine without a desktop session (for instance, in CI.)  Input can
+/// be scripted by queueing raw Piston `Input`s with `push_input`, which are then received by the
+/// run loop as if they came from the OS.
+///
+/// Each rendered frame is counted.  If a maximum number of frames is set, the window requests
+/// to be closed once that number of frames has been rendered, which ends the run loop.  In lazy
+/// mode, frames are only rendered when a `Widget` needs to be redrawn, and the run loop waits for
+/// input when there is nothing to do.  Since no input arrives unless it is queued, the window can
+/// be set to request to be closed once the input queue is empty and no `Widget` needs to be woken
+/// up, with `set_close_when_idle`.
+///
+/// Example:
+/// ```no_run
+/// # use pushrod::core::backend::*;
+/// # use pushrod::core::callbacks::*;
+/// # use pushrod::core::main::*;
+/// # struct EventHandler {}
+/// # impl PushrodCallbackEvents for EventHandler {}
+/// # fn main() {
+///   let mut window = HeadlessWindow::new(800, 600);
+///
+///   window.set_max_frames(10);
+///
+///   let mut pushrod = Pushrod::new(window);
+///
+///   pushrod.run(&mut EventHandler {});
+/// # }
+/// ```
+pub struct HeadlessWindow {
+    size: Size,
+    should_close: bool,
+    frame_count: u64,
+    max_frames: u64,
+    close_when_idle: bool,
+    input_queue: VecDeque<Input>,
+}
+
+impl HeadlessWindow {
+    /// Constructor.  Requires the width and height of the draw area.
+    pub fn new(width: u32, height: u32) -> Self {
+        Self {
+            size: Size {
+                width: width as f64,
+                height: height as f64,
+            },
+            should_close: false,
+            frame_count: 0,
+            max_frames: 0,
+            close_when_idle: false,
+            input_queue: VecDeque::new(),
+        }
+    }
+
+    /// Sets the number of frames to render before the window requests to be closed.  A value of
+    /// 0 (the default) never closes the window automatically.
+    pub fn set_max_frames(&mut self, max_frames: u64) {
+        self.max_frames = max_frames;
+    }
+
+    /// Sets whether the window requests to be closed when a lazy run loop waits for input, and the
+    /// input queue is empty.  By default, the run loop keeps waiting, as it would with a window
+    /// that receives no input, until it is stopped some other way.
+    pub fn set_close_when_idle(&mut self, close_when_idle: bool) {
+        self.close_when_idle = close_when_idle;
+    }
+
+    /// Retrieves the number of frames that have been rendered so far.
+    pub fn get_frame_count(&self) -> u64 {
+        self.frame_count
+    }
+
+    /// Queues a raw Piston `Input` that will be received by the run loop the next time it polls
+    /// for events.
+    pub fn push_input(&mut self, input: Input) {
+        self.input_queue.push_back(input);
+    }
+}
+
+impl Window for HeadlessWindow {
+    fn set_should_close(&mut self, value: bool) {
+        self.should_close = value;
+    }
+
+    fn should_close(&self) -> bool {
+        self.should_close
+    }
+
+    fn size(&self) -> Size {
+        self.size
+    }
+
+    fn swap_buffers(&mut self) {
+        self.frame_count += 1;
+
+        if self.max_frames > 0 && self.frame_count >= self.max_frames {
+            self.should_close = true;
+        }
+    }
+
+    fn wait_event(&mut self) -> Event {
+        match self.poll_event() {
+            Some(event) => event,
+            None => {
+                if self.close_when_idle {
+                    self.should_close = true;
+                } else {
+                    sleep(IDLE_WAIT);
+                }
+
+                Event::Loop(Loop::Idle(IdleArgs { dt: 0.0 }))
+            }
+        }
+    }
+
+    fn wait_event_timeout(&mut self, timeout: Duration) -> Option<Event> {
+        if self.input_queue.is_empty() {
+            sleep(timeout);
+        }
+
+        self.poll_event()
+    }
+
+    fn poll_event(&mut self) -> Option<Event> {
+        self.input_queue
+            .pop_front()
+            .map(|input| Event::Input(input, None))
+    }
+
+    fn draw_size(&self) -> Size {
+        self.size
+    }
+}
+
+impl PushrodWindow for HeadlessWindow {
+    fn has_graphics(&self) -> bool {
+        false
+    }
+}
@@ -0,0 +1,238 @@
+// Pushrod Builder
+// Creates a window and its run loop in a single call
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::core::backend::*;
+use crate::core::error::PushrodError;
+use crate::core::main::*;
+
+use glfw::{Context, SwapInterval, WindowHint};
+use glfw_window::GlfwWindow;
+use graphics::types;
+use opengl_graphics::OpenGL;
+use piston::window::AdvancedWindow;
+use std::cell::RefCell;
+use std::rc::Rc;
+
+/// The last error reported by GLFW, shared with its error callback.
+type GlfwErrorSlot = Rc<RefCell<Option<String>>>;
+
+/// This is a builder that creates a `Pushrod` run loop along with the window it draws into.  It
+/// collects the window settings (title, size, resizability, vsync, and multisampling), the OpenGL
+/// version, the frame pacing of the run loop, and the background color of the base canvas, and
+/// applies them all in a single call to `build`.
+///
+/// Example:
+/// ```no_run
+/// # use pushrod::core::builder::*;
+/// let mut pushrod = PushrodBuilder::new()
+///     .title("Pushrod Window")
+///     .size(800, 600)
+///     .resizable(false)
+///     .max_fps(60)
+///     .build()
+///     .unwrap_or_else(|error| panic!("Failed to create window: {}", error));
+/// ```
+pub struct PushrodBuilder {
+    title: String,
+    width: u32,
+    height: u32,
+    resizable: bool,
+    vsync: bool,
+    samples: u8,
+    opengl: OpenGL,
+    max_fps: u64,
+    ups: Option<u64>,
+    lazy: bool,
+    background_color: Option<types::Color>,
+}
+
+/// Implementation of the `PushrodBuilder`.  Each setting returns the builder, so that settings
+/// can be chained.
+impl PushrodBuilder {
+    /// Creates a new builder with the default settings: an 800x600 resizable window titled
+    /// "Pushrod Window", no vsync or multisampling, OpenGL 3.2, 30 frames per second, and a white
+    /// background.
+    pub fn new() -> Self {
+        Self {
+            title: String::from("Pushrod Window"),
+            width: 800,
+            height: 600,
+            resizable: true,
+            vsync: false,
+            samples: 0,
+            opengl: DEFAULT_OPENGL,
+            max_fps: DEFAULT_MAX_FPS,
+            ups: None,
+            lazy: false,
+            background_color: None,
+        }
+    }
+
+    /// Sets the title of the window.
+    pub fn title(mut self, title: &str) -> Self {
+        self.title = String::from(title);
+        self
+    }
+
+    /// Sets the size of the window, and of the base canvas `Widget`.
+    pub fn size(mut self, width: u32, height: u32) -> Self {
+        self.width = width;
+        self.height = height;
+        self
+    }
+
+    /// Sets whether or not the window can be resized by the user.
+    pub fn resizable(mut self, resizable: bool) -> Self {
+        self.resizable = resizable;
+        self
+    }
+
+    /// Sets whether or not buffer swaps are synchronized to the vertical refresh of the display.
+    pub fn vsync(mut self, vsync: bool) -> Self {
+        self.vsync = vsync;
+        self
+    }
+
+    /// Sets the number of samples used for multisample anti-aliasing.  0 disables it.
+    pub fn samples(mut self, samples: u8) -> Self {
+        self.samples = samples;
+        self
+    }
+
+    /// Sets the OpenGL version of the window's context, which is also used to draw the window.
+    pub fn opengl(mut self, opengl: OpenGL) -> Self {
+        self.opengl = opengl;
+        self
+    }
+
+    /// Sets the maximum number of frames drawn per second.  See `Pushrod::set_max_fps`.
+    pub fn max_fps(mut self, max_fps: u64) -> Self {
+        self.max_fps = max_fps;
+        self
+    }
+
+    /// Sets the number of update events processed per second.  See `Pushrod::set_ups`.
+    pub fn ups(mut self, ups: u64) -> Self {
+        self.ups = Some(ups);
+        self
+    }
+
+    /// Enables or disables lazy mode.  See `Pushrod::set_lazy`.
+    pub fn lazy(mut self, lazy: bool) -> Self {
+        self.lazy = lazy;
+        self
+    }
+
+    /// Sets the background color of the window.  See `Pushrod::set_background_color`.
+    pub fn background_color(mut self, color: types::Color) -> Self {
+        self.background_color = Some(color);
+        self
+    }
+
+    /// Creates the window, its `WidgetStore`, and its run loop.  Returns a `PushrodError::Window`
+    /// if GLFW could not be initialized (for instance, if no display is available), or if the
+    /// window could not be created (for instance, if the requested OpenGL version is not
+    /// supported.)
+    pub fn build(self) -> Result<Pushrod<GlfwWindow>, PushrodError> {
+        // GLFW reports errors through a callback, which is made to record them instead of
+        // panicking, so that they can be returned.
+        let last_error: GlfwErrorSlot = Rc::new(RefCell::new(None));
+        let mut glfw = glfw::init(Some(glfw::Callback {
+            f: record_glfw_error as fn(glfw::Error, String, &GlfwErrorSlot),
+            data: Rc::clone(&last_error),
+        }))
+        .map_err(|error| window_error(&last_error, error.to_string()))?;
+        let (major, minor) = self.opengl.get_major_minor();
+
+        glfw.window_hint(WindowHint::ContextVersion(major as u32, minor as u32));
+        glfw.window_hint(WindowHint::Resizable(self.resizable));
+        glfw.window_hint(WindowHint::SRgbCapable(true));
+
+        if self.opengl >= OpenGL::V3_2 {
+            if cfg!(target_os = "macos") {
+                glfw.window_hint(WindowHint::OpenGlForwardCompat(true));
+            }
+
+            glfw.window_hint(WindowHint::OpenGlProfile(glfw::OpenGlProfileHint::Core));
+        }
+
+        if self.samples != 0 {
+            glfw.window_hint(WindowHint::Samples(Some(u32::from(self.samples))));
+        }
+
+        let (mut window, events) = glfw
+            .create_window(
+                self.width,
+                self.height,
+                &self.title,
+                glfw::WindowMode::Windowed,
+            )
+            .ok_or_else(|| window_error(&last_error, String::from("Failed to create window.")))?;
+
+        window.make_current();
+        glfw.set_swap_interval(if self.vsync {
+            SwapInterval::Sync(1)
+        } else {
+            SwapInterval::None
+        });
+        gl::load_with(|symbol| window.get_proc_address(symbol) as *const _);
+
+        let mut window = GlfwWindow::from_pieces(window, glfw, events, false);
+
+        window.set_title(self.title.clone());
+
+        Ok(self.build_with_window(window))
+    }
+
+    /// Creates a run loop for an existing window backend, such as a `HeadlessWindow`, applying the
+    /// OpenGL version, frame pacing, and background color settings.  The window settings of this
+    /// builder are not used.
+    pub fn build_with_window<W: PushrodWindow>(self, window: W) -> Pushrod<W> {
+        let mut pushrod = Pushrod::new(window);
+
+        pushrod.set_opengl_version(self.opengl);
+        pushrod.set_max_fps(self.max_fps);
+        pushrod.set_lazy(self.lazy);
+
+        if let Some(ups) = self.ups {
+            pushrod.set_ups(ups);
+        }
+
+        if let Some(color) = self.background_color {
+            pushrod.set_background_color(color);
+        }
+
+        pushrod
+    }
+}
+
+/// Records an error reported by GLFW, replacing any previous error.
+fn record_glfw_error(_: glfw::Error, description: String, last_error: &GlfwErrorSlot) {
+    *last_error.borrow_mut() = Some(description);
+}
+
+/// Creates a `PushrodError::Window`, using the last error reported by GLFW as the reason if there
+/// was one, or the `fallback` reason otherwise.
+fn window_error(last_error: &GlfwErrorSlot, fallback: String) -> PushrodError {
+    PushrodError::Window {
+        reason: last_error.borrow_mut().take().unwrap_or(fallback),
+    }
+}
+
+impl Default for PushrodBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
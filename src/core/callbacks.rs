@@ -14,6 +14,7 @@
 // limitations under the License.
 
 use crate::core::point::{Point, Size};
+use crate::core::widget_id::WidgetId;
 use crate::core::widget_store::*;
 use piston::input::*;
 
@@ -44,8 +45,12 @@ pub trait PushrodCallbackEvents {
                 key,
                 state,
             } => self.key_pressed(widget_id, key, state, widget_store),
+            CallbackEvent::TextEntered { widget_id, text } => {
+                self.text_entered(widget_id, text, widget_store)
+            }
             CallbackEvent::WindowResized { size } => self.window_resized(size, widget_store),
             CallbackEvent::WindowFocused { flag } => self.window_focused(flag, widget_store),
+            CallbackEvent::WindowCloseRequested => self.window_close_requested(widget_store),
             CallbackEvent::MouseButtonDown { widget_id, button } => {
                 self.mouse_button_down(widget_id, button, widget_store)
             }
@@ -72,6 +77,18 @@ pub trait PushrodCallbackEvents {
             CallbackEvent::TimerTriggered { widget_id } => {
                 self.timer_triggered(widget_id, widget_store)
             }
+            CallbackEvent::WidgetTextChanged { widget_id, text } => {
+                self.widget_text_changed(widget_id, text, widget_store)
+            }
+            CallbackEvent::WidgetTextSubmitted { widget_id, text } => {
+                self.widget_text_submitted(widget_id, text, widget_store)
+            }
+            CallbackEvent::WidgetFocusGained { widget_id } => {
+                self.widget_focus_gained(widget_id, widget_store)
+            }
+            CallbackEvent::WidgetFocusLost { widget_id } => {
+                self.widget_focus_lost(widget_id, widget_store)
+            }
             // Radio button deselection is handled internally by the `RadioButtonWidget`, and
             // are specifically filtered out here.  If you wish to respond to this widget, you must
             // implement the handle_event function yourself.
@@ -83,27 +100,51 @@ pub trait PushrodCallbackEvents {
     }
 
     /// Called when a mouse enters a `Widget`.
-    fn mouse_entered(&mut self, _widget_id: i32, _widget_store: &mut WidgetStore) {}
+    fn mouse_entered(&mut self, _widget_id: WidgetId, _widget_store: &mut WidgetStore) {}
 
     /// Called when a mouse exits a `Widget`.
-    fn mouse_exited(&mut self, _widget_id: i32, _widget_store: &mut WidgetStore) {}
+    fn mouse_exited(&mut self, _widget_id: WidgetId, _widget_store: &mut WidgetStore) {}
 
     /// Called when a mouse scroll wheel is used inside a `Widget`.
-    fn mouse_scrolled(&mut self, _widget_id: i32, _point: Point, _widget_store: &mut WidgetStore) {}
+    fn mouse_scrolled(
+        &mut self,
+        _widget_id: WidgetId,
+        _point: Point,
+        _widget_store: &mut WidgetStore,
+    ) {
+    }
 
     /// Called when a mouse moves inside a `Widget`.
-    fn mouse_moved(&mut self, _widget_id: i32, _point: Point, _widget_store: &mut WidgetStore) {}
+    fn mouse_moved(
+        &mut self,
+        _widget_id: WidgetId,
+        _point: Point,
+        _widget_store: &mut WidgetStore,
+    ) {
+    }
 
     /// Called when a keyboard keypress is detected.  The state of the key press is passed as well.
+    /// The ID is that of the `Widget` that has keyboard focus, or `WidgetId::NONE` if no `Widget`
+    /// has focus.
     fn key_pressed(
         &mut self,
-        _widget_id: i32,
+        _widget_id: WidgetId,
         _key: Key,
         _state: ButtonState,
         _widget_store: &mut WidgetStore,
     ) {
     }
 
+    /// Called when text is typed on the keyboard.  The ID is that of the `Widget` that has keyboard
+    /// focus, or `WidgetId::NONE` if no `Widget` has focus.
+    fn text_entered(
+        &mut self,
+        _widget_id: WidgetId,
+        _text: String,
+        _widget_store: &mut WidgetStore,
+    ) {
+    }
+
     /// Called when the main window is resized.
     fn window_resized(&mut self, _size: crate::core::point::Size, _widget_store: &mut WidgetStore) {
     }
@@ -111,10 +152,15 @@ pub trait PushrodCallbackEvents {
     /// Called when the window gains or loses focus.
     fn window_focused(&mut self, _flag: bool, _widget_store: &mut WidgetStore) {}
 
+    /// Called when the user requests that the main window be closed.  The run loop quits with an
+    /// exit status of 0 after this callback returns, unless the request is vetoed by calling
+    /// `widget_store.cancel_quit()`.  (For instance, to ask the user to save their changes first.)
+    fn window_close_requested(&mut self, _widget_store: &mut WidgetStore) {}
+
     /// Called when a mouse button is pressed.
     fn mouse_button_down(
         &mut self,
-        _widget_id: i32,
+        _widget_id: WidgetId,
         _button: Button,
         _widget_store: &mut WidgetStore,
     ) {
@@ -123,7 +169,7 @@ pub trait PushrodCallbackEvents {
     /// Called when a mouse button is released within the same `Widget` which it was pressed.
     fn mouse_button_up_inside(
         &mut self,
-        _widget_id: i32,
+        _widget_id: WidgetId,
         _button: Button,
         _widget_store: &mut WidgetStore,
     ) {
@@ -133,7 +179,7 @@ pub trait PushrodCallbackEvents {
     /// pressed.
     fn mouse_button_up_outside(
         &mut self,
-        _widget_id: i32,
+        _widget_id: WidgetId,
         _button: Button,
         _widget_store: &mut WidgetStore,
     ) {
@@ -143,7 +189,7 @@ pub trait PushrodCallbackEvents {
     /// button down and release within the confines of the same `Widget`.
     fn widget_clicked(
         &mut self,
-        _widget_id: i32,
+        _widget_id: WidgetId,
         _button: Button,
         _widget_store: &mut WidgetStore,
     ) {
@@ -153,7 +199,7 @@ pub trait PushrodCallbackEvents {
     /// of the main run loop.  This `Widget` is generated by event injection.
     fn widget_selected(
         &mut self,
-        _widget_id: i32,
+        _widget_id: WidgetId,
         _button: Button,
         _selected: bool,
         _widget_store: &mut WidgetStore,
@@ -161,16 +207,54 @@ pub trait PushrodCallbackEvents {
     }
 
     /// Called when a `Widget` is moved.  Contains the ID of the `Widget`, along with its new
-    /// position as a `Point`.
-    fn widget_moved(&mut self, _widget_id: i32, _point: Point, _widget_store: &mut WidgetStore) {}
+    /// position as a `Point`, relative to its parent.
+    fn widget_moved(
+        &mut self,
+        _widget_id: WidgetId,
+        _point: Point,
+        _widget_store: &mut WidgetStore,
+    ) {
+    }
 
     /// Called when a `Widget` is resized.  Contains the ID of the `Widget`, along with its new
     /// `Size`.
-    fn widget_resized(&mut self, _widget_id: i32, _size: Size, _widget_store: &mut WidgetStore) {}
+    fn widget_resized(
+        &mut self,
+        _widget_id: WidgetId,
+        _size: Size,
+        _widget_store: &mut WidgetStore,
+    ) {
+    }
 
     /// Called when a timer expires for a widget.  The ID of the widget is the timer widget that
     /// generated the expiration timeout.
-    fn timer_triggered(&mut self, _widget_id: i32, _widget_store: &mut WidgetStore) {}
+    fn timer_triggered(&mut self, _widget_id: WidgetId, _widget_store: &mut WidgetStore) {}
+
+    /// Called when the text of an editable `Widget` (such as a `TextInputWidget`) is changed by the
+    /// user.  Contains the ID of the `Widget`, along with its new text.
+    fn widget_text_changed(
+        &mut self,
+        _widget_id: WidgetId,
+        _text: String,
+        _widget_store: &mut WidgetStore,
+    ) {
+    }
+
+    /// Called when the text of an editable `Widget` is submitted, usually by pressing `Enter`.
+    /// Contains the ID of the `Widget`, along with its text.
+    fn widget_text_submitted(
+        &mut self,
+        _widget_id: WidgetId,
+        _text: String,
+        _widget_store: &mut WidgetStore,
+    ) {
+    }
+
+    /// Called when a `Widget` receives keyboard focus.
+    fn widget_focus_gained(&mut self, _widget_id: WidgetId, _widget_store: &mut WidgetStore) {}
+
+    /// Called when a `Widget` loses keyboard focus.
+    fn widget_focus_lost(&mut self, _widget_id: WidgetId, _widget_store: &mut WidgetStore) {}
 }
 
 /// These are the different types of events that can be triggered.  Any other callback events
@@ -179,30 +263,35 @@ pub trait PushrodCallbackEvents {
 pub enum CallbackEvent {
     /// Indicates a mouse entered the bounds of a `Widget`.  Contains the ID of the `Widget` that was
     /// affected.
-    MouseEntered { widget_id: i32 },
+    MouseEntered { widget_id: WidgetId },
 
     /// Indicates a mouse exited the bounds of a `Widget`.  Contains the ID of the `Widget` that was
     /// affected.
-    MouseExited { widget_id: i32 },
+    MouseExited { widget_id: WidgetId },
 
     /// Indicates that the scroll wheel was moved inside a `Widget`.  Contains the ID of the
     /// `Widget` that had the mouse scroll action, and the point in the direction of the scroll,
     /// along with the amount of points the mouse scroll moved.
-    MouseScrolled { widget_id: i32, point: Point },
+    MouseScrolled { widget_id: WidgetId, point: Point },
 
     /// Indicates that a mouse moved within the bounds of a `Widget`.  Contains the ID of the
     /// `Widget` that was affected.
-    MouseMoved { widget_id: i32, point: Point },
+    MouseMoved { widget_id: WidgetId, point: Point },
 
-    /// Indicates that a keyboard key was pressed/released inside the bounds of a `Widget`.  Contains
-    /// the ID of the `Widget` that received the keypress, along with the `Key` value, and any
-    /// associated `Button` modifier states.
+    /// Indicates that a keyboard key was pressed/released while a `Widget` had keyboard focus.
+    /// Contains the ID of the `Widget` that received the keypress (`WidgetId::NONE` if no `Widget`
+    /// has focus), along with the `Key` value, and any associated `Button` modifier states.
     KeyPressed {
-        widget_id: i32,
+        widget_id: WidgetId,
         key: Key,
         state: ButtonState,
     },
 
+    /// Indicates that text was typed on the keyboard while a `Widget` had keyboard focus.  Contains
+    /// the ID of the `Widget` that received the text (`WidgetId::NONE` if no `Widget` has focus),
+    /// along with the Unicode text that was entered.
+    TextEntered { widget_id: WidgetId, text: String },
+
     /// Indicates that the main application window was resized.  Contains the `Size` of the new
     /// bounds.
     WindowResized { size: crate::core::point::Size },
@@ -211,45 +300,67 @@ pub enum CallbackEvent {
     /// boolean flag indicating focus: `true` is focused, `false` if lost.
     WindowFocused { flag: bool },
 
+    /// Indicates that the user requested that the main application window be closed.  The run loop
+    /// quits after this event is handled, unless the application vetoes it by calling
+    /// `WidgetStore::cancel_quit`.
+    WindowCloseRequested,
+
     /// Indicates that a mouse button was pressed within the bounds of a `Widget`.  Contains the
     /// ID of the `Widget`, along with the `Button` that was clicked.
-    MouseButtonDown { widget_id: i32, button: Button },
+    MouseButtonDown { widget_id: WidgetId, button: Button },
 
     /// Indicates that a mouse button was released within the bounds of a `Widget`.  Contains
     /// the ID of the `Widget`, along with the `Button` that was released.
-    MouseButtonUpInside { widget_id: i32, button: Button },
+    MouseButtonUpInside { widget_id: WidgetId, button: Button },
 
     /// Indicates that a mouse button was released outside of the bounds of a `Widget`.  Contains
     /// the ID of the `Widget`, along with the `Button` that was released.
-    MouseButtonUpOutside { widget_id: i32, button: Button },
+    MouseButtonUpOutside { widget_id: WidgetId, button: Button },
 
     /// Indicates that a mouse button triggered a click action within a `Widget`.  Contains the
     /// ID of the `Widget`, along with the `Button` that was used to indicate the click action.
-    WidgetClicked { widget_id: i32, button: Button },
+    WidgetClicked { widget_id: WidgetId, button: Button },
 
     /// Indicates that a `Widget`'s selected state has been toggled.  Contains the ID of the
     /// `Widget` that was toggled, along with the mouse `Button` that was clicked, and the
     /// final `selected` state of the widget.
     WidgetSelected {
-        widget_id: i32,
+        widget_id: WidgetId,
         button: Button,
         selected: bool,
     },
 
     /// Indicates that a `Widget`'s position has been moved either through physical interaction,
     /// or through a `LayoutManager` repositioning.  Contains the ID of the `Widget` that moved,
-    /// along with its new position as a `Point`.
-    WidgetMoved { widget_id: i32, point: Point },
+    /// along with its new position as a `Point`, relative to its parent.  Its children move
+    /// along with it.
+    WidgetMoved { widget_id: WidgetId, point: Point },
 
     /// Indicates that a `Widget`'s `Size` has changed.  Contains the ID of the `Widget`, along with
     /// the new `Size` of the `Widget`.
-    WidgetResized { widget_id: i32, size: Size },
+    WidgetResized { widget_id: WidgetId, size: Size },
 
     /// Indicates that a timer timeout has been triggered.  Contains the ID of the `Widget` that was
     /// affected.
-    TimerTriggered { widget_id: i32 },
+    TimerTriggered { widget_id: WidgetId },
+
+    /// Indicates that the text of an editable `Widget` was changed by the user.  Contains the ID of
+    /// the `Widget`, along with its new text.
+    WidgetTextChanged { widget_id: WidgetId, text: String },
+
+    /// Indicates that the text of an editable `Widget` was submitted, usually by pressing `Enter`.
+    /// Contains the ID of the `Widget`, along with its text.
+    WidgetTextSubmitted { widget_id: WidgetId, text: String },
+
+    /// Indicates that a `Widget` has received keyboard focus.  Contains the ID of the `Widget`
+    /// that gained focus.
+    WidgetFocusGained { widget_id: WidgetId },
+
+    /// Indicates that a `Widget` has lost keyboard focus.  Contains the ID of the `Widget` that
+    /// lost focus.
+    WidgetFocusLost { widget_id: WidgetId },
 
     /// Indicates that a group of `RadioButtonWidget` objects has been deselected.  Contains the
     /// ID of the `Widget` that was unselected, along with the group ID.
-    UnselectRadioButtons { widget_id: i32, group_id: i32 },
+    UnselectRadioButtons { widget_id: WidgetId, group_id: i32 },
 }
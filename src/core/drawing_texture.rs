@@ -16,6 +16,7 @@
 use gl::types::GLuint;
 use opengl_graphics::{Texture, TextureSettings};
 
+use crate::core::error::PushrodError;
 use crate::core::point::Size;
 
 pub struct DrawingTexture {
@@ -26,11 +27,18 @@ pub struct DrawingTexture {
 
 impl DrawingTexture {
     pub fn new() -> Self {
-        Self {
+        Self::try_new().unwrap_or_else(|error| panic!("{}", error))
+    }
+
+    pub fn try_new() -> Result<Self, PushrodError> {
+        let texture = Texture::empty(&TextureSettings::new())
+            .map_err(|reason| PushrodError::Texture { reason })?;
+
+        Ok(Self {
             texture_buffer: vec![0u8; 1],
-            texture: Texture::empty(&TextureSettings::new()).unwrap(),
+            texture,
             fbo: 0,
-        }
+        })
     }
 
     pub fn resize(&mut self, size: Size) {
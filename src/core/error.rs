@@ -0,0 +1,92 @@
+// Pushrod Error
+// Errors that can occur while creating windows, widgets, and their assets
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::core::widget_id::WidgetId;
+
+use std::error::Error;
+use std::fmt;
+use std::path::PathBuf;
+
+/// This is an error that is returned by the fallible constructors in Pushrod, such as
+/// `TextWidget::try_new` and `ImageWidget::try_new`, and by the `WidgetStore` functions that add a
+/// `Widget` to a parent.  Errors that are caused by an asset that could not be loaded name the path
+/// of that asset.
+#[derive(Debug)]
+pub enum PushrodError {
+    /// The font at `path` could not be loaded.
+    FontLoad { path: PathBuf, reason: String },
+
+    /// The image at `path` could not be loaded.
+    ImageLoad { path: PathBuf, reason: String },
+
+    /// The resource named `name` could not be found in any of the `searched` directories, or in
+    /// memory.
+    ResourceNotFound {
+        name: String,
+        searched: Vec<PathBuf>,
+    },
+
+    /// The parent `Widget` does not exist, or has been removed.
+    InvalidParent { parent_id: WidgetId },
+
+    /// No `Widget` has the name `name`.
+    WidgetNotFound { name: String },
+
+    /// No `LayoutManager` has been added with the ID `manager_id`.
+    InvalidLayoutManager { manager_id: i32 },
+
+    /// A texture could not be created on the GPU.
+    Texture { reason: String },
+
+    /// The window could not be created.
+    Window { reason: String },
+}
+
+impl fmt::Display for PushrodError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            PushrodError::FontLoad { path, reason } => {
+                write!(f, "Unable to load font '{}': {}", path.display(), reason)
+            }
+            PushrodError::ImageLoad { path, reason } => {
+                write!(f, "Unable to load image '{}': {}", path.display(), reason)
+            }
+            PushrodError::ResourceNotFound { name, searched } => {
+                let searched: Vec<String> = searched
+                    .iter()
+                    .map(|root| root.display().to_string())
+                    .collect();
+
+                write!(
+                    f,
+                    "Unable to find resource '{}' in [{}]",
+                    name,
+                    searched.join(", ")
+                )
+            }
+            PushrodError::InvalidParent { parent_id } => {
+                write!(f, "Invalid parent widget ID {}", parent_id)
+            }
+            PushrodError::WidgetNotFound { name } => write!(f, "No widget is named '{}'", name),
+            PushrodError::InvalidLayoutManager { manager_id } => {
+                write!(f, "Invalid layout manager ID {}", manager_id)
+            }
+            PushrodError::Texture { reason } => write!(f, "Unable to create texture: {}", reason),
+            PushrodError::Window { reason } => write!(f, "Unable to create window: {}", reason),
+        }
+    }
+}
+
+impl Error for PushrodError {}
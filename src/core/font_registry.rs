@@ -0,0 +1,225 @@
+// Font Registry
+// Loads each font once, and shares its glyph cache between widgets
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::cell::{RefCell, RefMut};
+use std::collections::HashMap;
+use std::rc::Rc;
+
+use opengl_graphics::GlyphCache;
+use rusttype::{GlyphId, Scale};
+
+use crate::core::error::PushrodError;
+use crate::core::resource_loader::ResourceLoader;
+
+/// This is the vertical metrics of a font at a given font size, in pixels.  `ascent` is the
+/// distance from the baseline to the top of the tallest glyph, and `descent` is the distance from
+/// the baseline to the bottom of the lowest glyph, which is usually negative.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct FontMetrics {
+    pub ascent: f64,
+    pub descent: f64,
+    pub line_gap: f64,
+}
+
+impl FontMetrics {
+    /// Retrieves the distance between the baselines of two consecutive lines of text.
+    pub fn get_line_height(&self) -> f64 {
+        self.ascent - self.descent + self.line_gap
+    }
+}
+
+/// This is a font that has been loaded by a `FontRegistry`.  It can be cloned; all clones share
+/// the same `GlyphCache`, so glyphs that have been rendered for one `Widget` are reused by all
+/// other `Widget`s that draw text in the same font.
+#[derive(Clone)]
+pub struct SharedFont {
+    name: String,
+    glyph_cache: Rc<RefCell<GlyphCache<'static>>>,
+}
+
+impl SharedFont {
+    /// Retrieves the name that the font was loaded with.
+    pub fn get_name(&self) -> &str {
+        &self.name
+    }
+
+    /// Borrows the shared `GlyphCache` of the font, which is used to draw text.
+    pub fn get_glyph_cache(&self) -> RefMut<'_, GlyphCache<'static>> {
+        self.glyph_cache.borrow_mut()
+    }
+
+    /// Retrieves the number of pixels that the specified character advances the position of the
+    /// next character, when drawn at `font_size`.
+    pub fn get_advance_width(&self, font_size: u32, ch: char) -> f64 {
+        let glyph_cache = self.glyph_cache.borrow();
+        let scale = Self::scale(font_size);
+        let mut glyph = glyph_cache.font.glyph(ch).scaled(scale);
+
+        // Characters that are missing from the font are drawn as U+FFFD, if the font has it.
+        if glyph.id() == GlyphId(0) && glyph.shape().is_none() {
+            glyph = glyph_cache.font.glyph('\u{FFFD}').scaled(scale);
+        }
+
+        glyph.h_metrics().advance_width as f64
+    }
+
+    /// Retrieves the width of the specified text in pixels, when drawn at `font_size`.
+    pub fn get_text_width(&self, font_size: u32, text: &str) -> f64 {
+        text.chars()
+            .map(|ch| self.get_advance_width(font_size, ch))
+            .sum()
+    }
+
+    /// Retrieves the vertical metrics of the font, when drawn at `font_size`.
+    pub fn get_metrics(&self, font_size: u32) -> FontMetrics {
+        let metrics = self
+            .glyph_cache
+            .borrow()
+            .font
+            .v_metrics(Self::scale(font_size));
+
+        FontMetrics {
+            ascent: metrics.ascent as f64,
+            descent: metrics.descent as f64,
+            line_gap: metrics.line_gap as f64,
+        }
+    }
+
+    // Font sizes are given in points, and converted to pixels the same way that the `GlyphCache`
+    // converts them when text is drawn.
+    fn scale(font_size: u32) -> Scale {
+        Scale::uniform((font_size as f32 * 1.333).round())
+    }
+}
+
+struct FontRegistryInner {
+    families: HashMap<String, String>,
+    fonts: HashMap<String, SharedFont>,
+}
+
+/// This is a handle to a registry of fonts, which loads each font only once, and shares it
+/// between all of the `Widget`s that draw text with it.  It can be cloned; all clones share the
+/// same fonts.
+///
+/// Fonts are requested by name, which is either a family name that has been registered with
+/// `register_family`, or the name of a font resource, which is located by the shared
+/// `ResourceLoader`.  All bundled `Widget`s that draw text request their fonts from the registry
+/// returned by `FontRegistry::shared`, which is also returned by `WidgetStore::get_font_registry`.
+///
+/// Example:
+/// ```no_run
+/// # use pushrod::core::font_registry::*;
+/// let fonts = FontRegistry::shared();
+///
+/// fonts.register_family("Open Sans", "OpenSans-Regular.ttf");
+///
+/// let font = fonts.get_font("Open Sans").unwrap();
+/// let width = font.get_text_width(14, "Hello, world!");
+/// let line_height = font.get_metrics(14).get_line_height();
+/// ```
+#[derive(Clone)]
+pub struct FontRegistry {
+    inner: Rc<RefCell<FontRegistryInner>>,
+}
+
+thread_local! {
+    static SHARED_FONT_REGISTRY: FontRegistry = FontRegistry::new();
+}
+
+impl FontRegistry {
+    /// Creates a new registry, with no families registered, and no fonts loaded.
+    pub fn new() -> Self {
+        Self {
+            inner: Rc::new(RefCell::new(FontRegistryInner {
+                families: HashMap::new(),
+                fonts: HashMap::new(),
+            })),
+        }
+    }
+
+    /// Retrieves the registry that is shared by all `Widget`s and `WidgetStore`s on the current
+    /// thread.
+    pub fn shared() -> Self {
+        SHARED_FONT_REGISTRY.with(|registry| registry.clone())
+    }
+
+    /// Registers a family name for the font resource with the specified name, so that the font
+    /// can be requested by its family name.
+    pub fn register_family(&self, family: &str, resource_name: &str) {
+        self.inner
+            .borrow_mut()
+            .families
+            .insert(String::from(family), String::from(resource_name));
+    }
+
+    /// Retrieves the font with the specified family or resource name, loading it if it has not
+    /// already been loaded.  Returns a `PushrodError` if the font cannot be found or loaded.
+    pub fn get_font(&self, name: &str) -> Result<SharedFont, PushrodError> {
+        let resource_name = self.get_resource_name(name);
+
+        if let Some(font) = self.inner.borrow().fonts.get(&resource_name) {
+            return Ok(font.clone());
+        }
+
+        let glyph_cache = ResourceLoader::shared().load_glyph_cache(&resource_name)?;
+        let font = SharedFont {
+            name: resource_name.clone(),
+            glyph_cache: Rc::new(RefCell::new(glyph_cache)),
+        };
+
+        self.inner
+            .borrow_mut()
+            .fonts
+            .insert(resource_name, font.clone());
+
+        Ok(font)
+    }
+
+    /// Indicates whether or not the font with the specified family or resource name has been
+    /// loaded.
+    pub fn is_loaded(&self, name: &str) -> bool {
+        let resource_name = self.get_resource_name(name);
+
+        self.inner.borrow().fonts.contains_key(&resource_name)
+    }
+
+    /// Removes the font with the specified family or resource name from the registry, so that it
+    /// is loaded again the next time it is requested.  `Widget`s that already use the font keep
+    /// their copy.  Returns `true` if the font had been loaded.
+    pub fn unload(&self, name: &str) -> bool {
+        let resource_name = self.get_resource_name(name);
+
+        self.inner
+            .borrow_mut()
+            .fonts
+            .remove(&resource_name)
+            .is_some()
+    }
+
+    fn get_resource_name(&self, name: &str) -> String {
+        self.inner
+            .borrow()
+            .families
+            .get(name)
+            .cloned()
+            .unwrap_or_else(|| String::from(name))
+    }
+}
+
+impl Default for FontRegistry {
+    fn default() -> Self {
+        Self::new()
+    }
+}
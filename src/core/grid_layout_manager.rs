@@ -0,0 +1,528 @@
+// Grid Layout Manager
+// Lays out Widgets in the Rows and Columns of a Grid
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::HashMap;
+
+use crate::core::layout_manager::*;
+use crate::core::point::{Point, Size};
+use crate::core::widget_id::WidgetId;
+
+/// This describes how the size of a row or a column of a `GridLayoutManager` is determined.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum GridTrackSize {
+    /// The row or column is always the specified number of pixels.
+    Fixed(i32),
+
+    /// The row or column is as large as the largest preferred size of the `Widget`s that are
+    /// placed in it, and do not span other rows or columns.
+    Auto,
+
+    /// The row or column receives a share of the space that is left over once the `Fixed` and
+    /// `Auto` rows or columns have been sized, in proportion to the specified weight.
+    Fraction(i32),
+}
+
+// The cell of a `Widget`: (column, row, columns spanned, rows spanned).
+type GridCell = (usize, usize, usize, usize);
+
+// The extent of a `Widget` along the columns or rows: (first track, tracks spanned, size).
+type TrackCell = (usize, usize, i32);
+
+/// This is a `LayoutManager` that places `Widget`s in the cells of a grid.  The position that a
+/// `Widget` is added to the layout with is its cell: `x` is the column, and `y` is the row, both
+/// starting at 0.  A `Widget` can span several rows or columns (see `set_span`), and the size of
+/// each row and column can be fixed, automatic, or a fraction of the remaining space (see
+/// `set_column_size` and `set_row_size`).  Rows and columns that are not given a size take an
+/// equal share of the remaining space.  A `Widget` fills its cell, within its minimum and maximum
+/// size.
+///
+/// The `left`, `right`, `top` and `bottom` padding is kept around the grid, and `spacing` pixels
+/// are kept between each row and each column.
+///
+/// Example:
+/// ```no_run
+/// # use pushrod::core::grid_layout_manager::*;
+/// # use pushrod::core::layout_manager::LayoutManagerPadding;
+/// # use pushrod::core::widget_id::WidgetId;
+/// # let form_widget_id = WidgetId::ROOT;
+/// // A form with labels on the left, and fields that take the remaining width.
+/// let mut grid = GridLayoutManager::new(form_widget_id, LayoutManagerPadding::default());
+///
+/// grid.set_column_size(0, GridTrackSize::Fixed(120));
+/// grid.set_column_size(1, GridTrackSize::Fraction(1));
+///
+/// // The widget in column 0 of row 3 spans both columns.
+/// grid.set_span(0, 3, 2, 1);
+/// ```
+pub struct GridLayoutManager {
+    container_widget_id: WidgetId,
+    padding: LayoutManagerPadding,
+    column_sizes: Vec<GridTrackSize>,
+    row_sizes: Vec<GridTrackSize>,
+    spans: HashMap<(i32, i32), (i32, i32)>,
+}
+
+impl GridLayoutManager {
+    pub fn new(widget_id: WidgetId, padding: LayoutManagerPadding) -> Self {
+        Self {
+            container_widget_id: widget_id,
+            padding,
+            column_sizes: Vec::new(),
+            row_sizes: Vec::new(),
+            spans: HashMap::new(),
+        }
+    }
+
+    /// Sets the size of a column.  Columns that have not been given a size are `Fraction(1)`.
+    pub fn set_column_size(&mut self, column: usize, size: GridTrackSize) {
+        Self::set_track_size(&mut self.column_sizes, column, size);
+    }
+
+    /// Sets the size of a row.  Rows that have not been given a size are `Fraction(1)`.
+    pub fn set_row_size(&mut self, row: usize, size: GridTrackSize) {
+        Self::set_track_size(&mut self.row_sizes, row, size);
+    }
+
+    /// Sets the number of columns and rows that the `Widget` in the specified cell spans.  Spans
+    /// of less than 1 are treated as 1.
+    pub fn set_span(&mut self, column: i32, row: i32, columns: i32, rows: i32) {
+        self.spans
+            .insert((column, row), (columns.max(1), rows.max(1)));
+    }
+
+    /// Retrieves the number of columns and rows that the `Widget` in the specified cell spans.
+    pub fn get_span(&self, column: i32, row: i32) -> (i32, i32) {
+        self.spans.get(&(column, row)).cloned().unwrap_or((1, 1))
+    }
+
+    fn set_track_size(sizes: &mut Vec<GridTrackSize>, index: usize, size: GridTrackSize) {
+        if sizes.len() <= index {
+            sizes.resize(index + 1, GridTrackSize::Fraction(1));
+        }
+
+        sizes[index] = size;
+    }
+
+    /// Computes the size of each track (row or column), given the sizes it was configured with,
+    /// the space available to all tracks, and the `(first track, span, size)` of each `Widget`.
+    fn compute_tracks(
+        configured: &[GridTrackSize],
+        count: usize,
+        available: i32,
+        cells: &[TrackCell],
+    ) -> Vec<i32> {
+        let kinds = Self::get_track_kinds(configured, count);
+        let mut tracks: Vec<i32> = kinds
+            .iter()
+            .enumerate()
+            .map(|(index, kind)| match kind {
+                GridTrackSize::Fixed(size) => (*size).max(0),
+                GridTrackSize::Auto => Self::natural_size(cells, index),
+                GridTrackSize::Fraction(_) => 0,
+            })
+            .collect();
+        let remaining = available - tracks.iter().sum::<i32>();
+        let weights: Vec<i32> = kinds
+            .iter()
+            .map(|kind| match kind {
+                GridTrackSize::Fraction(weight) => *weight,
+                _ => 0,
+            })
+            .collect();
+
+        for (track, share) in tracks
+            .iter_mut()
+            .zip(distribute_by_weight(remaining, &weights))
+        {
+            *track += share;
+        }
+
+        tracks
+    }
+
+    /// Computes the size of each track when every `Widget` is its preferred size: `Fraction`
+    /// tracks are sized like `Auto` tracks.
+    fn compute_natural_tracks(
+        configured: &[GridTrackSize],
+        count: usize,
+        cells: &[TrackCell],
+    ) -> Vec<i32> {
+        Self::get_track_kinds(configured, count)
+            .iter()
+            .enumerate()
+            .map(|(index, kind)| match kind {
+                GridTrackSize::Fixed(size) => (*size).max(0),
+                _ => Self::natural_size(cells, index),
+            })
+            .collect()
+    }
+
+    fn get_track_kinds(configured: &[GridTrackSize], count: usize) -> Vec<GridTrackSize> {
+        (0..count)
+            .map(|x| {
+                configured
+                    .get(x)
+                    .cloned()
+                    .unwrap_or(GridTrackSize::Fraction(1))
+            })
+            .collect()
+    }
+
+    /// Computes the largest size of the `Widget`s that are placed in a track, and do not span
+    /// other tracks.
+    fn natural_size(cells: &[TrackCell], index: usize) -> i32 {
+        cells
+            .iter()
+            .filter(|(first, span, _)| *first == index && *span == 1)
+            .map(|(_, _, size)| *size)
+            .max()
+            .unwrap_or(0)
+            .max(0)
+    }
+
+    /// Computes the cell of each `Widget`, as (column, row, columns spanned, rows spanned), and
+    /// the number of columns and rows in the grid.
+    fn get_cells(&self, coordinates: &LayoutManagerCoordinates) -> (Vec<GridCell>, usize, usize) {
+        let cells: Vec<GridCell> = coordinates
+            .widget_positions
+            .iter()
+            .map(|position| {
+                let (columns, rows) = self.get_span(position.x, position.y);
+
+                (
+                    position.x.max(0) as usize,
+                    position.y.max(0) as usize,
+                    columns as usize,
+                    rows as usize,
+                )
+            })
+            .collect();
+        let num_columns = cells
+            .iter()
+            .map(|cell| cell.0 + cell.2)
+            .chain(std::iter::once(self.column_sizes.len()))
+            .max()
+            .unwrap_or(0);
+        let num_rows = cells
+            .iter()
+            .map(|cell| cell.1 + cell.3)
+            .chain(std::iter::once(self.row_sizes.len()))
+            .max()
+            .unwrap_or(0);
+
+        (cells, num_columns, num_rows)
+    }
+
+    /// Retrieves the `(first track, span, preferred size)` of each `Widget` along the columns
+    /// and the rows of the grid.
+    fn get_track_cells(
+        cells: &[GridCell],
+        coordinates: &LayoutManagerCoordinates,
+    ) -> (Vec<TrackCell>, Vec<TrackCell>) {
+        let column_cells = cells
+            .iter()
+            .enumerate()
+            .map(|(x, cell)| {
+                (
+                    cell.0,
+                    cell.2,
+                    coordinates.get_width_limits(x).get_preferred(),
+                )
+            })
+            .collect();
+        let row_cells = cells
+            .iter()
+            .enumerate()
+            .map(|(x, cell)| {
+                (
+                    cell.1,
+                    cell.3,
+                    coordinates.get_height_limits(x).get_preferred(),
+                )
+            })
+            .collect();
+
+        (column_cells, row_cells)
+    }
+
+    /// Computes the offset of each track from the start of the container.
+    fn compute_offsets(tracks: &[i32], start: i32, spacing: i32) -> Vec<i32> {
+        let mut offsets = Vec::with_capacity(tracks.len());
+        let mut current = start;
+
+        for track in tracks {
+            offsets.push(current);
+            current += track + spacing;
+        }
+
+        offsets
+    }
+
+    /// Computes the size of a cell that starts at the `first` track, and spans `span` tracks.
+    fn span_size(tracks: &[i32], first: usize, span: usize, spacing: i32) -> i32 {
+        tracks[first..first + span].iter().sum::<i32>() + spacing * (span as i32 - 1)
+    }
+}
+
+impl LayoutManager for GridLayoutManager {
+    fn do_layout(
+        &mut self,
+        origin: Point,
+        size: Size,
+        coordinates: LayoutManagerCoordinates,
+    ) -> LayoutManagerCoordinates {
+        let (cells, num_columns, num_rows) = self.get_cells(&coordinates);
+        let spacing = self.padding.spacing;
+        let available_w = size.w
+            - (self.padding.left + self.padding.right)
+            - spacing * (num_columns as i32 - 1).max(0);
+        let available_h = size.h
+            - (self.padding.top + self.padding.bottom)
+            - spacing * (num_rows as i32 - 1).max(0);
+        let (column_cells, row_cells) = Self::get_track_cells(&cells, &coordinates);
+        let columns =
+            Self::compute_tracks(&self.column_sizes, num_columns, available_w, &column_cells);
+        let rows = Self::compute_tracks(&self.row_sizes, num_rows, available_h, &row_cells);
+        let column_offsets = Self::compute_offsets(&columns, origin.x + self.padding.left, spacing);
+        let row_offsets = Self::compute_offsets(&rows, origin.y + self.padding.top, spacing);
+        let mut widget_origins: Vec<Point> = vec![];
+        let mut widget_sizes: Vec<Size> = vec![];
+
+        for (x, (column, row, column_span, row_span)) in cells.into_iter().enumerate() {
+            widget_origins.push(Point {
+                x: column_offsets[column],
+                y: row_offsets[row],
+            });
+            widget_sizes.push(coordinates.constrain_size(
+                x,
+                Size {
+                    w: Self::span_size(&columns, column, column_span, spacing),
+                    h: Self::span_size(&rows, row, row_span, spacing),
+                },
+            ));
+        }
+
+        LayoutManagerCoordinates {
+            widget_origins,
+            widget_sizes,
+            widget_positions: coordinates.widget_positions,
+            ..LayoutManagerCoordinates::default()
+        }
+    }
+
+    fn adjust_layout(&mut self, coordinates: LayoutManagerPadding) {
+        self.padding = coordinates;
+    }
+
+    fn get_preferred_size(&self, coordinates: &LayoutManagerCoordinates) -> Option<Size> {
+        let (cells, num_columns, num_rows) = self.get_cells(coordinates);
+        let (column_cells, row_cells) = Self::get_track_cells(&cells, coordinates);
+        let columns = Self::compute_natural_tracks(&self.column_sizes, num_columns, &column_cells);
+        let rows = Self::compute_natural_tracks(&self.row_sizes, num_rows, &row_cells);
+        let spacing = self.padding.spacing;
+
+        Some(Size {
+            w: columns.iter().sum::<i32>()
+                + (self.padding.left + self.padding.right)
+                + spacing * (num_columns as i32 - 1).max(0),
+            h: rows.iter().sum::<i32>()
+                + (self.padding.top + self.padding.bottom)
+                + spacing * (num_rows as i32 - 1).max(0),
+        })
+    }
+
+    fn get_widget_id(&self) -> WidgetId {
+        self.container_widget_id
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn make_coordinates(positions: &[(i32, i32)]) -> LayoutManagerCoordinates {
+        LayoutManagerCoordinates {
+            widget_origins: vec![Point::default(); positions.len()],
+            widget_sizes: vec![Size::default(); positions.len()],
+            widget_positions: positions
+                .iter()
+                .map(|(x, y)| Point { x: *x, y: *y })
+                .collect(),
+            ..LayoutManagerCoordinates::default()
+        }
+    }
+
+    #[test]
+    fn fixed_tracks() {
+        let configured = [GridTrackSize::Fixed(50), GridTrackSize::Fixed(-5)];
+
+        // Fixed tracks ignore the space that is available, and are never negative.
+        assert_eq!(
+            GridLayoutManager::compute_tracks(&configured, 2, 200, &[]),
+            vec![50, 0]
+        );
+    }
+
+    #[test]
+    fn auto_tracks() {
+        let configured = [GridTrackSize::Auto; 3];
+        let cells = [(0, 1, 40), (0, 1, 60), (1, 1, 30), (0, 2, 500)];
+
+        // The widget that spans two tracks does not size either of them.
+        assert_eq!(
+            GridLayoutManager::compute_tracks(&configured, 3, 200, &cells),
+            vec![60, 30, 0]
+        );
+    }
+
+    #[test]
+    fn fraction_tracks() {
+        let configured = [
+            GridTrackSize::Fixed(40),
+            GridTrackSize::Fraction(1),
+            GridTrackSize::Fraction(2),
+        ];
+
+        assert_eq!(
+            GridLayoutManager::compute_tracks(&configured, 3, 130, &[]),
+            vec![40, 30, 60]
+        );
+
+        // Tracks that are not configured are `Fraction(1)`.
+        assert_eq!(
+            GridLayoutManager::compute_tracks(&[GridTrackSize::Fraction(1)], 3, 100, &[]),
+            vec![34, 33, 33]
+        );
+    }
+
+    #[test]
+    fn fraction_tracks_share_space_left_by_auto_tracks() {
+        let configured = [GridTrackSize::Auto, GridTrackSize::Fraction(1)];
+
+        assert_eq!(
+            GridLayoutManager::compute_tracks(&configured, 2, 100, &[(0, 1, 25)]),
+            vec![25, 75]
+        );
+    }
+
+    #[test]
+    fn span_across_spacing() {
+        let mut grid = GridLayoutManager::new(
+            WidgetId::ROOT,
+            LayoutManagerPadding {
+                spacing: 10,
+                ..LayoutManagerPadding::default()
+            },
+        );
+
+        grid.set_span(0, 0, 2, 1);
+
+        let layout = grid.do_layout(
+            Point::default(),
+            Size { w: 200, h: 100 },
+            make_coordinates(&[(0, 0), (1, 1)]),
+        );
+
+        // Each column and row is (size - spacing) / 2, and the span includes the spacing between
+        // the columns it covers.
+        assert_eq!(
+            layout.widget_origins,
+            vec![Point { x: 0, y: 0 }, Point { x: 105, y: 55 }]
+        );
+        assert_eq!(
+            layout.widget_sizes,
+            vec![Size { w: 200, h: 45 }, Size { w: 95, h: 45 }]
+        );
+    }
+
+    #[test]
+    fn padding_around_grid() {
+        let mut grid = GridLayoutManager::new(
+            WidgetId::ROOT,
+            LayoutManagerPadding {
+                left: 5,
+                right: 15,
+                top: 10,
+                bottom: 20,
+                spacing: 0,
+            },
+        );
+
+        grid.set_column_size(0, GridTrackSize::Fixed(30));
+
+        let layout = grid.do_layout(
+            Point::default(),
+            Size { w: 100, h: 100 },
+            make_coordinates(&[(0, 0), (1, 0)]),
+        );
+
+        assert_eq!(
+            layout.widget_origins,
+            vec![Point { x: 5, y: 10 }, Point { x: 35, y: 10 }]
+        );
+        assert_eq!(
+            layout.widget_sizes,
+            vec![Size { w: 30, h: 70 }, Size { w: 50, h: 70 }]
+        );
+    }
+
+    #[test]
+    fn preferred_size() {
+        let mut grid = GridLayoutManager::new(
+            WidgetId::ROOT,
+            LayoutManagerPadding {
+                left: 2,
+                right: 2,
+                top: 1,
+                bottom: 1,
+                spacing: 4,
+            },
+        );
+        let mut coordinates = make_coordinates(&[(0, 0), (1, 0), (0, 1)]);
+
+        grid.set_column_size(0, GridTrackSize::Fixed(50));
+        grid.set_span(0, 1, 2, 1);
+        coordinates.widget_preferred_sizes = vec![
+            Size { w: 30, h: 20 },
+            Size { w: 70, h: 10 },
+            Size { w: 500, h: 40 },
+        ];
+
+        // Fraction columns are as wide as their widest widget, and the spanning widget does not
+        // widen either column.
+        assert_eq!(
+            grid.get_preferred_size(&coordinates),
+            Some(Size {
+                w: 50 + 70 + 4 + 4,
+                h: 20 + 40 + 4 + 2,
+            })
+        );
+    }
+
+    #[test]
+    fn preferred_size_limited() {
+        let grid = GridLayoutManager::new(WidgetId::ROOT, LayoutManagerPadding::default());
+        let mut coordinates = make_coordinates(&[(0, 0)]);
+
+        coordinates.widget_preferred_sizes = vec![Size { w: 100, h: 10 }];
+        coordinates.widget_min_sizes = vec![Size { w: 0, h: 30 }];
+        coordinates.widget_max_sizes = vec![Size { w: 60, h: 0 }];
+
+        assert_eq!(
+            grid.get_preferred_size(&coordinates),
+            Some(Size { w: 60, h: 30 })
+        );
+    }
+}
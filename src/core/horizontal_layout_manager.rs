@@ -13,21 +13,55 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use std::collections::HashMap;
+
 use crate::core::layout_manager::*;
 use crate::core::point::{Point, Size};
+use crate::core::widget_id::WidgetId;
 
+/// This is a `LayoutManager` that places `Widget`s side by side, from left to right, in the order
+/// that they were added.  Each `Widget` is either a fixed number of pixels wide, its preferred
+/// width, or receives a share of the remaining width in proportion to its stretch weight (see
+/// `set_item_size`), within its minimum and maximum width.  Vertically, `Widget`s fill the height
+/// of the layout, unless they are aligned otherwise (see `set_alignment`).  The padding is kept
+/// around the `Widget`s, and `spacing` pixels are kept between them.
 pub struct HorizontalLayoutManager {
-    container_widget_id: i32,
+    container_widget_id: WidgetId,
     padding: LayoutManagerPadding,
+    alignment: LayoutAlignment,
+    item_sizes: HashMap<usize, LayoutItemSize>,
+    item_alignments: HashMap<usize, LayoutAlignment>,
 }
 
 impl HorizontalLayoutManager {
-    pub fn new(widget_id: i32, padding: LayoutManagerPadding) -> Self {
+    pub fn new(widget_id: WidgetId, padding: LayoutManagerPadding) -> Self {
         Self {
             container_widget_id: widget_id,
             padding,
+            alignment: LayoutAlignment::Stretch,
+            item_sizes: HashMap::new(),
+            item_alignments: HashMap::new(),
         }
     }
+
+    /// Sets how the width of the `Widget` at the specified index is determined.  Indexes
+    /// are in the order that `Widget`s were added to the layout.  `Widget`s that have not been
+    /// given a size are `LayoutItemSize::Stretch(1)`.
+    pub fn set_item_size(&mut self, index: usize, size: LayoutItemSize) {
+        self.item_sizes.insert(index, size);
+    }
+
+    /// Sets how `Widget`s are aligned vertically, unless they have been given an alignment of
+    /// their own.  Defaults to `LayoutAlignment::Stretch`.
+    pub fn set_alignment(&mut self, alignment: LayoutAlignment) {
+        self.alignment = alignment;
+    }
+
+    /// Sets how the `Widget` at the specified index is aligned vertically, overriding the
+    /// alignment set by `set_alignment`.
+    pub fn set_item_alignment(&mut self, index: usize, alignment: LayoutAlignment) {
+        self.item_alignments.insert(index, alignment);
+    }
 }
 
 impl LayoutManager for HorizontalLayoutManager {
@@ -37,52 +71,84 @@ impl LayoutManager for HorizontalLayoutManager {
         size: Size,
         coordinates: LayoutManagerCoordinates,
     ) -> LayoutManagerCoordinates {
-        let num_widgets = coordinates.widget_sizes.len() as i32;
-        let width_per_widget = size.w / num_widgets;
+        let num_widgets = coordinates.widget_sizes.len();
+        let items: Vec<LayoutItemSize> = (0..num_widgets)
+            .map(|x| self.item_sizes.get(&x).cloned().unwrap_or_default())
+            .collect();
+        let available_along = size.w
+            - (self.padding.left + self.padding.right)
+            - self.padding.spacing * (num_widgets as i32 - 1).max(0);
+        let available_across = size.h - (self.padding.top + self.padding.bottom);
+        let limits: Vec<AxisLimits> = (0..num_widgets)
+            .map(|x| coordinates.get_width_limits(x))
+            .collect();
+        let item_sizes = compute_item_sizes(available_along, &items, &limits);
         let mut widget_origins: Vec<Point> = vec![];
         let mut widget_sizes: Vec<Size> = vec![];
-        let mut current_x: i32 = origin.x;
-        let current_y: i32 = origin.y;
+        let mut current_x: i32 = origin.x + self.padding.left;
 
-        for x in 0..num_widgets {
-            if x == 0 {
-                current_x = self.padding.left + origin.x;
-            } else {
-                current_x += width_per_widget + (self.padding.spacing / 2);
-            }
+        for (x, item_size) in item_sizes.into_iter().enumerate() {
+            let alignment = self
+                .item_alignments
+                .get(&x)
+                .cloned()
+                .unwrap_or(self.alignment);
+            let (offset, cross_size) = align_item(
+                available_across,
+                &coordinates.get_height_limits(x),
+                alignment,
+            );
 
             widget_origins.push(Point {
                 x: current_x,
-                y: current_y + self.padding.top,
+                y: origin.y + self.padding.top + offset,
+            });
+            widget_sizes.push(Size {
+                w: item_size,
+                h: cross_size,
             });
 
-            if x == num_widgets - 1 {
-                widget_sizes.push(Size {
-                    w: width_per_widget
-                        - (self.padding.left + self.padding.right)
-                        - (self.padding.spacing / 2),
-                    h: size.h - (self.padding.top + self.padding.bottom),
-                });
-            } else {
-                widget_sizes.push(Size {
-                    w: width_per_widget - (self.padding.spacing / 2),
-                    h: size.h - (self.padding.top + self.padding.bottom),
-                });
-            }
+            current_x += item_size + self.padding.spacing;
         }
 
         LayoutManagerCoordinates {
-            widget_origins: widget_origins.clone(),
-            widget_sizes: widget_sizes.clone(),
-            widget_positions: coordinates.widget_positions.clone(),
+            widget_origins,
+            widget_sizes,
+            widget_positions: coordinates.widget_positions,
+            ..LayoutManagerCoordinates::default()
         }
     }
 
     fn adjust_layout(&mut self, coordinates: LayoutManagerPadding) {
-        self.padding = coordinates.clone()
+        self.padding = coordinates;
+    }
+
+    fn get_preferred_size(&self, coordinates: &LayoutManagerCoordinates) -> Option<Size> {
+        let num_widgets = coordinates.widget_sizes.len();
+        let along: i32 = (0..num_widgets)
+            .map(|x| {
+                let limits = coordinates.get_width_limits(x);
+
+                match self.item_sizes.get(&x).cloned().unwrap_or_default() {
+                    LayoutItemSize::Fixed(size) => limits.clamp(size),
+                    _ => limits.get_preferred(),
+                }
+            })
+            .sum();
+        let across: i32 = (0..num_widgets)
+            .map(|x| coordinates.get_height_limits(x).get_preferred())
+            .max()
+            .unwrap_or(0);
+
+        Some(Size {
+            w: along
+                + (self.padding.left + self.padding.right)
+                + self.padding.spacing * (num_widgets as i32 - 1).max(0),
+            h: across + (self.padding.top + self.padding.bottom),
+        })
     }
 
-    fn get_widget_id(&self) -> i32 {
-        return self.container_widget_id;
+    fn get_widget_id(&self) -> WidgetId {
+        self.container_widget_id
     }
 }
@@ -13,7 +13,10 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use std::cmp::Reverse;
+
 use crate::core::point::{Point, Size};
+use crate::core::widget_id::WidgetId;
 
 /// This class defines the spacing rules and the padding for the layout manager.  The layout
 /// manager must take the rules and honor it during the layout process.
@@ -26,17 +29,160 @@ pub struct LayoutManagerPadding {
     pub spacing: i32,
 }
 
+/// This describes how a `Widget` is sized along the axis of a `HorizontalLayoutManager` (its
+/// width) or a `VerticalLayoutManager` (its height).
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum LayoutItemSize {
+    /// The `Widget` receives a share of the space that is left over once the `Fixed` `Widget`s
+    /// have been sized, in proportion to the specified weight.  A weight of 0 gives the `Widget`
+    /// no space.
+    Stretch(i32),
+
+    /// The `Widget` is always the specified number of pixels.
+    Fixed(i32),
+
+    /// The `Widget` is its preferred size (see `Widget::preferred_size`), such as the width of
+    /// the text of a button.
+    Preferred,
+}
+
+impl Default for LayoutItemSize {
+    fn default() -> Self {
+        LayoutItemSize::Stretch(1)
+    }
+}
+
+/// This describes how a `Widget` is positioned across the axis of a `HorizontalLayoutManager`
+/// (vertically) or a `VerticalLayoutManager` (horizontally).  Unless it is stretched, the
+/// `Widget` is its preferred size across the axis, limited to the space that is available.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum LayoutAlignment {
+    /// The `Widget` is placed at the top or left of the layout.
+    Start,
+
+    /// The `Widget` is centered in the layout.
+    Center,
+
+    /// The `Widget` is placed at the bottom or right of the layout.
+    End,
+
+    /// The `Widget` fills the layout.
+    Stretch,
+}
+
+impl Default for LayoutAlignment {
+    fn default() -> Self {
+        LayoutAlignment::Stretch
+    }
+}
+
 /// This structure is sent to the `LayoutManager` at the time `do_layout` is called.  This
 /// structure contains the layout of all of the `Widget`s by origin, the sizes of each of the
 /// `Widget`s, and their positions relative to the layout manager.  The `widget_positions` do not
 /// need to be actual positions in points - they can be used to denote the positions within the
 /// layout they are to occur - for instance, 0x0, 0x1, 1x0 and 1x1 in a layout, if the layout were
 /// a grid layout.
+///
+/// The preferred, minimum and maximum sizes of each `Widget` are also supplied, so that the
+/// layout can honor them.  These are only used as inputs, and need not be returned by
+/// `do_layout`.  If a list is shorter than `widget_sizes`, the current size of the `Widget` is
+/// its preferred size, and its size is not limited.
 #[derive(Clone, Debug, Default, PartialEq)]
 pub struct LayoutManagerCoordinates {
     pub widget_origins: Vec<Point>,
     pub widget_sizes: Vec<Size>,
     pub widget_positions: Vec<Point>,
+    pub widget_preferred_sizes: Vec<Size>,
+    pub widget_min_sizes: Vec<Size>,
+    pub widget_max_sizes: Vec<Size>,
+}
+
+impl LayoutManagerCoordinates {
+    /// Retrieves the preferred size of the `Widget` at the specified index, without its
+    /// minimum or maximum size applied.
+    pub fn get_preferred_size(&self, index: usize) -> Size {
+        self.widget_preferred_sizes
+            .get(index)
+            .or_else(|| self.widget_sizes.get(index))
+            .cloned()
+            .unwrap_or_default()
+    }
+
+    /// Retrieves the minimum size of the `Widget` at the specified index.
+    pub fn get_min_size(&self, index: usize) -> Size {
+        self.widget_min_sizes
+            .get(index)
+            .cloned()
+            .unwrap_or_default()
+    }
+
+    /// Retrieves the maximum size of the `Widget` at the specified index.  Dimensions that are
+    /// not limited are `i32::MAX`.
+    pub fn get_max_size(&self, index: usize) -> Size {
+        let unlimited = |x: i32| if x > 0 { x } else { i32::MAX };
+        let size = self
+            .widget_max_sizes
+            .get(index)
+            .cloned()
+            .unwrap_or_default();
+
+        Size {
+            w: unlimited(size.w),
+            h: unlimited(size.h),
+        }
+    }
+
+    /// Applies the minimum and maximum size of the `Widget` at the specified index to a size.
+    /// The minimum size wins if the two conflict.
+    pub fn constrain_size(&self, index: usize, size: Size) -> Size {
+        let width = self.get_width_limits(index);
+        let height = self.get_height_limits(index);
+
+        Size {
+            w: width.clamp(size.w),
+            h: height.clamp(size.h),
+        }
+    }
+
+    /// Retrieves the preferred, minimum and maximum widths of the `Widget` at the specified
+    /// index.
+    pub(crate) fn get_width_limits(&self, index: usize) -> AxisLimits {
+        AxisLimits {
+            preferred: self.get_preferred_size(index).w,
+            min: self.get_min_size(index).w,
+            max: self.get_max_size(index).w,
+        }
+    }
+
+    /// Retrieves the preferred, minimum and maximum heights of the `Widget` at the specified
+    /// index.
+    pub(crate) fn get_height_limits(&self, index: usize) -> AxisLimits {
+        AxisLimits {
+            preferred: self.get_preferred_size(index).h,
+            min: self.get_min_size(index).h,
+            max: self.get_max_size(index).h,
+        }
+    }
+}
+
+/// The preferred, minimum and maximum size of a `Widget` along one axis of a layout.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub(crate) struct AxisLimits {
+    pub preferred: i32,
+    pub min: i32,
+    pub max: i32,
+}
+
+impl AxisLimits {
+    /// Limits a size to the minimum and maximum.  The minimum wins if the two conflict.
+    pub fn clamp(&self, size: i32) -> i32 {
+        size.min(self.max).max(self.min).max(0)
+    }
+
+    /// Retrieves the preferred size, limited to the minimum and maximum.
+    pub fn get_preferred(&self) -> i32 {
+        self.clamp(self.preferred)
+    }
 }
 
 /// Describes the behavior of a `LayoutManager`.  Layout Managers do not actually manage any
@@ -44,14 +190,16 @@ pub struct LayoutManagerCoordinates {
 /// coordinates, and the size of the `LayoutManager` that is available.
 pub trait LayoutManager {
     /// This method is called when a new `Widget` is added to the list, or the layout needs to
-    /// be re-computed (due to a resize).  The `origin` passed is the origin of the layout
-    /// manager's top-level `Widget`, as each `LayoutManager` requires a `Widget` against which
-    /// objects can be added.  `size` indicates the `Size` of the `Widget` container, which is
-    /// the total allowed bounds of the objects within its layout area.  `coordinates` are the
-    /// coordinates of the `Widget`s to be resized.  Once the compute of all of the coordinates
-    /// of all of the objects is completed, a new `LayoutManagerCoordinates` object must be
-    /// returned, containing the _new_ coordinates of all of the objects within its bounds.
-    /// See also `HorizontalLayoutManager` and `VerticalLayoutManager` for more info.
+    /// be re-computed (due to a resize).  The `origin` passed is the origin that the `Widget`s
+    /// are positioned against: as the `Widget`s in a layout are children of the layout manager's
+    /// top-level `Widget` (each `LayoutManager` requires a `Widget` against which objects can be
+    /// added), and `Widget` origins are relative to their parent, this is always `0x0`.  `size`
+    /// indicates the `Size` of the `Widget` container, which is the total allowed bounds of the
+    /// objects within its layout area.  `coordinates` are the coordinates of the `Widget`s to be
+    /// resized.  Once the compute of all of the coordinates of all of the objects is completed, a
+    /// new `LayoutManagerCoordinates` object must be returned, containing the _new_ coordinates
+    /// of all of the objects within its bounds.  See also `HorizontalLayoutManager` and
+    /// `VerticalLayoutManager` for more info.
     fn do_layout(
         &mut self,
         origin: Point,
@@ -64,7 +212,324 @@ pub trait LayoutManager {
     /// re-calculated, and the objects are redrawn inside the bounds of the layout box.
     fn adjust_layout(&mut self, coordinates: LayoutManagerPadding);
 
+    /// Computes the size that the container `Widget` would need to be, for every `Widget` in the
+    /// layout to be its preferred size.  This is the preferred size of the container, which is
+    /// used when the container is itself placed in a layout.  `coordinates` contains the current
+    /// and preferred sizes of the `Widget`s in the layout.  Returns `None` if the layout has no
+    /// preferred size, in which case the container's own `preferred_size` is used.
+    fn get_preferred_size(&self, _coordinates: &LayoutManagerCoordinates) -> Option<Size> {
+        None
+    }
+
     /// This function must be overridden to return the ID of the `Widget` that is stored within
     /// the `LayoutManager`, since the system doesn't have direct access to it.
-    fn get_widget_id(&self) -> i32;
+    fn get_widget_id(&self) -> WidgetId;
+}
+
+/// Divides `total` pixels between entries in proportion to their `weights`.  Pixels that are left
+/// over from rounding are handed out one at a time, to the entries with the largest remainders
+/// first, so that the sizes always add up to `total`.  Entries with a weight of 0 receive nothing.
+pub(crate) fn distribute_by_weight(total: i32, weights: &[i32]) -> Vec<i32> {
+    let total_weight: i64 = weights.iter().map(|x| i64::from((*x).max(0))).sum();
+
+    if total <= 0 || total_weight == 0 {
+        return vec![0; weights.len()];
+    }
+
+    let mut shares: Vec<i32> = Vec::with_capacity(weights.len());
+    let mut remainders: Vec<(i64, usize)> = Vec::with_capacity(weights.len());
+
+    for (index, weight) in weights.iter().enumerate() {
+        let scaled = i64::from(total) * i64::from((*weight).max(0));
+
+        shares.push((scaled / total_weight) as i32);
+
+        if *weight > 0 {
+            remainders.push((scaled % total_weight, index));
+        }
+    }
+
+    let leftover = (total - shares.iter().sum::<i32>()) as usize;
+
+    // Stable sort, so that entries with equal remainders receive pixels in order.
+    remainders.sort_by_key(|x| Reverse(x.0));
+
+    for (_, index) in remainders.iter().take(leftover) {
+        shares[*index] += 1;
+    }
+
+    shares
+}
+
+/// Computes the size of each `Widget` along the axis of a layout, given the space available to
+/// all `Widget`s once padding and spacing have been removed.  `Fixed` and `Preferred` `Widget`s
+/// are sized first, and the remaining space is shared between `Stretch` `Widget`s by weight.  A
+/// `Stretch` `Widget` whose share is outside of its minimum or maximum size is held at that size,
+/// and the space is shared out again between the others.
+pub(crate) fn compute_item_sizes(
+    available: i32,
+    items: &[LayoutItemSize],
+    limits: &[AxisLimits],
+) -> Vec<i32> {
+    let mut sizes: Vec<i32> = vec![0; items.len()];
+    let mut frozen: Vec<bool> = vec![true; items.len()];
+
+    for (index, item) in items.iter().enumerate() {
+        match item {
+            LayoutItemSize::Fixed(size) => sizes[index] = limits[index].clamp(*size),
+            LayoutItemSize::Preferred => sizes[index] = limits[index].get_preferred(),
+            LayoutItemSize::Stretch(_) => frozen[index] = false,
+        }
+    }
+
+    // Each pass holds at least one `Widget` at its limit, so this ends within `items.len()`
+    // passes.
+    loop {
+        let remaining = available
+            - sizes
+                .iter()
+                .zip(frozen.iter())
+                .filter(|(_, frozen)| **frozen)
+                .map(|(size, _)| *size)
+                .sum::<i32>();
+        let weights: Vec<i32> = items
+            .iter()
+            .zip(frozen.iter())
+            .map(|(item, frozen)| match item {
+                LayoutItemSize::Stretch(weight) if !*frozen => *weight,
+                _ => 0,
+            })
+            .collect();
+        let shares = distribute_by_weight(remaining, &weights);
+        let violations: Vec<(usize, i32)> = (0..items.len())
+            .filter(|index| !frozen[*index])
+            .map(|index| (index, limits[index].clamp(shares[index]) - shares[index]))
+            .filter(|(_, violation)| *violation != 0)
+            .collect();
+
+        if violations.is_empty() {
+            for index in 0..items.len() {
+                if !frozen[index] {
+                    sizes[index] = shares[index];
+                }
+            }
+
+            return sizes;
+        }
+
+        // Like CSS flexbox: if the `Widget`s held at their limits need more space than they were
+        // given in total, only those held at their minimum are frozen, and vice versa.
+        let total: i32 = violations.iter().map(|(_, violation)| *violation).sum();
+
+        for (index, violation) in violations {
+            if total == 0 || total.signum() == violation.signum() {
+                sizes[index] = shares[index] + violation;
+                frozen[index] = true;
+            }
+        }
+    }
+}
+
+/// Computes the offset and size of a `Widget` across the axis of a layout, given the space that
+/// is available, and the preferred, minimum and maximum sizes of the `Widget`.
+pub(crate) fn align_item(
+    available: i32,
+    limits: &AxisLimits,
+    alignment: LayoutAlignment,
+) -> (i32, i32) {
+    let available = available.max(0);
+    let size = match alignment {
+        LayoutAlignment::Stretch => limits.clamp(available),
+        _ => limits.clamp(limits.preferred.min(available)),
+    };
+
+    match alignment {
+        LayoutAlignment::Start | LayoutAlignment::Stretch => (0, size),
+        LayoutAlignment::Center => ((available - size) / 2, size),
+        LayoutAlignment::End => (available - size, size),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn limits(preferred: i32) -> AxisLimits {
+        AxisLimits {
+            preferred,
+            min: 0,
+            max: i32::MAX,
+        }
+    }
+
+    #[test]
+    fn distribute_evenly() {
+        assert_eq!(distribute_by_weight(90, &[1, 1, 1]), vec![30, 30, 30]);
+    }
+
+    #[test]
+    fn distribute_uneven_weights() {
+        // The pixel left over goes to the entry with the largest remainder.
+        assert_eq!(distribute_by_weight(10, &[1, 2]), vec![3, 7]);
+
+        // Equal remainders hand out the leftover pixels in order.
+        assert_eq!(distribute_by_weight(10, &[1, 1, 1]), vec![4, 3, 3]);
+        assert_eq!(distribute_by_weight(11, &[1, 1, 1]), vec![4, 4, 3]);
+    }
+
+    #[test]
+    fn distribute_adds_up_to_total() {
+        for total in 0..50 {
+            let shares = distribute_by_weight(total, &[3, 5, 7, 1]);
+
+            assert_eq!(shares.iter().sum::<i32>(), total);
+        }
+    }
+
+    #[test]
+    fn distribute_zero_weight() {
+        assert_eq!(distribute_by_weight(10, &[0, 1, 1]), vec![0, 5, 5]);
+        assert_eq!(distribute_by_weight(11, &[1, 0, 1]), vec![6, 0, 5]);
+        assert_eq!(distribute_by_weight(10, &[0, 0]), vec![0, 0]);
+        assert_eq!(distribute_by_weight(10, &[-1, 1]), vec![0, 10]);
+    }
+
+    #[test]
+    fn distribute_no_space() {
+        assert_eq!(distribute_by_weight(0, &[1, 2]), vec![0, 0]);
+        assert_eq!(distribute_by_weight(-10, &[1, 2]), vec![0, 0]);
+        assert!(distribute_by_weight(10, &[]).is_empty());
+    }
+
+    #[test]
+    fn stretch_items_share_by_weight() {
+        let items = [LayoutItemSize::Stretch(1), LayoutItemSize::Stretch(2)];
+
+        assert_eq!(
+            compute_item_sizes(100, &items, &[limits(0), limits(0)]),
+            vec![33, 67]
+        );
+    }
+
+    #[test]
+    fn fixed_and_preferred_items_are_sized_first() {
+        let items = [
+            LayoutItemSize::Fixed(30),
+            LayoutItemSize::Preferred,
+            LayoutItemSize::Stretch(1),
+            LayoutItemSize::Stretch(0),
+        ];
+
+        assert_eq!(
+            compute_item_sizes(100, &items, &[limits(0), limits(20), limits(0), limits(0)]),
+            vec![30, 20, 50, 0]
+        );
+    }
+
+    #[test]
+    fn stretch_items_without_space() {
+        let items = [LayoutItemSize::Fixed(80), LayoutItemSize::Stretch(1)];
+
+        assert_eq!(
+            compute_item_sizes(50, &items, &[limits(0), limits(0)]),
+            vec![80, 0]
+        );
+    }
+
+    #[test]
+    fn align_item_across_layout() {
+        let limits = limits(30);
+
+        assert_eq!(align_item(100, &limits, LayoutAlignment::Start), (0, 30));
+        assert_eq!(align_item(100, &limits, LayoutAlignment::Center), (35, 30));
+        assert_eq!(align_item(100, &limits, LayoutAlignment::End), (70, 30));
+        assert_eq!(align_item(100, &limits, LayoutAlignment::Stretch), (0, 100));
+    }
+
+    #[test]
+    fn align_item_larger_than_layout() {
+        let limits = limits(150);
+
+        assert_eq!(align_item(100, &limits, LayoutAlignment::Start), (0, 100));
+        assert_eq!(align_item(100, &limits, LayoutAlignment::Center), (0, 100));
+        assert_eq!(align_item(100, &limits, LayoutAlignment::End), (0, 100));
+        assert_eq!(align_item(-10, &limits, LayoutAlignment::Stretch), (0, 0));
+    }
+
+    #[test]
+    fn stretch_item_held_at_maximum() {
+        let items = [LayoutItemSize::Stretch(1); 3];
+        let mut item_limits = [limits(0); 3];
+
+        item_limits[0].max = 20;
+
+        assert_eq!(
+            compute_item_sizes(100, &items, &item_limits),
+            vec![20, 40, 40]
+        );
+    }
+
+    #[test]
+    fn stretch_item_held_at_minimum() {
+        let items = [LayoutItemSize::Stretch(1); 3];
+        let mut item_limits = [limits(0); 3];
+
+        item_limits[0].min = 50;
+
+        assert_eq!(
+            compute_item_sizes(100, &items, &item_limits),
+            vec![50, 25, 25]
+        );
+    }
+
+    #[test]
+    fn minimum_items_frozen_first() {
+        let items = [LayoutItemSize::Stretch(1); 3];
+        let mut item_limits = [limits(0); 3];
+
+        // The first item needs 30 more pixels, and the second 20 fewer, so the minimum wins, and
+        // the maximum is only applied once the space has been shared out again.
+        item_limits[0].min = 60;
+        item_limits[1].max = 10;
+
+        assert_eq!(
+            compute_item_sizes(90, &items, &item_limits),
+            vec![60, 10, 20]
+        );
+    }
+
+    #[test]
+    fn maximum_items_frozen_first() {
+        let items = [LayoutItemSize::Stretch(1); 3];
+        let mut item_limits = [limits(0); 3];
+
+        // The first item needs 10 more pixels, and the second 25 fewer, so the maximum wins, and
+        // the minimum is already met once the space has been shared out again.
+        item_limits[0].min = 40;
+        item_limits[1].max = 5;
+
+        assert_eq!(
+            compute_item_sizes(90, &items, &item_limits),
+            vec![43, 5, 42]
+        );
+    }
+
+    #[test]
+    fn fixed_item_limited() {
+        let items = [LayoutItemSize::Fixed(80), LayoutItemSize::Preferred];
+        let item_limits = [
+            AxisLimits {
+                preferred: 0,
+                min: 0,
+                max: 50,
+            },
+            AxisLimits {
+                preferred: 10,
+                min: 30,
+                max: i32::MAX,
+            },
+        ];
+
+        assert_eq!(compute_item_sizes(100, &items, &item_limits), vec![50, 30]);
+    }
 }
@@ -17,12 +17,20 @@ use std::cell::RefCell;
 use std::collections::HashMap;
 use std::collections::HashSet;
 use std::rc::Rc;
+use std::time::{Duration, Instant};
 
+use crate::core::backend::*;
 use crate::core::callbacks::*;
 use crate::core::drawing_texture::*;
+use crate::core::error::PushrodError;
+use crate::core::font_registry::FontRegistry;
 use crate::core::layout_manager::*;
 use crate::core::point::*;
+use crate::core::resource_loader::ResourceLoader;
+use crate::core::texture_cache::TextureCache;
+use crate::core::widget_id::WidgetId;
 use crate::core::widget_store::*;
+use crate::widget::config::*;
 use crate::widget::widget::*;
 
 use glfw_window::GlfwWindow;
@@ -32,35 +40,156 @@ use graphics::*;
 use opengl_graphics::{GlGraphics, OpenGL};
 use piston::event_loop::*;
 use piston::input::*;
-use piston::window::*;
 
-/// This structure is returned when instantiating a new Pushrod main object.
-pub struct Pushrod {
-    window: GlfwWindow,
+/// This structure is returned when instantiating a new Pushrod main object.  It is generic over
+/// the `PushrodWindow` backend that drives the run loop, which defaults to a `GlfwWindow`.
+pub struct Pushrod<W: PushrodWindow = GlfwWindow> {
+    window: W,
     events: Events,
 
     /// This is the `WidgetStore` object that is used to store the `Widget` list in the current
     /// display stack.
     pub widget_store: Rc<RefCell<WidgetStore>>,
-    pub drawing_texture: DrawingTexture,
+
+    /// This is the texture that the `Widget`s are drawn into.  It is only available if the
+    /// window backend provides an OpenGL context.
+    pub drawing_texture: Option<DrawingTexture>,
+
+    last_widget_id: Option<WidgetId>,
+    previous_mouse_position: Point,
+    button_map: HashMap<WidgetId, HashSet<Button>>,
+    shift_pressed: bool,
+    max_fps: u64,
+    lazy: bool,
+    window_focused: bool,
+    opengl: OpenGL,
+    background_color: types::Color,
 }
 
-/// Pushrod implementation.  Create a `Pushrod::new( PistonWindow )` object to create a new
+/// The default maximum number of frames drawn per second.
+pub(crate) const DEFAULT_MAX_FPS: u64 = 30;
+
+/// The default OpenGL version used to draw the window.
+pub(crate) const DEFAULT_OPENGL: OpenGL = OpenGL::V3_2;
+
+/// Pushrod implementation.  Create a `Pushrod::new( PushrodWindow )` object to create a new
 /// main loop.  Only one of these should be set for the entire application runtime.
-impl Pushrod {
-    /// Pushrod Object Constructor.  Takes in a single OpenGL configuration type.
-    pub fn new(window: GlfwWindow) -> Self {
-        let event_settings = EventSettings::new().max_fps(30);
+impl<W: PushrodWindow> Pushrod<W> {
+    /// Pushrod Object Constructor.  Takes in the window backend that drives the run loop.  The
+    /// base canvas `Widget` is sized to the size of the window.  To create the window and the run
+    /// loop in one call, use a `PushrodBuilder`.
+    pub fn new(mut window: W) -> Self {
+        // Buffers are only swapped when a frame has actually been drawn.
+        let event_settings = EventSettings::new()
+            .max_fps(DEFAULT_MAX_FPS)
+            .swap_buffers(false);
+        let drawing_texture = if window.has_graphics() {
+            Some(DrawingTexture::new())
+        } else {
+            None
+        };
+        let window_size = window.size();
+        let widget_store = WidgetStore::new();
+
+        window.disable_automatic_close();
+
+        widget_store.widgets[0].widget.borrow_mut().set_size(
+            CONFIG_BODY_SIZE,
+            window_size.width as i32,
+            window_size.height as i32,
+        );
+
         Self {
             window,
             events: Events::new(event_settings),
-            widget_store: Rc::new(RefCell::new(WidgetStore::new())),
-            drawing_texture: DrawingTexture::new(),
+            widget_store: Rc::new(RefCell::new(widget_store)),
+            drawing_texture,
+            last_widget_id: None,
+            previous_mouse_position: make_origin_point(),
+            button_map: HashMap::new(),
+            shift_pressed: false,
+            max_fps: DEFAULT_MAX_FPS,
+            lazy: false,
+            window_focused: true,
+            opengl: DEFAULT_OPENGL,
+            background_color: [1.0, 1.0, 1.0, 0.0],
         }
     }
 
+    /// Sets the OpenGL version used to draw the window.  This must match the version of the
+    /// context provided by the window backend, and must be set before calling `run`.  Defaults to
+    /// `OpenGL::V3_2`.
+    pub fn set_opengl_version(&mut self, opengl: OpenGL) {
+        self.opengl = opengl;
+    }
+
+    /// Sets the background color of the window.  This is the color of the base canvas `Widget`,
+    /// and the color that the window is cleared to before each frame is drawn.  Defaults to white.
+    pub fn set_background_color(&mut self, color: types::Color) {
+        self.background_color = color;
+
+        let widget_store = self.widget_store.borrow();
+        let mut base_widget = widget_store.widgets[0].widget.borrow_mut();
+
+        base_widget.set_color(CONFIG_MAIN_COLOR, color);
+        base_widget.invalidate();
+    }
+
+    /// Sets the maximum number of frames drawn per second.  Defaults to 30.
+    pub fn set_max_fps(&mut self, max_fps: u64) {
+        self.max_fps = max_fps.max(1);
+        self.events.set_max_fps(self.max_fps);
+    }
+
+    /// Sets the number of update events per second that the run loop processes between frames.
+    /// Update events are not used by `Pushrod` itself; a value of 0 disables them.  Defaults to
+    /// 120.  This setting is not used in lazy mode.
+    pub fn set_ups(&mut self, ups: u64) {
+        self.events.set_ups(ups);
+    }
+
+    /// Enables or disables lazy mode.  In lazy mode, the run loop sleeps until an input event
+    /// arrives, a timer is due, or a `Widget` is invalidated, and only then draws a frame (still
+    /// limited to the maximum frame rate.)  Drawing is paused while the window is unfocused or
+    /// minimized.  This saves CPU for applications that are mostly idle.  Defaults to `false`.
+    pub fn set_lazy(&mut self, lazy: bool) {
+        self.lazy = lazy;
+    }
+
+    /// Retrieves a `QuitHandle`, which can be moved into `Widget` callbacks to quit the run loop.
+    /// See `WidgetStore::quit`.
+    pub fn get_quit_handle(&self) -> QuitHandle {
+        self.widget_store.borrow().get_quit_handle()
+    }
+
+    /// Retrieves the `ResourceLoader` used to locate fonts and images.  See
+    /// `WidgetStore::get_resource_loader`.
+    pub fn get_resource_loader(&self) -> ResourceLoader {
+        self.widget_store.borrow().get_resource_loader()
+    }
+
+    /// Retrieves the `FontRegistry` used to load fonts.  See `WidgetStore::get_font_registry`.
+    pub fn get_font_registry(&self) -> FontRegistry {
+        self.widget_store.borrow().get_font_registry()
+    }
+
+    /// Retrieves the `TextureCache` used to load images.  See `WidgetStore::get_texture_cache`.
+    pub fn get_texture_cache(&self) -> TextureCache {
+        self.widget_store.borrow().get_texture_cache()
+    }
+
+    /// Indicates whether or not the run loop has been asked to quit.
+    fn is_quitting(&self) -> bool {
+        self.widget_store.borrow().get_quit_handle().is_quitting()
+    }
+
+    /// Retrieves the window backend that drives this run loop.
+    pub fn get_window(&mut self) -> &mut W {
+        &mut self.window
+    }
+
     /// Convenience method that adds a `Widget` to the GUI display stack.
-    pub fn add_widget(&mut self, name: &str, widget: Box<dyn Widget>) -> i32 {
+    pub fn add_widget(&mut self, name: &str, widget: Box<dyn Widget>) -> WidgetId {
         self.widget_store.borrow_mut().add_widget(name, widget)
     }
 
@@ -70,50 +199,56 @@ impl Pushrod {
     }
 
     /// Convenience method that adds a `Widget` to a `LayoutManager` by the manager's ID and
-    /// the positioning of the `Widget`.
+    /// the positioning of the `Widget`.  Returns an error, without adding the `Widget`, if the
+    /// manager ID is not valid.
     pub fn add_widget_to_layout_manager(
         &mut self,
         name: &str,
         widget: Box<dyn Widget>,
         manager_id: i32,
         position: Point,
-    ) -> i32 {
+    ) -> Result<WidgetId, PushrodError> {
         let widget_id = self
             .widget_store
             .borrow_mut()
-            .add_widget_to_layout_manager(name, widget, manager_id, position);
+            .add_widget_to_layout_manager(name, widget, manager_id, position)?;
 
         self.widget_store
             .borrow_mut()
             .do_layout_for_manager(manager_id);
 
-        widget_id
+        Ok(widget_id)
     }
 
     /// Convenience method that adds a `Widget` to a parent by its ID.  This guarantees a refresh
-    /// if the top level parent becomes invalidated.
+    /// if the top level parent becomes invalidated.  Returns an error, without adding the
+    /// `Widget`, if the parent ID is not valid.
     pub fn add_widget_to_parent(
         &mut self,
         name: &str,
         widget: Box<dyn Widget>,
-        parent_id: i32,
-    ) -> i32 {
+        parent_id: WidgetId,
+    ) -> Result<WidgetId, PushrodError> {
         self.widget_store
             .borrow_mut()
             .add_widget_to_parent(name, widget, parent_id)
     }
 
-    /// Convenience method that adds a `Widget` to a parent by the parent's name.
+    /// Convenience method that adds a `Widget` to a parent by the parent's name.  If no `Widget`
+    /// has that name, the `Widget` is not added, and `PushrodError::WidgetNotFound` is returned.
     pub fn add_widget_to_parent_by_name(
         &mut self,
         parent_name: &str,
         name: &str,
         widget: Box<dyn Widget>,
-    ) -> i32 {
+    ) -> Result<WidgetId, PushrodError> {
         let parent_id = self
             .widget_store
             .borrow_mut()
-            .get_widget_id_for_name(parent_name);
+            .get_widget_id_for_name(parent_name)
+            .ok_or_else(|| PushrodError::WidgetNotFound {
+                name: parent_name.to_string(),
+            })?;
 
         self.widget_store
             .borrow_mut()
@@ -135,24 +270,25 @@ impl Pushrod {
         }
     }
 
-    /// Handles a specific event generated by the OS or the GUI interaction.
+    /// Handles a specific event generated by the OS or the GUI interaction.  Events for `Widget`s
+    /// that have been removed from the `WidgetStore` are dropped.
     fn handle_event(
         &mut self,
-        widget_id: i32,
+        widget_id: WidgetId,
         event_handler: &mut dyn PushrodCallbackEvents,
         event: CallbackEvent,
     ) {
-        if widget_id == -1 {
+        if !self.widget_store.borrow().is_valid_widget_id(widget_id) {
             return;
         }
 
-        let handles_events = self.widget_store.borrow().widgets[widget_id as usize]
+        let handles_events = self.widget_store.borrow().widgets[widget_id.index()]
             .widget
             .borrow_mut()
             .handles_events();
 
         let injectable_event = if handles_events {
-            self.widget_store.borrow().widgets[widget_id as usize]
+            self.widget_store.borrow().widgets[widget_id.index()]
                 .widget
                 .borrow_mut()
                 .handle_event(
@@ -191,12 +327,14 @@ impl Pushrod {
     fn rebuild_gl_buffers(&mut self) {
         let draw_size = self.window.draw_size();
 
-        self.drawing_texture.resize(crate::core::point::Size {
-            w: draw_size.width as i32,
-            h: draw_size.height as i32,
-        });
+        if let Some(drawing_texture) = &mut self.drawing_texture {
+            drawing_texture.resize(crate::core::point::Size {
+                w: draw_size.width as i32,
+                h: draw_size.height as i32,
+            });
 
-        eprintln!("Rebuild of OpenGL buffers for rendering complete.");
+            eprintln!("Rebuild of OpenGL buffers for rendering complete.");
+        }
     }
 
     fn get_system_events_list(&mut self) -> Vec<CallbackEvent> {
@@ -220,284 +358,650 @@ impl Pushrod {
         return_list
     }
 
-    /// This is the main run loop for `Pushrod`.  A run loop requires the use of an assigned
-    /// `PushrodCallbackEvents` event handler.  This is how all communications take place when
-    /// an action occurs within the GUI window.
-    pub fn run(&mut self, event_handler: &mut dyn PushrodCallbackEvents) {
-        let mut last_widget_id = -1;
-        let mut previous_mouse_position: Point = make_origin_point();
-        let mut button_map: HashMap<i32, HashSet<Button>> = HashMap::new();
-        let injectable_map: Vec<i32> = Rc::clone(&self.widget_store)
+    fn handle_system_events(&mut self, event_handler: &mut dyn PushrodCallbackEvents) {
+        let events_list = self.get_system_events_list();
+
+        for event in events_list {
+            self.handle_system_event(event_handler, event.clone());
+        }
+    }
+
+    fn get_injectable_map(&self) -> Vec<WidgetId> {
+        self.widget_store
             .borrow()
             .widgets
             .iter()
             .filter(|x| x.widget.borrow_mut().injects_custom_events())
             .map(|x| x.widget_id)
-            .collect();
-        let mut gl: GlGraphics = GlGraphics::new(OpenGL::V3_2);
+            .collect()
+    }
 
-        eprintln!("Injectable Map: {:?}", injectable_map);
-        eprintln!("Window Size: {:?}", self.window.size());
-        eprintln!("Draw Size: {:?}", self.window.draw_size());
+    /// Handles the input portion of an `Event`: mouse movement, scrolling, button presses, typed
+    /// text, window resizing and focus changes.  This is where hit testing takes place, and where
+    /// mouse enter/exit and button tracking states are kept.
+    fn handle_input_event(&mut self, event_handler: &mut dyn PushrodCallbackEvents, event: &Event) {
+        event.mouse_cursor(|pos| {
+            let mouse_point = make_point_f64(pos[0], pos[1]);
 
-        Rc::clone(&self.widget_store)
-            .borrow_mut()
-            .invalidate_all_widgets();
-        self.rebuild_gl_buffers();
-
-        while let Some(ref event) = self.events.next(&mut self.window) {
-            let events_list = self.get_system_events_list();
+            if mouse_point.x != self.previous_mouse_position.x
+                || mouse_point.y != self.previous_mouse_position.y
+            {
+                self.previous_mouse_position = mouse_point.clone();
 
-            for event in events_list {
-                self.handle_system_event(event_handler, event.clone());
-            }
+                let current_widget_id = Rc::clone(&self.widget_store)
+                    .borrow_mut()
+                    .get_widget_id_for_point(mouse_point.clone());
 
-            event.mouse_cursor(|pos| {
-                let mouse_point = make_point_f64(pos[0], pos[1]);
+                // Handles the mouse move callback.
+                if let Some(widget_id) = current_widget_id {
+                    self.handle_event(
+                        widget_id,
+                        event_handler,
+                        CallbackEvent::MouseMoved {
+                            widget_id,
+                            point: mouse_point.clone(),
+                        },
+                    );
+                }
 
-                if mouse_point.x != previous_mouse_position.x
-                    || mouse_point.y != previous_mouse_position.y
-                {
-                    previous_mouse_position = mouse_point.clone();
+                if current_widget_id != self.last_widget_id {
+                    if let Some(widget_id) = self.last_widget_id {
+                        self.handle_event(
+                            widget_id,
+                            event_handler,
+                            CallbackEvent::MouseExited { widget_id },
+                        );
+                    }
 
-                    let current_widget_id = Rc::clone(&self.widget_store)
-                        .borrow_mut()
-                        .get_widget_id_for_point(mouse_point.clone());
+                    self.last_widget_id = current_widget_id;
 
-                    // Handles the mouse move callback.
-                    if current_widget_id != -1 {
+                    if let Some(widget_id) = self.last_widget_id {
                         self.handle_event(
-                            current_widget_id,
+                            widget_id,
                             event_handler,
-                            CallbackEvent::MouseMoved {
-                                widget_id: current_widget_id,
-                                point: mouse_point.clone(),
-                            },
+                            CallbackEvent::MouseEntered { widget_id },
                         );
                     }
+                }
+            }
+        });
 
-                    if current_widget_id != last_widget_id {
-                        if last_widget_id != -1 {
-                            self.handle_event(
-                                last_widget_id,
-                                event_handler,
-                                CallbackEvent::MouseExited {
-                                    widget_id: last_widget_id,
-                                },
-                            );
-                        }
+        event.mouse_scroll(|pos| {
+            let mouse_point = make_point_f64(pos[0], pos[1]);
 
-                        last_widget_id = current_widget_id;
+            if let Some(widget_id) = self.last_widget_id {
+                self.handle_event(
+                    widget_id,
+                    event_handler,
+                    CallbackEvent::MouseScrolled {
+                        widget_id,
+                        point: mouse_point.clone(),
+                    },
+                );
+            }
+        });
 
-                        if last_widget_id != -1 {
-                            self.handle_event(
-                                last_widget_id,
-                                event_handler,
-                                CallbackEvent::MouseEntered {
-                                    widget_id: last_widget_id,
-                                },
-                            );
-                        }
-                    }
-                }
-            });
+        event.button(|args| match args.button {
+            Button::Keyboard(key) => self.handle_key_event(event_handler, key, args.state),
+            button => self.handle_mouse_button_event(event_handler, button, args.state),
+        });
 
-            event.mouse_scroll(|pos| {
-                let mouse_point = make_point_f64(pos[0], pos[1]);
+        event.text(|text| self.handle_text_event(event_handler, text.to_string()));
 
-                if last_widget_id != -1 {
-                    self.handle_event(
-                        last_widget_id,
-                        event_handler,
-                        CallbackEvent::MouseScrolled {
-                            widget_id: last_widget_id,
-                            point: mouse_point.clone(),
-                        },
-                    );
-                }
+        event.resize(|args| {
+            let w: u32 = args.window_size[0] as u32;
+            let h: u32 = args.window_size[1] as u32;
+
+            // A minimized window reports a size of zero, which has nothing to draw into.
+            if w > 0 && h > 0 {
+                self.handle_resize(w, h);
+            }
+
+            // The base canvas follows the window, and the GUI is laid out again within it.
+            Rc::clone(&self.widget_store).borrow_mut().resize_widget(
+                WidgetId::ROOT,
+                crate::core::point::Size {
+                    w: w as i32,
+                    h: h as i32,
+                },
+            );
+
+            self.broadcast_event(CallbackEvent::WindowResized {
+                size: crate::core::point::Size {
+                    w: w as i32,
+                    h: h as i32,
+                },
             });
 
-            event.button(|args| match args.state {
-                ButtonState::Press => {
-                    button_map
-                        .entry(last_widget_id)
-                        .or_insert(HashSet::new())
-                        .insert(args.button);
+            Rc::clone(&self.widget_store)
+                .borrow_mut()
+                .invalidate_all_widgets();
+        });
+
+        event.focus(|focused| {
+            self.window_focused = focused;
+
+            if let Some(widget_id) = self.last_widget_id {
+                self.handle_event(
+                    widget_id,
+                    event_handler,
+                    CallbackEvent::WindowFocused { flag: focused },
+                );
+            }
+        });
+
+        event.close(|_| self.handle_close_request(event_handler));
+    }
 
+    /// Handles a request from the user to close the window.  A quit is requested before the
+    /// `WindowCloseRequested` event is sent, so that the application can veto the request by
+    /// cancelling it.  A quit that was already requested keeps its exit status.
+    fn handle_close_request(&mut self, event_handler: &mut dyn PushrodCallbackEvents) {
+        if !self.is_quitting() {
+            self.widget_store.borrow().quit(0);
+        }
+
+        event_handler.handle_event(
+            CallbackEvent::WindowCloseRequested,
+            &mut self.widget_store.borrow_mut(),
+        );
+    }
+
+    /// Handles a mouse (or other non-keyboard) button press or release.  Presses and releases
+    /// are tracked per `Widget`, so that a release can be reported as being inside or outside
+    /// of the `Widget` in which the button was pressed.
+    fn handle_mouse_button_event(
+        &mut self,
+        event_handler: &mut dyn PushrodCallbackEvents,
+        button: Button,
+        state: ButtonState,
+    ) {
+        // Presses outside of any `Widget` are tracked as well, under `WidgetId::NONE`, so that
+        // their release is not reported to another `Widget`.
+        let widget_id = self.last_widget_id.unwrap_or(WidgetId::NONE);
+
+        match state {
+            ButtonState::Press => {
+                // Clicking a `Widget` gives it keyboard focus, or clears the focus if the `Widget`
+                // does not accept it.
+                self.widget_store
+                    .borrow_mut()
+                    .set_focused_widget_id(self.last_widget_id);
+                self.handle_pending_events(event_handler);
+
+                self.button_map
+                    .entry(widget_id)
+                    .or_insert(HashSet::new())
+                    .insert(button);
+
+                self.handle_event(
+                    widget_id,
+                    event_handler,
+                    CallbackEvent::MouseButtonDown { widget_id, button },
+                );
+            }
+
+            ButtonState::Release => {
+                let released_inside = self
+                    .button_map
+                    .entry(widget_id)
+                    .or_insert(HashSet::new())
+                    .remove(&button);
+
+                if released_inside {
                     self.handle_event(
-                        last_widget_id,
+                        widget_id,
                         event_handler,
-                        CallbackEvent::MouseButtonDown {
-                            widget_id: last_widget_id,
-                            button: args.button,
-                        },
+                        CallbackEvent::MouseButtonUpInside { widget_id, button },
                     );
-                }
-
-                ButtonState::Release => {
-                    let button_set = button_map.entry(last_widget_id).or_insert(HashSet::new());
-
-                    if button_set.contains(&args.button) {
-                        button_set.remove(&args.button);
+                } else {
+                    // The button was pressed in another widget: any widget that is still
+                    // tracking the press receives the release as being outside of its bounds.
+                    let released_ids: Vec<WidgetId> = self
+                        .button_map
+                        .iter_mut()
+                        .filter_map(|(widget_id, button_set)| {
+                            if button_set.remove(&button) {
+                                Some(*widget_id)
+                            } else {
+                                None
+                            }
+                        })
+                        .collect();
 
+                    for widget_id in released_ids {
                         self.handle_event(
-                            last_widget_id,
+                            widget_id,
                             event_handler,
-                            CallbackEvent::MouseButtonUpInside {
-                                widget_id: last_widget_id,
-                                button: args.button,
-                            },
+                            CallbackEvent::MouseButtonUpOutside { widget_id, button },
                         );
-                    } else {
-                        for (widget_id, button_set) in button_map.iter_mut() {
-                            if button_set.contains(&args.button) {
-                                self.handle_event(
-                                    *widget_id,
-                                    event_handler,
-                                    CallbackEvent::MouseButtonUpOutside {
-                                        widget_id: *widget_id,
-                                        button: args.button,
-                                    },
-                                );
-
-                                button_set.remove(&args.button);
-                            }
-                        }
                     }
                 }
-            });
+            }
+        }
+    }
 
-            event.resize(|args| {
-                let w: u32 = args.window_size[0] as u32;
-                let h: u32 = args.window_size[1] as u32;
+    /// Handles a keyboard key press or release.  Key events are sent to the `Widget` that has
+    /// keyboard focus, regardless of the position of the mouse.  If no `Widget` has focus, the
+    /// event is only sent to the `event_handler`, with a `Widget` ID of `WidgetId::NONE`.  The
+    /// `Tab` key moves focus to the next `Widget` that accepts focus (or the previous one, if
+    /// `Shift` is held.)
+    fn handle_key_event(
+        &mut self,
+        event_handler: &mut dyn PushrodCallbackEvents,
+        key: Key,
+        state: ButtonState,
+    ) {
+        if key == Key::LShift || key == Key::RShift {
+            self.shift_pressed = state == ButtonState::Press;
+        }
 
-                self.handle_resize(w, h);
-                Rc::clone(&self.widget_store)
+        if key == Key::Tab {
+            if state == ButtonState::Press {
+                self.widget_store
                     .borrow_mut()
-                    .resize_layout_managers(w as u32, h as u32);
+                    .focus_next_widget(self.shift_pressed);
+                self.handle_pending_events(event_handler);
+            }
 
-                self.broadcast_event(CallbackEvent::WindowResized {
-                    size: crate::core::point::Size {
-                        w: w as i32,
-                        h: h as i32,
-                    },
-                });
+            return;
+        }
 
-                Rc::clone(&self.widget_store)
-                    .borrow_mut()
-                    .invalidate_all_widgets();
-            });
+        let focused_widget_id = self
+            .widget_store
+            .borrow()
+            .get_focused_widget_id()
+            .unwrap_or(WidgetId::NONE);
+
+        self.handle_focused_event(
+            focused_widget_id,
+            event_handler,
+            CallbackEvent::KeyPressed {
+                widget_id: focused_widget_id,
+                key,
+                state,
+            },
+        );
+    }
 
-            event.focus(|focused| {
-                self.handle_event(
-                    last_widget_id,
-                    event_handler,
-                    CallbackEvent::WindowFocused { flag: focused },
-                );
-            });
-            //
-            //            match event {
-            //                Event::Input(Input::Button(ButtonArgs {
-            //                    state,
-            //                    button: Button::Keyboard(key),
-            //                    scancode: _,
-            //                })) => {
-            //                    self.handle_event(
-            //                        last_widget_id,
-            //                        event_handler,
-            //                        CallbackEvent::KeyPressed {
-            //                            widget_id: last_widget_id,
-            //                            key: *key,
-            //                            state: *state,
-            //                        },
-            //                    );
-            //                }
-            //                _ => {}
-            //            };
+    /// Handles text typed on the keyboard.  Like key presses, the text is sent to the `Widget` that
+    /// has keyboard focus, or only to the `event_handler` with a `Widget` ID of `WidgetId::NONE` if
+    /// no `Widget` has focus.
+    fn handle_text_event(&mut self, event_handler: &mut dyn PushrodCallbackEvents, text: String) {
+        if text.is_empty() {
+            return;
+        }
 
-            event.render(|args| {
-                for pos in 0..injectable_map.len() {
-                    let widget_id = injectable_map[pos];
-                    let can_inject = Rc::clone(&self.widget_store)
-                        .borrow_mut()
-                        .get_widget_for_id(widget_id)
-                        .borrow_mut()
-                        .injects_custom_events();
-
-                    if can_inject {
-                        let injectable_event = Rc::clone(&self.widget_store)
-                            .borrow_mut()
-                            .get_widget_for_id(widget_id)
-                            .borrow_mut()
-                            .get_injectable_custom_events()
-                            .inject_custom_event(widget_id);
-
-                        match injectable_event {
-                            Some(x) => {
-                                self.handle_event(widget_id, event_handler, x.clone());
-
-                                // Injects an event to all widgets, allowing them to exhibit custom event handling behavior if
-                                // required.  This is usually used in cases where special triggering needs to take place, like
-                                // an indication of a timeout or transient error.
-                                let widget_size =
-                                    Rc::clone(&self.widget_store).borrow_mut().widgets.len();
-
-                                for i in 0..widget_size {
-                                    let handles_events =
-                                        Rc::clone(&self.widget_store).borrow().widgets[i]
-                                            .widget
-                                            .borrow_mut()
-                                            .handles_events();
-
-                                    if handles_events {
-                                        Rc::clone(&self.widget_store).borrow().widgets[i]
-                                            .widget
-                                            .borrow_mut()
-                                            .handle_event(
-                                                true,
-                                                x.clone(),
-                                                Some(&self.widget_store.borrow().widgets),
-                                            );
-                                    }
-                                }
+        let focused_widget_id = self
+            .widget_store
+            .borrow()
+            .get_focused_widget_id()
+            .unwrap_or(WidgetId::NONE);
+
+        self.handle_focused_event(
+            focused_widget_id,
+            event_handler,
+            CallbackEvent::TextEntered {
+                widget_id: focused_widget_id,
+                text,
+            },
+        );
+    }
+
+    /// Sends a keyboard event to the focused `Widget`.  If no `Widget` has focus, the event is
+    /// only sent to the `event_handler`.
+    fn handle_focused_event(
+        &mut self,
+        focused_widget_id: WidgetId,
+        event_handler: &mut dyn PushrodCallbackEvents,
+        event: CallbackEvent,
+    ) {
+        if focused_widget_id.is_none() {
+            event_handler.handle_event(event, &mut self.widget_store.borrow_mut());
+        } else {
+            self.handle_event(focused_widget_id, event_handler, event);
+        }
+    }
+
+    /// Dispatches the events generated by the `WidgetStore` itself, such as keyboard focus
+    /// changes.  Dispatching an event may cause new events to be generated, so this repeats until
+    /// no events are left.  Layouts whose containers were resized by the events handled so far
+    /// are laid out again first.
+    fn handle_pending_events(&mut self, event_handler: &mut dyn PushrodCallbackEvents) {
+        loop {
+            self.widget_store.borrow_mut().update_layouts();
+
+            let pending_events = self.widget_store.borrow_mut().take_pending_events();
+
+            if pending_events.is_empty() {
+                break;
+            }
+
+            for event in pending_events {
+                match event {
+                    CallbackEvent::WidgetFocusGained { widget_id }
+                    | CallbackEvent::WidgetFocusLost { widget_id } => {
+                        // A `Widget` that was removed while it had focus has already received
+                        // its `WidgetFocusLost` event, so only the `event_handler` is notified.
+                        if self.widget_store.borrow().is_valid_widget_id(widget_id) {
+                            self.handle_event(widget_id, event_handler, event)
+                        } else {
+                            self.handle_system_event(event_handler, event)
+                        }
+                    }
+                    _ => self.handle_system_event(event_handler, event),
+                }
+            }
+        }
+    }
+
+    /// Queries the `Widget`s in the `injectable_map` for any custom events they wish to inject,
+    /// and dispatches them.  This is called once per frame.
+    fn handle_custom_events(
+        &mut self,
+        event_handler: &mut dyn PushrodCallbackEvents,
+        injectable_map: &[WidgetId],
+    ) {
+        for widget_id in injectable_map.iter().cloned() {
+            // `Widget`s that have been removed since the map was built are skipped.
+            let can_inject = match Rc::clone(&self.widget_store)
+                .borrow_mut()
+                .get_widget_for_id(widget_id)
+            {
+                Some(widget) => widget.borrow_mut().injects_custom_events(),
+                None => false,
+            };
+
+            if can_inject {
+                let injectable_event = self.widget_store.borrow().widgets[widget_id.index()]
+                    .widget
+                    .borrow_mut()
+                    .get_injectable_custom_events()
+                    .inject_custom_event(widget_id);
+
+                match injectable_event {
+                    Some(x) => {
+                        self.handle_event(widget_id, event_handler, x.clone());
+
+                        // Injects an event to all widgets, allowing them to exhibit custom event handling behavior if
+                        // required.  This is usually used in cases where special triggering needs to take place, like
+                        // an indication of a timeout or transient error.
+                        let widget_size = Rc::clone(&self.widget_store).borrow_mut().widgets.len();
+
+                        for i in 0..widget_size {
+                            let handles_events = Rc::clone(&self.widget_store).borrow().widgets[i]
+                                .widget
+                                .borrow_mut()
+                                .handles_events();
+
+                            if handles_events {
+                                Rc::clone(&self.widget_store).borrow().widgets[i]
+                                    .widget
+                                    .borrow_mut()
+                                    .handle_event(
+                                        true,
+                                        x.clone(),
+                                        Some(&self.widget_store.borrow().widgets),
+                                    );
                             }
-                            None => (),
                         }
                     }
+                    None => (),
                 }
+            }
+        }
+    }
 
-                if self.widget_store.borrow_mut().needs_repaint() {
-                    self.drawing_texture.switch_to_texture();
+    /// Injects a raw Piston `Input` into the GUI, as if it had been received from the window.
+    /// The input goes through the same processing as the run loop: hit testing, mouse
+    /// enter/exit tracking, the button press tracking that determines `MouseButtonUpInside`
+    /// and `MouseButtonUpOutside`, keyboard focus and typed text, resizing and focus handling.
+    /// Any resulting events are sent to the `Widget`s, and to the `event_handler`.  This allows
+    /// clicks, hovers, drags and typing to be scripted without a user at the keyboard or mouse.
+    ///
+    /// Example:
+    /// ```no_run
+    /// # use pushrod::core::backend::*;
+    /// # use pushrod::core::callbacks::*;
+    /// # use pushrod::core::main::*;
+    /// # use piston::input::*;
+    /// # struct EventHandler {}
+    /// # impl PushrodCallbackEvents for EventHandler {}
+    /// # fn main() {
+    ///   let mut pushrod = Pushrod::new(HeadlessWindow::new(800, 600));
+    ///   let mut handler = EventHandler {};
+    ///
+    ///   pushrod.inject_input(&mut handler, Input::Move(Motion::MouseCursor([40.0, 40.0])));
+    ///   pushrod.inject_input(
+    ///       &mut handler,
+    ///       Input::Button(ButtonArgs {
+    ///           state: ButtonState::Press,
+    ///           button: Button::Mouse(MouseButton::Left),
+    ///           scancode: None,
+    ///       }),
+    ///   );
+    /// # }
+    /// ```
+    pub fn inject_input(&mut self, event_handler: &mut dyn PushrodCallbackEvents, input: Input) {
+        self.handle_pending_events(event_handler);
+        self.handle_input_event(event_handler, &Event::from(input));
+    }
+
+    /// Runs a single step of the run loop without drawing anything: injects each of the `inputs`
+    /// in order (see `inject_input`), then dispatches any pending system events, any custom
+    /// events injected by `Widget`s (such as timer ticks), and any focus changes, exactly as a
+    /// frame of the run loop would.
+    pub fn step(&mut self, event_handler: &mut dyn PushrodCallbackEvents, inputs: &[Input]) {
+        for input in inputs {
+            self.inject_input(event_handler, input.clone());
+        }
 
-                    gl.draw(args.viewport(), |c, g| {
-                        Rc::clone(&self.widget_store).borrow_mut().draw(
-                            0,
-                            c,
-                            g,
-                            self.drawing_texture.fbo,
-                        )
-                    });
+        let injectable_map = self.get_injectable_map();
 
-                    self.drawing_texture.switch_to_fb(0);
-                }
+        self.handle_system_events(event_handler);
+        self.handle_custom_events(event_handler, &injectable_map);
+        self.handle_pending_events(event_handler);
+    }
 
-                // Redraw the currently assigned drawing area.
-                gl.draw(args.viewport(), |c, g| {
-                    clear([1.0, 1.0, 1.0, 0.0], g);
-                    let flipped = c.transform.prepend_transform(scale(1.0, -1.0));
+    /// This is the main run loop for `Pushrod`.  A run loop requires the use of an assigned
+    /// `PushrodCallbackEvents` event handler.  This is how all communications take place when
+    /// an action occurs within the GUI window.
+    ///
+    /// The run loop ends when a quit is requested (see `WidgetStore::quit`), or when the window
+    /// closes.  Returns the exit status of the quit request, or 0 if none was made.
+    pub fn run(&mut self, event_handler: &mut dyn PushrodCallbackEvents) -> i32 {
+        let injectable_map: Vec<WidgetId> = self.get_injectable_map();
+        let mut gl: Option<GlGraphics> = if self.window.has_graphics() {
+            Some(GlGraphics::new(self.opengl))
+        } else {
+            None
+        };
 
-                    // Enable zoom only if the draw size is larger than the window size.
-                    let zoom_factor = (self.window.size().width + self.window.size().height)
-                        / (self.window.draw_size().width + self.window.draw_size().height);
+        eprintln!("Injectable Map: {:?}", injectable_map);
+        eprintln!("Window Size: {:?}", self.window.size());
+        eprintln!("Draw Size: {:?}", self.window.draw_size());
 
-                    Image::new().draw(
-                        &self.drawing_texture.texture,
-                        &c.draw_state,
-                        flipped.zoom(zoom_factor),
-                        g,
-                    );
-                });
+        Rc::clone(&self.widget_store)
+            .borrow_mut()
+            .invalidate_all_widgets();
+        self.rebuild_gl_buffers();
+
+        if self.lazy {
+            self.run_lazy(event_handler, &injectable_map, &mut gl);
+        } else {
+            self.run_events(event_handler, &injectable_map, &mut gl);
+        }
+
+        let exit_code = self.widget_store.borrow().get_quit_handle().get_exit_code();
+
+        if exit_code.is_some() {
+            self.window.set_should_close(true);
+        }
+
+        exit_code.unwrap_or(0)
+    }
+
+    /// The run loop used when not in lazy mode.  Frames are rendered at a fixed frame rate, and
+    /// only drawn if a `Widget` has been invalidated.
+    fn run_events(
+        &mut self,
+        event_handler: &mut dyn PushrodCallbackEvents,
+        injectable_map: &[WidgetId],
+        gl: &mut Option<GlGraphics>,
+    ) {
+        while let Some(ref event) = self.events.next(&mut self.window) {
+            self.handle_system_events(event_handler);
+            self.handle_input_event(event_handler, event);
+            self.handle_pending_events(event_handler);
+
+            if self.is_quitting() {
+                break;
+            }
+
+            event.render(|args| {
+                self.handle_custom_events(event_handler, injectable_map);
+                self.handle_pending_events(event_handler);
+
+                // Headless backends count every frame, as they have no buffers to swap.
+                if self.render_frame(gl, args) || !self.window.has_graphics() {
+                    self.window.swap_buffers();
+                }
             });
         }
     }
+
+    /// The run loop used in lazy mode.  Instead of rendering at a fixed frame rate, the loop
+    /// sleeps until an input event arrives, a `Widget` asks to be woken up (see
+    /// `Widget::get_next_wakeup`), or a `Widget` is invalidated.  Frames are still limited to the
+    /// maximum frame rate, and nothing is drawn while the window is unfocused or minimized.
+    fn run_lazy(
+        &mut self,
+        event_handler: &mut dyn PushrodCallbackEvents,
+        injectable_map: &[WidgetId],
+        gl: &mut Option<GlGraphics>,
+    ) {
+        let frame_duration = Duration::from_nanos(1_000_000_000 / self.max_fps);
+        let mut last_frame: Option<Instant> = None;
+
+        while !self.window.should_close() && !self.is_quitting() {
+            let needs_repaint = self.can_render() && self.widget_store.borrow_mut().needs_repaint();
+            let timeout = if needs_repaint {
+                match last_frame {
+                    Some(last_frame) => Some(
+                        (last_frame + frame_duration).saturating_duration_since(Instant::now()),
+                    ),
+                    None => Some(Duration::from_millis(0)),
+                }
+            } else {
+                self.widget_store.borrow_mut().get_next_wakeup()
+            };
+
+            let first_event = match timeout {
+                Some(timeout) => self.window.wait_event_timeout(timeout),
+                None => Some(self.window.wait_event()),
+            };
+
+            if let Some(event) = first_event {
+                self.handle_input_event(event_handler, &event);
+
+                // Handles the rest of the queued events before rendering.
+                while let Some(event) = self.window.poll_event() {
+                    self.handle_input_event(event_handler, &event);
+                }
+            }
+
+            self.handle_system_events(event_handler);
+            self.handle_custom_events(event_handler, injectable_map);
+            self.handle_pending_events(event_handler);
+
+            if self.is_quitting() {
+                break;
+            }
+
+            let frame_due = match last_frame {
+                Some(last_frame) => last_frame.elapsed() >= frame_duration,
+                None => true,
+            };
+
+            if frame_due && self.can_render() && self.widget_store.borrow_mut().needs_repaint() {
+                let size = self.window.size();
+                let draw_size = self.window.draw_size();
+                let args = RenderArgs {
+                    ext_dt: 0.0,
+                    window_size: size.into(),
+                    draw_size: draw_size.into(),
+                };
+
+                last_frame = Some(Instant::now());
+
+                if self.render_frame(gl, &args) {
+                    self.window.swap_buffers();
+                }
+            }
+        }
+    }
+
+    /// Indicates whether or not a frame can be rendered.  Nothing is rendered while the window is
+    /// minimized, and in lazy mode, nothing is rendered while the window is unfocused.
+    fn can_render(&self) -> bool {
+        let draw_size = self.window.draw_size();
+
+        draw_size.width > 0.0 && draw_size.height > 0.0 && (self.window_focused || !self.lazy)
+    }
+
+    /// Renders a frame.  Only the damaged areas of the drawing texture are redrawn (see
+    /// `WidgetStore::draw`), and the texture is then drawn to the window.  Returns `true` if a
+    /// frame was drawn, and the window buffers need to be swapped.  If no `Widget` has been
+    /// invalidated, and no area has been damaged, nothing is drawn.  Headless backends have
+    /// nothing to draw into, so the invalidated `Widget`s and damaged areas are discarded as if
+    /// they had been drawn.
+    fn render_frame(&mut self, gl: &mut Option<GlGraphics>, args: &RenderArgs) -> bool {
+        let widget_store = Rc::clone(&self.widget_store);
+        let window_size = self.window.size();
+        let draw_size = self.window.draw_size();
+        let background_color = self.background_color;
+
+        let (gl, drawing_texture) = match (gl.as_mut(), self.drawing_texture.as_mut()) {
+            (Some(gl), Some(drawing_texture)) => (gl, drawing_texture),
+            _ => {
+                let needs_repaint = widget_store.borrow_mut().needs_repaint();
+
+                widget_store.borrow_mut().discard_damage();
+                return needs_repaint;
+            }
+        };
+
+        if !widget_store.borrow_mut().needs_repaint() {
+            return false;
+        }
+
+        drawing_texture.switch_to_texture();
+
+        gl.draw(args.viewport(), |c, g| {
+            widget_store
+                .borrow_mut()
+                .draw(WidgetId::ROOT, c, g, drawing_texture.fbo)
+        });
+
+        drawing_texture.switch_to_fb(0);
+
+        // Redraw the currently assigned drawing area.
+        gl.draw(args.viewport(), |c, g| {
+            clear(background_color, g);
+            let flipped = c.transform.prepend_transform(scale(1.0, -1.0));
+
+            // Enable zoom only if the draw size is larger than the window size.
+            let zoom_factor =
+                (window_size.width + window_size.height) / (draw_size.width + draw_size.height);
+
+            Image::new().draw(
+                &drawing_texture.texture,
+                &c.draw_state,
+                flipped.zoom(zoom_factor),
+                g,
+            );
+        });
+
+        true
+    }
 }
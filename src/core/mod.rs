@@ -17,15 +17,43 @@
 /// their widgets, and propagating events where appropriate.
 pub mod main;
 
-/// Contains geometric shape representations: `Point` and `Size`, representing a point on
-/// the screen within a window, and the size of an object.  (This may change to Piston's
-/// representations.)
+/// Describes the window backends that can drive a `Pushrod` run loop: the `PushrodWindow` trait,
+/// and a `HeadlessWindow` that runs without a display.
+pub mod backend;
+
+/// Provides a `PushrodBuilder`, which creates a window, its `WidgetStore`, and its run loop in a
+/// single call, using a set of window, OpenGL, frame pacing, and background color settings.
+pub mod builder;
+
+/// Contains geometric shape representations: `Point`, `Size` and `Rect`, representing a point on
+/// the screen within a window, the size of an object, and an area of the screen.  (This may change
+/// to Piston's representations.)
 pub mod point;
 
 /// This is a cache that is used to store `Widget` objects for a `Pushrod` run loop.  Each
 /// `Pushrod` object that is created contains its own set of `Widget` objects, stored here.
 pub mod widget_store;
 
+/// Provides `WidgetId`, a handle to a `Widget` in a `WidgetStore`.  A handle to a `Widget` that
+/// has been removed no longer refers to any `Widget`, even if its slot in the store is reused.
+pub mod widget_id;
+
+/// Provides `PushrodError`, the error returned by fallible constructors, such as those of
+/// `Widget`s that load fonts or images from disk.
+pub mod error;
+
+/// Provides a `ResourceLoader`, which locates the fonts and images used by `Widget`s in a list of
+/// search roots, or in memory.
+pub mod resource_loader;
+
+/// Provides a `FontRegistry`, which loads each font once and shares its glyph cache between
+/// `Widget`s, and measures text drawn in a font.
+pub mod font_registry;
+
+/// Provides a `TextureCache`, which loads each image once and shares its texture between
+/// `Widget`s.
+pub mod texture_cache;
+
 /// This is a per-widget event store that is used to call an event handler when an event is
 /// triggered.
 pub mod callbacks;
@@ -47,3 +75,13 @@ pub mod horizontal_layout_manager;
 /// resizes and repositions them according to the height of the manager.  `Widget`s are
 /// repositioned in the `VerticalLayoutManager`'s top level widget equally.
 pub mod vertical_layout_manager;
+
+/// This provides a `GridLayoutManager` object, which accepts `Widget` objects, and positions them
+/// in the rows and columns of a grid.  The position of each `Widget` in the layout is its cell,
+/// and rows and columns can be fixed in size, sized to their `Widget`s, or share the remaining
+/// space.
+pub mod grid_layout_manager;
+
+/// Provides `Anchor`, which describes how a `Widget` is moved and resized when its parent is
+/// resized, such as when the window is resized.
+pub mod anchor;
@@ -69,3 +69,77 @@ pub fn make_point_f64(x: f64, y: f64) -> Point {
 pub fn make_unsized() -> Size {
     Size { w: 0, h: 0 }
 }
+
+/// Structure identifying a rectangular area on the screen by the X and Y coordinates of its upper
+/// left-hand corner, and its W (width) and H (height).
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Rect {
+    pub x: i32,
+    pub y: i32,
+    pub w: i32,
+    pub h: i32,
+}
+
+impl Rect {
+    /// Indicates whether or not the area is empty: an area with no width or no height.
+    pub fn is_empty(&self) -> bool {
+        self.w <= 0 || self.h <= 0
+    }
+
+    /// Indicates whether or not a `Point` lies within the area.
+    pub fn contains(&self, point: &Point) -> bool {
+        point.x >= self.x
+            && point.x < self.x + self.w
+            && point.y >= self.y
+            && point.y < self.y + self.h
+    }
+
+    /// Indicates whether or not two areas overlap.  Empty areas never overlap anything.
+    pub fn intersects(&self, other: &Rect) -> bool {
+        !self.intersection(other).is_empty()
+    }
+
+    /// Returns the area that is covered by both areas.  If they do not overlap, the area returned
+    /// is empty.
+    pub fn intersection(&self, other: &Rect) -> Rect {
+        let x = self.x.max(other.x);
+        let y = self.y.max(other.y);
+
+        Rect {
+            x,
+            y,
+            w: ((self.x + self.w).min(other.x + other.w) - x).max(0),
+            h: ((self.y + self.h).min(other.y + other.h) - y).max(0),
+        }
+    }
+
+    /// Returns the smallest area that covers both areas.  Empty areas are ignored.
+    pub fn union(&self, other: &Rect) -> Rect {
+        if self.is_empty() {
+            return other.clone();
+        } else if other.is_empty() {
+            return self.clone();
+        }
+
+        let x = self.x.min(other.x);
+        let y = self.y.min(other.y);
+
+        Rect {
+            x,
+            y,
+            w: (self.x + self.w).max(other.x + other.w) - x,
+            h: (self.y + self.h).max(other.y + other.h) - y,
+        }
+    }
+}
+
+/// Convenience method to create a `Rect` from the `Point` of its upper left-hand corner, and its
+/// `Size`.
+pub fn make_rect(origin: &Point, size: &Size) -> Rect {
+    Rect {
+        x: origin.x,
+        y: origin.y,
+        w: size.w,
+        h: size.h,
+    }
+}
@@ -0,0 +1,260 @@
+// Resource Loader
+// Locates fonts and images on disk or in memory, and loads them
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::env;
+use std::path::{Path, PathBuf};
+use std::rc::Rc;
+
+use image::RgbaImage;
+use opengl_graphics::{GlyphCache, Texture, TextureSettings};
+
+use crate::core::error::PushrodError;
+use crate::widget::checkbox_widget::{CHECKBOX_SELECTED_IMAGE, CHECKBOX_UNSELECTED_IMAGE};
+use crate::widget::radio_button_widget::{RADIO_SELECTED_IMAGE, RADIO_UNSELECTED_IMAGE};
+
+/// This is the name of the environment variable that can be used to override the location of
+/// resources.  It contains a list of directories, separated the same way as `PATH`, that are
+/// searched before any other search root.
+pub const RESOURCE_PATH_ENV: &str = "PUSHROD_ASSET_PATH";
+
+/// This is a resource that has been located by a `ResourceLoader`.
+pub enum Resource {
+    /// A file on disk.
+    File(PathBuf),
+
+    /// A resource stored in memory, such as one included with `include_bytes!`.
+    Memory(&'static [u8]),
+}
+
+struct ResourceLoaderInner {
+    search_roots: Vec<PathBuf>,
+    memory_resources: HashMap<String, &'static [u8]>,
+    assets_folder: Option<Option<PathBuf>>,
+}
+
+/// This is a handle to a resource loader, which locates the fonts and images used by `Widget`s.
+/// It can be cloned; all clones share the same search roots and in-memory resources.
+///
+/// All bundled `Widget`s load their resources through the loader returned by
+/// `ResourceLoader::shared`, which is also the loader returned by
+/// `WidgetStore::get_resource_loader` and `Pushrod::get_resource_loader`.  Search roots and
+/// in-memory resources must therefore be added before the `Widget`s that use them are created.
+///
+/// A resource name is resolved in the following order:
+/// - Absolute paths are used as they are.
+/// - Each directory listed in the `PUSHROD_ASSET_PATH` environment variable.
+/// - Each search root added with `add_search_root`, in the order they were added.
+/// - The current working directory, followed by the first `assets` folder found within three
+///   parent or child directories of it.
+/// - Resources added with `add_memory_resource`.  The images used by `CheckboxWidget` and
+///   `RadioButtonWidget` are always available from memory.
+///
+/// Example:
+/// ```no_run
+/// # use pushrod::core::resource_loader::*;
+/// let loader = ResourceLoader::shared();
+///
+/// loader.add_search_root("/usr/share/ourapp/assets");
+/// loader.add_memory_resource("logo.png", &[]);
+/// ```
+#[derive(Clone)]
+pub struct ResourceLoader {
+    inner: Rc<RefCell<ResourceLoaderInner>>,
+}
+
+thread_local! {
+    static SHARED_RESOURCE_LOADER: ResourceLoader = ResourceLoader::new();
+}
+
+impl ResourceLoader {
+    /// Creates a new loader, with no search roots, and only the bundled `Widget` images in
+    /// memory.
+    pub fn new() -> Self {
+        let loader = Self {
+            inner: Rc::new(RefCell::new(ResourceLoaderInner {
+                search_roots: Vec::new(),
+                memory_resources: HashMap::new(),
+                assets_folder: None,
+            })),
+        };
+
+        loader.add_memory_resource(
+            CHECKBOX_SELECTED_IMAGE,
+            include_bytes!("../../assets/checkbox_selected.png"),
+        );
+        loader.add_memory_resource(
+            CHECKBOX_UNSELECTED_IMAGE,
+            include_bytes!("../../assets/checkbox_unselected.png"),
+        );
+        loader.add_memory_resource(
+            RADIO_SELECTED_IMAGE,
+            include_bytes!("../../assets/radio_selected.png"),
+        );
+        loader.add_memory_resource(
+            RADIO_UNSELECTED_IMAGE,
+            include_bytes!("../../assets/radio_unselected.png"),
+        );
+
+        loader
+    }
+
+    /// Retrieves the loader that is shared by all `Widget`s and `WidgetStore`s on the current
+    /// thread.
+    pub fn shared() -> Self {
+        SHARED_RESOURCE_LOADER.with(|loader| loader.clone())
+    }
+
+    /// Adds a directory to the end of the list of search roots.
+    pub fn add_search_root<P: Into<PathBuf>>(&self, root: P) {
+        self.inner.borrow_mut().search_roots.push(root.into());
+    }
+
+    /// Removes all search roots added with `add_search_root`.
+    pub fn clear_search_roots(&self) {
+        self.inner.borrow_mut().search_roots.clear();
+    }
+
+    /// Retrieves the list of directories that are searched for resources, in the order that they
+    /// are searched.
+    pub fn get_search_roots(&self) -> Vec<PathBuf> {
+        let mut roots: Vec<PathBuf> = env::var_os(RESOURCE_PATH_ENV)
+            .map(|paths| env::split_paths(&paths).collect())
+            .unwrap_or_default();
+        let mut inner = self.inner.borrow_mut();
+
+        roots.extend(inner.search_roots.iter().cloned());
+        roots.push(PathBuf::from("."));
+
+        let assets_folder = inner.assets_folder.get_or_insert_with(|| {
+            find_folder::Search::ParentsThenKids(3, 3)
+                .for_folder("assets")
+                .ok()
+        });
+
+        if let Some(assets_folder) = assets_folder {
+            roots.push(assets_folder.clone());
+        }
+
+        roots
+    }
+
+    /// Adds a resource stored in memory under the specified name, replacing any resource that
+    /// was previously added with the same name.  Resources on disk take precedence over resources
+    /// in memory.
+    pub fn add_memory_resource(&self, name: &str, bytes: &'static [u8]) {
+        self.inner
+            .borrow_mut()
+            .memory_resources
+            .insert(String::from(name), bytes);
+    }
+
+    /// Removes a resource that was added with `add_memory_resource`.  Returns `true` if the
+    /// resource existed.
+    pub fn remove_memory_resource(&self, name: &str) -> bool {
+        self.inner
+            .borrow_mut()
+            .memory_resources
+            .remove(name)
+            .is_some()
+    }
+
+    /// Locates the resource with the specified name.  Returns a `PushrodError::ResourceNotFound`
+    /// listing the directories that were searched if the resource cannot be found.
+    pub fn find(&self, name: &str) -> Result<Resource, PushrodError> {
+        let path = Path::new(name);
+
+        if path.is_absolute() {
+            if path.is_file() {
+                return Ok(Resource::File(path.to_path_buf()));
+            }
+        } else {
+            for root in self.get_search_roots() {
+                let candidate = root.join(path);
+
+                if candidate.is_file() {
+                    return Ok(Resource::File(candidate));
+                }
+            }
+        }
+
+        if let Some(bytes) = self.inner.borrow().memory_resources.get(name) {
+            return Ok(Resource::Memory(*bytes));
+        }
+
+        Err(PushrodError::ResourceNotFound {
+            name: String::from(name),
+            searched: if path.is_absolute() {
+                Vec::new()
+            } else {
+                self.get_search_roots()
+            },
+        })
+    }
+
+    /// Locates and decodes the image with the specified name.  This does not require an OpenGL
+    /// context.
+    pub fn load_image(&self, name: &str) -> Result<RgbaImage, PushrodError> {
+        match self.find(name)? {
+            Resource::File(path) => {
+                image::open(&path)
+                    .map(|image| image.to_rgba())
+                    .map_err(|error| PushrodError::ImageLoad {
+                        path,
+                        reason: error.to_string(),
+                    })
+            }
+            Resource::Memory(bytes) => image::load_from_memory(bytes)
+                .map(|image| image.to_rgba())
+                .map_err(|error| PushrodError::ImageLoad {
+                    path: PathBuf::from(name),
+                    reason: error.to_string(),
+                }),
+        }
+    }
+
+    /// Locates and loads the image with the specified name into a `Texture`.
+    pub fn load_texture(&self, name: &str) -> Result<Texture, PushrodError> {
+        self.load_image(name)
+            .map(|image| Texture::from_image(&image, &TextureSettings::new()))
+    }
+
+    /// Locates and loads the font with the specified name into a `GlyphCache`.
+    pub fn load_glyph_cache(&self, name: &str) -> Result<GlyphCache<'static>, PushrodError> {
+        let settings = TextureSettings::new();
+
+        match self.find(name)? {
+            Resource::File(path) => {
+                GlyphCache::new(&path, (), settings).map_err(|error| PushrodError::FontLoad {
+                    path,
+                    reason: error.to_string(),
+                })
+            }
+            Resource::Memory(bytes) => {
+                GlyphCache::from_bytes(bytes, (), settings).map_err(|_| PushrodError::FontLoad {
+                    path: PathBuf::from(name),
+                    reason: String::from("invalid font data"),
+                })
+            }
+        }
+    }
+}
+
+impl Default for ResourceLoader {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -0,0 +1,319 @@
+// Texture Cache
+// Loads each image once, and shares its texture between widgets
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::cell::{Cell, Ref, RefCell};
+use std::collections::HashMap;
+use std::rc::{Rc, Weak};
+
+use graphics::ImageSize;
+use image::RgbaImage;
+use opengl_graphics::{Texture, TextureSettings};
+
+use crate::core::error::PushrodError;
+use crate::core::point::Size;
+use crate::core::resource_loader::ResourceLoader;
+
+/// This is a texture that is held by a `TextureCache`.  It can be cloned; all clones share the
+/// same texture, and see the new image when the texture is reloaded.
+#[derive(Clone)]
+pub struct SharedTexture {
+    name: String,
+    texture: Rc<TextureSlot>,
+}
+
+/// A texture, along with its size, and the number of times it has been reloaded.
+struct TextureSlot {
+    data: RefCell<TextureData>,
+    size: Cell<(u32, u32)>,
+    generation: Cell<u64>,
+}
+
+/// The contents of a `TextureSlot`: either an image that has not been uploaded to the GPU yet,
+/// along with the settings to upload it with, or an uploaded texture.
+enum TextureData {
+    Image(RgbaImage, TextureSettings),
+    Texture(Texture),
+}
+
+impl TextureData {
+    fn get_size(&self) -> (u32, u32) {
+        match self {
+            TextureData::Image(image, _) => image.dimensions(),
+            TextureData::Texture(texture) => texture.get_size(),
+        }
+    }
+}
+
+impl TextureSlot {
+    fn new(data: TextureData) -> Self {
+        Self {
+            size: Cell::new(data.get_size()),
+            data: RefCell::new(data),
+            generation: Cell::new(0),
+        }
+    }
+
+    /// Replaces the contents of the slot, and increases its generation.
+    fn replace(&self, data: TextureData) {
+        self.size.set(data.get_size());
+        self.data.replace(data);
+        self.generation.set(self.generation.get() + 1);
+    }
+
+    /// Uploads the image to the GPU, if it has not been uploaded yet.
+    fn upload(&self) {
+        let mut data = self.data.borrow_mut();
+        let texture = match &*data {
+            TextureData::Image(image, settings) => Texture::from_image(image, settings),
+            TextureData::Texture(_) => return,
+        };
+
+        *data = TextureData::Texture(texture);
+    }
+}
+
+impl SharedTexture {
+    /// Retrieves the name or ID of the texture in the cache.
+    pub fn get_name(&self) -> &str {
+        &self.name
+    }
+
+    /// Borrows the texture, which is used to draw the image.  The image is uploaded to the GPU the
+    /// first time this is called, so this requires an OpenGL context.
+    pub fn get_texture(&self) -> Ref<'_, Texture> {
+        self.texture.upload();
+
+        Ref::map(self.texture.data.borrow(), |data| match data {
+            TextureData::Texture(texture) => texture,
+            TextureData::Image(..) => unreachable!("The image has just been uploaded."),
+        })
+    }
+
+    /// Retrieves the number of times the texture has been reloaded.  `Widget`s that draw the
+    /// texture compare this with the generation they last drew, to find out that they need to be
+    /// redrawn.
+    pub fn get_generation(&self) -> u64 {
+        self.texture.generation.get()
+    }
+
+    /// Retrieves the size of the image in pixels.
+    pub fn get_size(&self) -> Size {
+        let (width, height) = self.texture.size.get();
+
+        Size {
+            w: width as i32,
+            h: height as i32,
+        }
+    }
+}
+
+struct TextureEntry {
+    texture: Weak<TextureSlot>,
+    reloadable: bool,
+}
+
+/// This is a handle to a cache of textures, which loads each image only once, and shares its
+/// texture between all of the `Widget`s that draw it.  It can be cloned; all clones share the same
+/// textures.
+///
+/// Textures are stored by name: either the name of an image resource, which is located by the
+/// shared `ResourceLoader`, or an ID given to a texture that was created by the application with
+/// `insert`.  The cache does not keep textures alive by itself: a texture is evicted once the last
+/// `SharedTexture` that refers to it has been dropped, and is loaded again the next time it is
+/// requested.  All bundled `Widget`s that draw images request their textures from the cache
+/// returned by `TextureCache::shared`, which is also returned by `WidgetStore::get_texture_cache`.
+///
+/// Images are decoded when they are requested, but are only uploaded to the GPU the first time
+/// they are drawn, so `Widget`s that draw images can be created without an OpenGL context, such as
+/// when running with a `HeadlessWindow`.
+///
+/// Example:
+/// ```no_run
+/// # use pushrod::core::texture_cache::*;
+/// let textures = TextureCache::shared();
+/// let logo = textures.get_texture("logo.png").unwrap();
+///
+/// // After logo.png has changed on disk, all Widgets that draw it show the new image.
+/// textures.reload("logo.png").unwrap();
+/// ```
+#[derive(Clone)]
+pub struct TextureCache {
+    textures: Rc<RefCell<HashMap<String, TextureEntry>>>,
+}
+
+thread_local! {
+    static SHARED_TEXTURE_CACHE: TextureCache = TextureCache::new();
+}
+
+impl TextureCache {
+    /// Creates a new, empty cache.
+    pub fn new() -> Self {
+        Self {
+            textures: Rc::new(RefCell::new(HashMap::new())),
+        }
+    }
+
+    /// Retrieves the cache that is shared by all `Widget`s and `WidgetStore`s on the current
+    /// thread.
+    pub fn shared() -> Self {
+        SHARED_TEXTURE_CACHE.with(|cache| cache.clone())
+    }
+
+    /// Retrieves the texture with the specified name, loading the image resource with that name
+    /// if the texture is not in the cache.  Returns a `PushrodError` if the image cannot be found
+    /// or loaded.
+    pub fn get_texture(&self, name: &str) -> Result<SharedTexture, PushrodError> {
+        if let Some(texture) = self.get_cached(name) {
+            return Ok(texture);
+        }
+
+        let image = ResourceLoader::shared().load_image(name)?;
+
+        Ok(self.store(
+            name,
+            TextureData::Image(image, TextureSettings::new()),
+            true,
+        ))
+    }
+
+    /// Retrieves the texture with the specified ID, creating it with `create` if the texture is
+    /// not in the cache.  Textures created this way are not reloaded by `reload` or `reload_all`.
+    pub fn get_or_insert_with<F>(&self, id: &str, create: F) -> SharedTexture
+    where
+        F: FnOnce() -> Texture,
+    {
+        match self.get_cached(id) {
+            Some(texture) => texture,
+            None => self.store(id, TextureData::Texture(create()), false),
+        }
+    }
+
+    /// Retrieves the texture with the specified ID, creating it from the image returned by
+    /// `create` if the texture is not in the cache.  Unlike `get_or_insert_with`, this does not
+    /// require an OpenGL context until the texture is drawn.  Textures created this way are not
+    /// reloaded by `reload` or `reload_all`.
+    pub fn get_or_insert_image_with<F>(
+        &self,
+        id: &str,
+        settings: TextureSettings,
+        create: F,
+    ) -> SharedTexture
+    where
+        F: FnOnce() -> RgbaImage,
+    {
+        match self.get_cached(id) {
+            Some(texture) => texture,
+            None => self.store(id, TextureData::Image(create(), settings), false),
+        }
+    }
+
+    /// Stores a texture created by the application under the specified ID, replacing any texture
+    /// that was stored with the same name.  `SharedTexture`s that refer to the replaced texture
+    /// keep drawing it.
+    pub fn insert(&self, id: &str, texture: Texture) -> SharedTexture {
+        self.store(id, TextureData::Texture(texture), false)
+    }
+
+    /// Indicates whether or not the texture with the specified name is in the cache.
+    pub fn is_loaded(&self, name: &str) -> bool {
+        self.get_cached(name).is_some()
+    }
+
+    /// Retrieves the number of `SharedTexture`s that refer to the texture with the specified
+    /// name, or 0 if it is not in the cache.
+    pub fn get_reference_count(&self, name: &str) -> usize {
+        self.textures
+            .borrow()
+            .get(name)
+            .map(|entry| entry.texture.strong_count())
+            .unwrap_or(0)
+    }
+
+    /// Loads the image resource with the specified name again, and replaces the texture that all
+    /// `SharedTexture`s with that name draw.  The generation of the texture is increased, so the
+    /// `Widget`s that draw it are redrawn.  Returns `false` if the texture was not in the cache,
+    /// or was not loaded from an image resource.  The texture is left unchanged if the image
+    /// cannot be loaded.
+    pub fn reload(&self, name: &str) -> Result<bool, PushrodError> {
+        let slot = match self.textures.borrow().get(name) {
+            Some(entry) if entry.reloadable => entry.texture.upgrade(),
+            _ => None,
+        };
+
+        match slot {
+            Some(slot) => {
+                let image = ResourceLoader::shared().load_image(name)?;
+
+                slot.replace(TextureData::Image(image, TextureSettings::new()));
+                Ok(true)
+            }
+            None => Ok(false),
+        }
+    }
+
+    /// Reloads every texture in the cache that was loaded from an image resource.  Stops at, and
+    /// returns, the first error.
+    pub fn reload_all(&self) -> Result<(), PushrodError> {
+        let names: Vec<String> = self.textures.borrow().keys().cloned().collect();
+
+        for name in names {
+            self.reload(&name)?;
+        }
+
+        Ok(())
+    }
+
+    fn get_cached(&self, name: &str) -> Option<SharedTexture> {
+        let mut textures = self.textures.borrow_mut();
+        let texture = textures.get(name).and_then(|entry| entry.texture.upgrade());
+
+        match texture {
+            Some(texture) => Some(SharedTexture {
+                name: String::from(name),
+                texture,
+            }),
+            None => {
+                textures.remove(name);
+                None
+            }
+        }
+    }
+
+    fn store(&self, name: &str, data: TextureData, reloadable: bool) -> SharedTexture {
+        let texture = Rc::new(TextureSlot::new(data));
+        let mut textures = self.textures.borrow_mut();
+
+        // Entries of textures that have been dropped are removed as new textures are stored.
+        textures.retain(|_, entry| entry.texture.strong_count() > 0);
+        textures.insert(
+            String::from(name),
+            TextureEntry {
+                texture: Rc::downgrade(&texture),
+                reloadable,
+            },
+        );
+
+        SharedTexture {
+            name: String::from(name),
+            texture,
+        }
+    }
+}
+
+impl Default for TextureCache {
+    fn default() -> Self {
+        Self::new()
+    }
+}
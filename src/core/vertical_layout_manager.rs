@@ -13,21 +13,55 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use std::collections::HashMap;
+
 use crate::core::layout_manager::*;
 use crate::core::point::{Point, Size};
+use crate::core::widget_id::WidgetId;
 
+/// This is a `LayoutManager` that stacks `Widget`s from top to bottom, in the order that they
+/// were added.  Each `Widget` is either a fixed number of pixels tall, its preferred height, or
+/// receives a share of the remaining height in proportion to its stretch weight (see
+/// `set_item_size`), within its minimum and maximum height.  Horizontally, `Widget`s fill the
+/// width of the layout, unless they are aligned otherwise (see `set_alignment`).  The padding is
+/// kept around the `Widget`s, and `spacing` pixels are kept between them.
 pub struct VerticalLayoutManager {
-    container_widget_id: i32,
+    container_widget_id: WidgetId,
     padding: LayoutManagerPadding,
+    alignment: LayoutAlignment,
+    item_sizes: HashMap<usize, LayoutItemSize>,
+    item_alignments: HashMap<usize, LayoutAlignment>,
 }
 
 impl VerticalLayoutManager {
-    pub fn new(widget_id: i32, padding: LayoutManagerPadding) -> Self {
+    pub fn new(widget_id: WidgetId, padding: LayoutManagerPadding) -> Self {
         Self {
             container_widget_id: widget_id,
             padding,
+            alignment: LayoutAlignment::Stretch,
+            item_sizes: HashMap::new(),
+            item_alignments: HashMap::new(),
         }
     }
+
+    /// Sets how the height of the `Widget` at the specified index is determined.  Indexes
+    /// are in the order that `Widget`s were added to the layout.  `Widget`s that have not been
+    /// given a size are `LayoutItemSize::Stretch(1)`.
+    pub fn set_item_size(&mut self, index: usize, size: LayoutItemSize) {
+        self.item_sizes.insert(index, size);
+    }
+
+    /// Sets how `Widget`s are aligned horizontally, unless they have been given an alignment of
+    /// their own.  Defaults to `LayoutAlignment::Stretch`.
+    pub fn set_alignment(&mut self, alignment: LayoutAlignment) {
+        self.alignment = alignment;
+    }
+
+    /// Sets how the `Widget` at the specified index is aligned horizontally, overriding the
+    /// alignment set by `set_alignment`.
+    pub fn set_item_alignment(&mut self, index: usize, alignment: LayoutAlignment) {
+        self.item_alignments.insert(index, alignment);
+    }
 }
 
 impl LayoutManager for VerticalLayoutManager {
@@ -37,50 +71,84 @@ impl LayoutManager for VerticalLayoutManager {
         size: Size,
         coordinates: LayoutManagerCoordinates,
     ) -> LayoutManagerCoordinates {
-        let num_widgets = coordinates.widget_sizes.len() as i32;
-        let height_per_widget = (size.h / num_widgets) - (self.padding.spacing / 2);
+        let num_widgets = coordinates.widget_sizes.len();
+        let items: Vec<LayoutItemSize> = (0..num_widgets)
+            .map(|x| self.item_sizes.get(&x).cloned().unwrap_or_default())
+            .collect();
+        let available_along = size.h
+            - (self.padding.top + self.padding.bottom)
+            - self.padding.spacing * (num_widgets as i32 - 1).max(0);
+        let available_across = size.w - (self.padding.left + self.padding.right);
+        let limits: Vec<AxisLimits> = (0..num_widgets)
+            .map(|x| coordinates.get_height_limits(x))
+            .collect();
+        let item_sizes = compute_item_sizes(available_along, &items, &limits);
         let mut widget_origins: Vec<Point> = vec![];
         let mut widget_sizes: Vec<Size> = vec![];
-        let current_x: i32 = origin.x;
-        let mut current_y: i32 = origin.y;
+        let mut current_y: i32 = origin.y + self.padding.top;
 
-        for x in 0..num_widgets {
-            if x == 0 {
-                current_y = self.padding.top + origin.y;
-            } else {
-                current_y += height_per_widget + (self.padding.spacing / 2);
-            }
+        for (x, item_size) in item_sizes.into_iter().enumerate() {
+            let alignment = self
+                .item_alignments
+                .get(&x)
+                .cloned()
+                .unwrap_or(self.alignment);
+            let (offset, cross_size) = align_item(
+                available_across,
+                &coordinates.get_width_limits(x),
+                alignment,
+            );
 
             widget_origins.push(Point {
-                x: current_x + self.padding.left,
+                x: origin.x + self.padding.left + offset,
                 y: current_y,
             });
+            widget_sizes.push(Size {
+                w: cross_size,
+                h: item_size,
+            });
 
-            if x == num_widgets - 1 {
-                widget_sizes.push(Size {
-                    w: size.w - (self.padding.left + self.padding.right),
-                    h: height_per_widget - self.padding.bottom,
-                });
-            } else {
-                widget_sizes.push(Size {
-                    w: size.w - (self.padding.left + self.padding.right),
-                    h: height_per_widget - (self.padding.spacing / 2),
-                });
-            }
+            current_y += item_size + self.padding.spacing;
         }
 
         LayoutManagerCoordinates {
-            widget_origins: widget_origins.clone(),
-            widget_sizes: widget_sizes.clone(),
-            widget_positions: coordinates.widget_positions.clone(),
+            widget_origins,
+            widget_sizes,
+            widget_positions: coordinates.widget_positions,
+            ..LayoutManagerCoordinates::default()
         }
     }
 
     fn adjust_layout(&mut self, coordinates: LayoutManagerPadding) {
-        self.padding = coordinates.clone()
+        self.padding = coordinates;
+    }
+
+    fn get_preferred_size(&self, coordinates: &LayoutManagerCoordinates) -> Option<Size> {
+        let num_widgets = coordinates.widget_sizes.len();
+        let along: i32 = (0..num_widgets)
+            .map(|x| {
+                let limits = coordinates.get_height_limits(x);
+
+                match self.item_sizes.get(&x).cloned().unwrap_or_default() {
+                    LayoutItemSize::Fixed(size) => limits.clamp(size),
+                    _ => limits.get_preferred(),
+                }
+            })
+            .sum();
+        let across: i32 = (0..num_widgets)
+            .map(|x| coordinates.get_width_limits(x).get_preferred())
+            .max()
+            .unwrap_or(0);
+
+        Some(Size {
+            h: along
+                + (self.padding.top + self.padding.bottom)
+                + self.padding.spacing * (num_widgets as i32 - 1).max(0),
+            w: across + (self.padding.left + self.padding.right),
+        })
     }
 
-    fn get_widget_id(&self) -> i32 {
+    fn get_widget_id(&self) -> WidgetId {
         self.container_widget_id
     }
 }
@@ -0,0 +1,87 @@
+// Widget ID: a handle to a Widget in a WidgetStore
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::fmt;
+
+/// This is a handle to a `Widget` in a `WidgetStore`, returned when the `Widget` is added.  It
+/// consists of the index of the slot in which the `Widget` is stored, and the generation of that
+/// slot.  When a `Widget` is removed, its slot can be reused by a new `Widget`, with the next
+/// generation, so a handle to the removed `Widget` never refers to the new one: lookups using a
+/// stale handle fail instead.
+///
+/// `WidgetId::ROOT` refers to the top-level `CanvasWidget` of the store, and `WidgetId::NONE`
+/// never refers to a `Widget`.  `NONE` is used in a `CallbackEvent` that is not sent to a
+/// `Widget`, such as a key press when no `Widget` has keyboard focus.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct WidgetId {
+    index: u32,
+    generation: u32,
+}
+
+impl WidgetId {
+    /// The ID of the top-level `CanvasWidget`, which is the parent of all `Widget`s that are added
+    /// without a parent.
+    pub const ROOT: WidgetId = WidgetId {
+        index: 0,
+        generation: 0,
+    };
+
+    /// An ID that does not refer to any `Widget`.
+    pub const NONE: WidgetId = WidgetId {
+        index: u32::MAX,
+        generation: 0,
+    };
+
+    pub(crate) fn new(index: usize, generation: u32) -> Self {
+        Self {
+            index: index as u32,
+            generation,
+        }
+    }
+
+    /// Retrieves the index of the slot in which the `Widget` is stored in `WidgetStore::widgets`.
+    pub fn index(&self) -> usize {
+        self.index as usize
+    }
+
+    /// Retrieves the generation of the slot in which the `Widget` is stored.  This is incremented
+    /// each time the slot is reused.
+    pub fn generation(&self) -> u32 {
+        self.generation
+    }
+
+    /// Indicates whether or not this is `WidgetId::NONE`.
+    pub fn is_none(&self) -> bool {
+        *self == WidgetId::NONE
+    }
+}
+
+/// The default `WidgetId` is `WidgetId::NONE`, which is used by `Widget`s that have not yet been
+/// added to a `WidgetStore`.
+impl Default for WidgetId {
+    fn default() -> Self {
+        WidgetId::NONE
+    }
+}
+
+/// Displays a `WidgetId` as its index and generation, separated by a colon, or as `none`.
+impl fmt::Display for WidgetId {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if self.is_none() {
+            write!(f, "none")
+        } else {
+            write!(f, "{}:{}", self.index, self.generation)
+        }
+    }
+}
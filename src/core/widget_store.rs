@@ -16,10 +16,19 @@
 use gl::types::GLuint;
 use graphics::*;
 use opengl_graphics::GlGraphics;
-use std::cell::RefCell;
-
+use std::cell::{Cell, Ref, RefCell, RefMut};
+use std::collections::{HashMap, HashSet};
+use std::rc::Rc;
+use std::time::Duration;
+
+use crate::core::callbacks::*;
+use crate::core::error::PushrodError;
+use crate::core::font_registry::FontRegistry;
 use crate::core::layout_manager::*;
 use crate::core::point::*;
+use crate::core::resource_loader::ResourceLoader;
+use crate::core::texture_cache::TextureCache;
+use crate::core::widget_id::WidgetId;
 use crate::widget::config::*;
 use crate::widget::widget::*;
 
@@ -33,30 +42,145 @@ pub struct WidgetContainer {
     pub widget_name: String,
 
     /// The `Widget`'s automatically assigned ID.
-    pub widget_id: i32,
+    pub widget_id: WidgetId,
 
     /// The `Widget`'s parent ID.
-    pub parent_id: i32,
+    pub parent_id: WidgetId,
+
+    /// The stacking order of the `Widget` among its siblings.  `Widget`s with a higher z-index are
+    /// drawn above their siblings, and receive mouse events first.  `Widget`s with the same
+    /// z-index are stacked in the order in which they were added.
+    pub(crate) z_index: i32,
+
+    /// The order in which the `Widget` was added to the store.  As the slots of removed `Widget`s
+    /// are reused, this cannot be derived from the ID.
+    pub(crate) sequence: u64,
+
+    /// The bounds of the `Widget` when it was last drawn, used to repaint the area that it leaves
+    /// behind when it is moved, resized or hidden.
+    pub(crate) drawn_bounds: Option<Rect>,
+
+    /// Indicates whether or not the `Widget` has been removed from the store.  Removed `Widget`s
+    /// keep their slot, so that the IDs of the remaining `Widget`s do not change, but the slot
+    /// only holds an inert placeholder until it is reused by a new `Widget`.
+    pub(crate) removed: bool,
 }
 
 pub struct LayoutManagerContainer {
     pub container_id: i32,
-    pub widget_ids: RefCell<Vec<i32>>,
+    pub widget_ids: RefCell<Vec<WidgetId>>,
     pub widget_positions: RefCell<Vec<Point>>,
     pub layout_manager: RefCell<Box<dyn LayoutManager>>,
+
+    /// The size of the container `Widget` when the layout was last run, or `None` if it has not
+    /// been run yet.  This is used to find layouts whose container has been resized.
+    pub(crate) layout_size: Option<Size>,
+}
+
+/// A step of a layout pass: either running a layout manager by ID, or adapting the children of a
+/// `Widget` that has been resized from the specified size.
+enum LayoutTask {
+    Manager(i32),
+    Resized(WidgetId, Size),
+}
+
+/// This is a handle that is used to quit a `Pushrod` run loop.  It can be cloned and moved into
+/// `Widget` callbacks, which do not have access to the `WidgetStore`.  All clones share the same
+/// state: once `quit` is called on any of them, the run loop stops after the current event has
+/// been handled, and `Pushrod::run` returns the exit status.
+///
+/// Example:
+/// ```ignore
+/// let quit_handle = pushrod.get_quit_handle();
+///
+/// button.get_callbacks().on_click(move |_, _| quit_handle.quit(0));
+/// ```
+#[derive(Clone, Default)]
+pub struct QuitHandle {
+    exit_code: Rc<Cell<Option<i32>>>,
+}
+
+impl QuitHandle {
+    /// Creates a new handle, with no quit request.
+    pub fn new() -> Self {
+        Self {
+            exit_code: Rc::new(Cell::new(None)),
+        }
+    }
+
+    /// Requests that the run loop quit, returning the specified exit status from `Pushrod::run`.
+    pub fn quit(&self, exit_code: i32) {
+        self.exit_code.set(Some(exit_code));
+    }
+
+    /// Cancels a quit request that has not yet been acted upon.
+    pub fn cancel(&self) {
+        self.exit_code.set(None);
+    }
+
+    /// Indicates whether or not the run loop has been asked to quit.
+    pub fn is_quitting(&self) -> bool {
+        self.exit_code.get().is_some()
+    }
+
+    /// Retrieves the exit status of the quit request, if one has been made.
+    pub fn get_exit_code(&self) -> Option<i32> {
+        self.exit_code.get()
+    }
 }
 
 /// This is the `WidgetStore`, which contains a list of `Widget` objects for a GUI window.
 pub struct WidgetStore {
     pub widgets: Vec<WidgetContainer>,
     pub layout_managers: Vec<LayoutManagerContainer>,
+    layout_containers: HashMap<WidgetId, Vec<i32>>,
+    focused_widget_id: Option<WidgetId>,
+    pending_events: Vec<CallbackEvent>,
+    quit_handle: QuitHandle,
+    resource_loader: ResourceLoader,
+    font_registry: FontRegistry,
+    texture_cache: TextureCache,
+    damaged_areas: Vec<Rect>,
+    next_sequence: u64,
 }
 
+/// A snapshot of the `Widget` tree, taken when drawing or hit testing: the children of each
+/// `Widget` in stacking order, and the origin of each `Widget` in window coordinates, both indexed
+/// by ID.
+struct WidgetTree {
+    child_map: Vec<Vec<WidgetId>>,
+    window_origins: Vec<Point>,
+}
+
+/// Adds a task to a layout pass.  A layout manager is only queued once per pass, and a `Widget`
+/// that is resized more than once keeps the size that its children were last adapted to.
+fn queue_layout_task(
+    tasks: &mut Vec<LayoutTask>,
+    queued_managers: &mut HashSet<i32>,
+    task: LayoutTask,
+) {
+    let queued = match task {
+        LayoutTask::Manager(manager_id) => !queued_managers.insert(manager_id),
+        LayoutTask::Resized(widget_id, _) => tasks.iter().any(|x| match x {
+            LayoutTask::Resized(id, _) => *id == widget_id,
+            _ => false,
+        }),
+    };
+
+    if !queued {
+        tasks.push(task);
+    }
+}
+
+/// The maximum number of separate damaged areas that are tracked.  Beyond this, all areas are
+/// merged into a single area, as redrawing it is cheaper than walking the `Widget` tree for each.
+const MAX_DAMAGED_AREAS: usize = 16;
+
 impl WidgetStore {
     /// Constructor, creates a new `WidgetStore`, assigning a top-level `CanvasWidget` as the
     /// very top-level widget.  All `Widget` objects added will be a parent to this `Widget`,
-    /// which is stored at ID 0.  If this `Widget` object ever becomes invalidated, the entire
-    /// window is force refreshed.
+    /// which is stored at `WidgetId::ROOT`.  If this `Widget` object ever becomes invalidated,
+    /// the entire window is force refreshed.
     pub fn new() -> Self {
         let mut widgets_list: Vec<WidgetContainer> = Vec::new();
         let mut base_widget = CanvasWidget::new();
@@ -65,13 +189,26 @@ impl WidgetStore {
         widgets_list.push(WidgetContainer {
             widget: RefCell::new(Box::new(base_widget)),
             widget_name: String::from("_WidgetStoreBase"),
-            widget_id: 0,
-            parent_id: 0,
+            widget_id: WidgetId::ROOT,
+            parent_id: WidgetId::ROOT,
+            z_index: 0,
+            sequence: 0,
+            drawn_bounds: None,
+            removed: false,
         });
 
         Self {
             widgets: widgets_list,
             layout_managers: Vec::new(),
+            layout_containers: HashMap::new(),
+            focused_widget_id: None,
+            pending_events: Vec::new(),
+            quit_handle: QuitHandle::new(),
+            resource_loader: ResourceLoader::shared(),
+            font_registry: FontRegistry::shared(),
+            texture_cache: TextureCache::shared(),
+            damaged_areas: Vec::new(),
+            next_sequence: 1,
         }
     }
 
@@ -82,81 +219,236 @@ impl WidgetStore {
             .for_each(|x| x.widget.borrow_mut().invalidate());
     }
 
-    /// Indicates whether or not a widget in the store has been invalidated.
+    /// Indicates whether or not a widget in the store has been invalidated, or an area of the
+    /// window has been damaged.
     pub fn needs_repaint(&mut self) -> bool {
-        self.widgets
-            .iter_mut()
-            .map(|x| x.widget.borrow_mut().is_invalidated())
-            .find(|x| x == &true)
-            .unwrap_or(false)
+        !self.damaged_areas.is_empty()
+            || self.widgets.iter().any(|x| {
+                let mut widget = x.widget.borrow_mut();
+
+                widget.is_drawable() && widget.is_invalidated()
+            })
+    }
+
+    /// Marks an area of the window as damaged, so that every `Widget` that overlaps the area is
+    /// redrawn in the next frame.  Damaged areas that overlap are merged into a single area.
+    pub fn damage_area(&mut self, area: Rect) {
+        if area.is_empty() {
+            return;
+        }
+
+        let mut area = area;
+
+        while let Some(pos) = self.damaged_areas.iter().position(|x| x.intersects(&area)) {
+            area = area.union(&self.damaged_areas.swap_remove(pos));
+        }
+
+        self.damaged_areas.push(area);
+
+        if self.damaged_areas.len() > MAX_DAMAGED_AREAS {
+            let merged = self
+                .damaged_areas
+                .drain(..)
+                .fold(Rect::default(), |acc, x| acc.union(&x));
+
+            self.damaged_areas.push(merged);
+        }
+    }
+
+    /// Damages the areas covered by each invalidated `Widget`: the area in which it was last
+    /// drawn, and its current bounds, unless it is hidden.  `Widget`s that were moved along with
+    /// one of their parents are damaged the same way.  Returns the IDs of the invalidated
+    /// `Widget`s.
+    fn damage_invalidated_widgets(&mut self, widget_tree: &WidgetTree) -> Vec<WidgetId> {
+        let mut invalidated_ids = Vec::new();
+        let mut areas = Vec::new();
+
+        for container in self.widgets.iter_mut() {
+            let mut widget = container.widget.borrow_mut();
+
+            if !widget.is_drawable() {
+                continue;
+            }
+
+            let bounds = make_rect(
+                &widget_tree.window_origins[container.widget_id.index()],
+                &widget.config().get_size(CONFIG_BODY_SIZE),
+            );
+            let is_invalidated = widget.is_invalidated();
+            let is_moved = match &container.drawn_bounds {
+                Some(drawn_bounds) => *drawn_bounds != bounds,
+                None => false,
+            };
+
+            if !is_invalidated && !is_moved {
+                continue;
+            }
+
+            if is_invalidated {
+                invalidated_ids.push(container.widget_id);
+            }
+
+            if let Some(drawn_bounds) = container.drawn_bounds.take() {
+                areas.push(drawn_bounds);
+            }
+
+            if !widget.config().get_toggle(CONFIG_WIDGET_HIDDEN) {
+                areas.push(bounds);
+            }
+        }
+
+        areas.into_iter().for_each(|x| self.damage_area(x));
+
+        invalidated_ids
+    }
+
+    /// Clears the invalidated `Widget`s and damaged areas without drawing anything, as if a frame
+    /// had been drawn.  This is used when there is nothing to draw into, such as with a headless
+    /// backend.
+    pub fn discard_damage(&mut self) {
+        let widget_tree = self.get_widget_tree();
+        let invalidated_ids = self.damage_invalidated_widgets(&widget_tree);
+
+        self.damaged_areas.clear();
+
+        for invalidated_id in invalidated_ids {
+            self.widgets[invalidated_id.index()]
+                .widget
+                .borrow_mut()
+                .clear_invalidate();
+        }
     }
 
-    /// Adds a `Widget` to the stack by name.
-    pub fn add_widget(&mut self, name: &str, mut widget: Box<dyn Widget>) -> i32 {
-        let widget_size = self.widgets.len() as i32;
+    /// Returns the origin of each `Widget` in window coordinates, indexed by ID.
+    fn get_window_origins(&self, child_map: &[Vec<WidgetId>]) -> Vec<Point> {
+        let mut window_origins: Vec<Point> = vec![make_origin_point(); self.widgets.len()];
+        let mut parent_ids: Vec<WidgetId> = vec![WidgetId::ROOT];
+
+        window_origins[0] = self.widgets[0]
+            .widget
+            .borrow_mut()
+            .config()
+            .get_point(CONFIG_ORIGIN);
+
+        while let Some(parent_id) = parent_ids.pop() {
+            let parent_origin = window_origins[parent_id.index()].clone();
+
+            for &child_id in &child_map[parent_id.index()] {
+                let origin = self.widgets[child_id.index()]
+                    .widget
+                    .borrow_mut()
+                    .config()
+                    .get_point(CONFIG_ORIGIN);
+
+                window_origins[child_id.index()] = Point {
+                    x: parent_origin.x + origin.x,
+                    y: parent_origin.y + origin.y,
+                };
+                parent_ids.push(child_id);
+            }
+        }
+
+        window_origins
+    }
+
+    /// Converts a `Point` relative to the origin of a `Widget` by ID into window coordinates.
+    /// Returns `None` if the ID is not valid.  (See `to_window_coords`.)
+    pub fn to_window_coords(&self, widget_id: WidgetId, point: Point) -> Option<Point> {
+        to_window_coords(&self.widgets, widget_id, point)
+    }
+
+    /// Converts a `Point` in window coordinates into a `Point` relative to the origin of a
+    /// `Widget` by ID.  Returns `None` if the ID is not valid.  (See `to_widget_coords`.)
+    pub fn to_widget_coords(&self, widget_id: WidgetId, point: Point) -> Option<Point> {
+        to_widget_coords(&self.widgets, widget_id, point)
+    }
+
+    /// Adds a `Widget` to the stack by name, and returns its ID.  The slot of a removed `Widget` is
+    /// reused if there is one, with the next generation, so the IDs of removed `Widget`s never
+    /// refer to the new `Widget`.
+    pub fn add_widget(&mut self, name: &str, mut widget: Box<dyn Widget>) -> WidgetId {
+        let widget_id = match self.widgets.iter().position(|x| x.removed) {
+            Some(index) => WidgetId::new(index, self.widgets[index].widget_id.generation() + 1),
+            None => WidgetId::new(self.widgets.len(), 0),
+        };
 
         // #117 - assigns widget ID to itself
-        widget.set_widget_id(widget_size);
+        widget.set_widget_id(widget_id);
 
         let container = WidgetContainer {
             widget: RefCell::new(widget),
             widget_name: String::from(name),
-            widget_id: widget_size,
-            parent_id: 0,
+            widget_id,
+            parent_id: WidgetId::ROOT,
+            z_index: 0,
+            sequence: self.next_sequence,
+            drawn_bounds: None,
+            removed: false,
         };
 
-        self.widgets.push(container);
+        self.next_sequence += 1;
+
+        if widget_id.index() < self.widgets.len() {
+            self.widgets[widget_id.index()] = container;
+        } else {
+            self.widgets.push(container);
+        }
 
-        widget_size
+        widget_id
     }
 
-    fn set_parent_for_widget(&mut self, widget_id: i32, parent_id: i32) {
-        let mut container = self.widgets.get_mut(widget_id as usize).unwrap();
+    fn set_parent_for_widget(&mut self, widget_id: WidgetId, parent_id: WidgetId) {
+        let mut container = self.widgets.get_mut(widget_id.index()).unwrap();
 
         container.parent_id = parent_id;
     }
 
-    /// Adds a `Widget` object to the parent specified by ID.
+    /// Adds a `Widget` object to the parent specified by ID.  If the parent ID is not valid, or the
+    /// parent has been removed, the `Widget` is not added, and `PushrodError::InvalidParent` is
+    /// returned.
     pub fn add_widget_to_parent(
         &mut self,
         name: &str,
         widget: Box<dyn Widget>,
-        parent_id: i32,
-    ) -> i32 {
+        parent_id: WidgetId,
+    ) -> Result<WidgetId, PushrodError> {
+        if !self.is_valid_widget_id(parent_id) {
+            return Err(PushrodError::InvalidParent { parent_id });
+        }
+
         let widget_id = self.add_widget(name, widget);
 
         self.set_parent_for_widget(widget_id, parent_id);
 
-        widget_id
+        Ok(widget_id)
     }
 
-    fn get_layout_manager_widget_id(&mut self, manager_id: i32) -> i32 {
-        let layout_container = self.layout_managers[manager_id as usize]
-            .layout_manager
-            .borrow_mut();
+    fn get_layout_manager_widget_id(&self, manager_id: i32) -> Option<WidgetId> {
+        if manager_id < 0 {
+            return None;
+        }
 
-        layout_container.get_widget_id()
+        self.layout_managers
+            .get(manager_id as usize)
+            .map(|container| container.layout_manager.borrow().get_widget_id())
     }
 
+    /// Adds a `Widget` object to the `LayoutManager` specified by ID, at the `position` in its
+    /// layout.  The `Widget` becomes a child of the `Widget` that the `LayoutManager` manages.
+    /// If the manager ID is not valid, `PushrodError::InvalidLayoutManager` is returned, and if
+    /// its managed `Widget` has been removed, `PushrodError::InvalidParent` is returned.  In either
+    /// case, the `Widget` is not added.
     pub fn add_widget_to_layout_manager(
         &mut self,
         name: &str,
         widget: Box<dyn Widget>,
         manager_id: i32,
         position: Point,
-    ) -> i32 {
-        let widget_id = self.add_widget(name, widget);
-
-        eprintln!("[Add widget to layout manager] widget_id={}", widget_id);
-
-        let layout_widget_id = self.get_layout_manager_widget_id(manager_id);
-
-        self.set_parent_for_widget(widget_id, layout_widget_id);
-
-        eprintln!(
-            "[Add widget to layout manager] layout_widget_id={}",
-            layout_widget_id
-        );
+    ) -> Result<WidgetId, PushrodError> {
+        let layout_widget_id = self
+            .get_layout_manager_widget_id(manager_id)
+            .ok_or(PushrodError::InvalidLayoutManager { manager_id })?;
+        let widget_id = self.add_widget_to_parent(name, widget, layout_widget_id)?;
 
         // Add to the widget_store with the widget_id and position
         let layout_container = &self.layout_managers[manager_id as usize];
@@ -167,193 +459,755 @@ impl WidgetStore {
             .borrow_mut()
             .push(position);
 
-        widget_id
+        Ok(widget_id)
     }
 
     /// Gets the parent of the child `Widget` by ID.  If the child has no assigned parent, the
-    /// top-level `CanvasWidget` is returned (ID 0).
-    pub fn get_parent_of(&mut self, widget_id: i32) -> i32 {
-        if widget_id <= 0 {
-            0
-        } else {
-            self.widgets[widget_id as usize].parent_id
-        }
+    /// top-level `CanvasWidget` is returned (`WidgetId::ROOT`), which is also its own parent.
+    /// Returns `None` if the ID is not valid.
+    pub fn get_parent_of(&self, widget_id: WidgetId) -> Option<WidgetId> {
+        get_widget_container(&self.widgets, widget_id).map(|x| x.parent_id)
     }
 
     /// Returns a list of the children that are owned by a parent ID.  Does not return a list of
     /// siblings, only the first-level children.
-    pub fn get_children_of(&self, parent_id: i32) -> Vec<i32> {
+    pub fn get_children_of(&self, parent_id: WidgetId) -> Vec<WidgetId> {
         self.widgets
             .iter()
-            .filter(|x| x.parent_id == parent_id)
+            .filter(|x| x.parent_id == parent_id && !x.removed)
             .map(|x| x.widget_id)
             .collect()
     }
 
     /// Gets a `Widget` by ID for a point in the screen.  If the GUI object is hidden or disabled,
-    /// the ID is not returned.  If no widget is found under the point specified, an ID of -1 is
-    /// returned.
-    pub fn get_widget_id_for_point(&mut self, point: Point) -> i32 {
-        let mut found_id = -1;
+    /// the ID is not returned.  If more than one `Widget` is under the point, the topmost one is
+    /// returned: `Widget`s are searched in the reverse of the order in which they are drawn.  A
+    /// `Widget` is only found inside the bounds of its parents, unless it sets `CONFIG_NO_CLIP`.
+    /// If no widget is found under the point specified, `None` is returned.
+    pub fn get_widget_id_for_point(&mut self, point: Point) -> Option<WidgetId> {
+        let widget_tree = self.get_widget_tree();
+
+        self.find_widget_id_for_point(WidgetId::ROOT, &widget_tree, &point, None)
+    }
 
-        for (pos, obj) in self.widgets.iter_mut().enumerate() {
-            let hidden = *&obj
-                .widget
-                .borrow_mut()
-                .config()
-                .get_toggle(CONFIG_WIDGET_HIDDEN);
-            let disabled = *&obj
-                .widget
-                .borrow_mut()
-                .config()
-                .get_toggle(CONFIG_WIDGET_DISABLED);
-
-            if !hidden && !disabled {
-                let widget_point = &obj.widget.borrow_mut().config().get_point(CONFIG_ORIGIN);
-                let widget_size: crate::core::point::Size =
-                    obj.widget.borrow_mut().config().get_size(CONFIG_BODY_SIZE);
-
-                // Skip over item widgets that have a width and height of 0.
-                if widget_size.w > 0 && widget_size.h > 0 {
-                    if point.x >= widget_point.x
-                        && point.x <= widget_point.x + widget_size.w
-                        && point.y >= widget_point.y
-                        && point.y <= widget_point.y + widget_size.h
-                    {
-                        found_id = pos as i32;
-                    }
+    /// Searches a `Widget` by ID and its children for the topmost `Widget` under a point, within
+    /// the clipping area set by its parents, if any.  Hidden `Widget`s are skipped along with their
+    /// children.
+    fn find_widget_id_for_point(
+        &self,
+        widget_id: WidgetId,
+        widget_tree: &WidgetTree,
+        point: &Point,
+        clip_area: Option<&Rect>,
+    ) -> Option<WidgetId> {
+        let (bounds, disabled, no_clip) = {
+            let mut widget = self.widgets[widget_id.index()].widget.borrow_mut();
+
+            if widget.config().get_toggle(CONFIG_WIDGET_HIDDEN) {
+                return None;
+            }
+
+            (
+                make_rect(
+                    &widget_tree.window_origins[widget_id.index()],
+                    &widget.config().get_size(CONFIG_BODY_SIZE),
+                ),
+                widget.config().get_toggle(CONFIG_WIDGET_DISABLED),
+                widget.config().get_toggle(CONFIG_NO_CLIP),
+            )
+        };
+        let clip_area = if no_clip { None } else { clip_area };
+        let child_clip_area = get_child_clip_area(widget_id, &bounds, clip_area);
+
+        for &child_id in widget_tree.child_map[widget_id.index()].iter().rev() {
+            if let Some(found_id) = self.find_widget_id_for_point(
+                child_id,
+                widget_tree,
+                point,
+                child_clip_area.as_ref(),
+            ) {
+                return Some(found_id);
+            }
+        }
+
+        let in_clip_area = match clip_area {
+            Some(clip_area) => clip_area.contains(point),
+            None => true,
+        };
+
+        if !disabled && in_clip_area && bounds.contains(point) {
+            Some(widget_id)
+        } else {
+            None
+        }
+    }
+
+    /// Returns the name of the `Widget` by specified ID.  Returns `None` if the ID is not valid.
+    pub fn get_name_for_widget_id(&self, widget_id: WidgetId) -> Option<&str> {
+        get_widget_container(&self.widgets, widget_id).map(|x| x.widget_name.as_str())
+    }
+
+    /// Retrieves a widget ID for the name specified.  Returns `None` if no `Widget` has the name.
+    pub fn get_widget_id_for_name(&self, name: &str) -> Option<WidgetId> {
+        self.widgets
+            .iter()
+            .find(|x| !x.removed && x.widget_name == name)
+            .map(|x| x.widget_id)
+    }
+
+    /// Retrieves a reference to a `Widget` by its name.  Returns `None` if no `Widget` has the
+    /// name.
+    pub fn get_widget_for_name(&self, name: &str) -> Option<&RefCell<Box<dyn Widget>>> {
+        self.widgets
+            .iter()
+            .find(|x| !x.removed && x.widget_name == name)
+            .map(|x| &x.widget)
+    }
+
+    /// Retrieves a `Widget` by its ID.  Returns `None` if the ID is not valid.
+    pub fn get_widget_for_id(&self, widget_id: WidgetId) -> Option<&RefCell<Box<dyn Widget>>> {
+        get_widget_container(&self.widgets, widget_id).map(|x| &x.widget)
+    }
+
+    /// Retrieves a `Widget` by its ID as its concrete type, so that methods specific to that type
+    /// can be used.  Returns `None` if the ID is not valid, or if the `Widget` is not a `T`.  The
+    /// `Widget` stays borrowed until the returned reference is dropped.
+    ///
+    /// Example:
+    /// ```
+    /// # use pushrod::core::widget_store::*;
+    /// # use pushrod::widget::push_button_widget::*;
+    /// # use pushrod::widget::timer_widget::*;
+    /// let mut widget_store = WidgetStore::new();
+    /// let timer_id = widget_store.add_widget("Timer", Box::new(TimerWidget::new()));
+    ///
+    /// assert!(widget_store.get_widget_as::<TimerWidget>(timer_id).is_some());
+    /// assert!(widget_store.get_widget_as::<PushButtonWidget>(timer_id).is_none());
+    /// ```
+    pub fn get_widget_as<T: Widget>(&self, widget_id: WidgetId) -> Option<Ref<'_, T>> {
+        let widget = self.get_widget_for_id(widget_id)?.borrow();
+
+        Ref::filter_map(widget, |x| x.as_ref().as_any().downcast_ref::<T>()).ok()
+    }
+
+    /// Retrieves a `Widget` by its ID as its concrete type, borrowing it mutably.  Returns `None`
+    /// if the ID is not valid, or if the `Widget` is not a `T`.  (See `get_widget_as`.)
+    ///
+    /// Example:
+    /// ```
+    /// # use pushrod::core::widget_store::*;
+    /// # use pushrod::widget::timer_widget::*;
+    /// let mut widget_store = WidgetStore::new();
+    /// let timer_id = widget_store.add_widget("Timer", Box::new(TimerWidget::new()));
+    ///
+    /// let mut timer = widget_store.get_widget_as_mut::<TimerWidget>(timer_id).unwrap();
+    ///
+    /// timer.on_tick(|_| eprintln!("Tick"));
+    /// ```
+    pub fn get_widget_as_mut<T: Widget>(&self, widget_id: WidgetId) -> Option<RefMut<'_, T>> {
+        let widget = self.get_widget_for_id(widget_id)?.borrow_mut();
+
+        RefMut::filter_map(widget, |x| x.as_mut().as_any_mut().downcast_mut::<T>()).ok()
+    }
+
+    /// Indicates whether or not an ID refers to a `Widget` in the store: it must not be
+    /// `WidgetId::NONE`, and the `Widget` it was issued for must not have been removed.
+    pub fn is_valid_widget_id(&self, widget_id: WidgetId) -> bool {
+        get_widget_container(&self.widgets, widget_id).is_some()
+    }
+
+    // --- Tree Editing Routines ---
+
+    /// Returns the IDs of a `Widget` and all of its descendants, parents first.
+    fn get_subtree_ids(&self, widget_id: WidgetId) -> Vec<WidgetId> {
+        let mut subtree_ids = vec![widget_id];
+        let mut pos = 0;
+
+        while pos < subtree_ids.len() {
+            let parent_id = subtree_ids[pos];
+
+            for child_id in self.get_children_of(parent_id) {
+                if child_id != parent_id {
+                    subtree_ids.push(child_id);
                 }
             }
+
+            pos += 1;
         }
 
-        found_id
+        subtree_ids
     }
 
-    /// Returns the name of the `Widget` by specified ID.
-    pub fn get_name_for_widget_id(&mut self, widget_id: i32) -> &str {
-        self.widgets[widget_id as usize].widget_name.as_str()
+    /// Removes a list of `Widget` IDs from the layout managers that position them, and returns the
+    /// IDs of the managers that need to lay out their remaining `Widget`s again.  Managers whose
+    /// container `Widget` is in the list lose all of their `Widget`s, and are not laid out again.
+    fn remove_from_layout_managers(&mut self, widget_ids: &[WidgetId]) -> Vec<i32> {
+        let mut manager_ids = Vec::new();
+
+        for layout_container in &self.layout_managers {
+            let container_widget_id = layout_container.layout_manager.borrow().get_widget_id();
+            let mut ids = layout_container.widget_ids.borrow_mut();
+            let mut positions = layout_container.widget_positions.borrow_mut();
+
+            if widget_ids.contains(&container_widget_id) {
+                ids.clear();
+                positions.clear();
+                continue;
+            }
+
+            let mut pos = 0;
+            let mut changed = false;
+
+            while pos < ids.len() {
+                if widget_ids.contains(&ids[pos]) {
+                    ids.remove(pos);
+                    positions.remove(pos);
+                    changed = true;
+                } else {
+                    pos += 1;
+                }
+            }
+
+            if changed {
+                manager_ids.push(layout_container.container_id);
+            }
+        }
+
+        self.layout_containers
+            .retain(|widget_id, _| !widget_ids.contains(widget_id));
+
+        manager_ids
     }
 
-    /// Retrieves a widget ID for the name specified.  Returns top-level `CanvasWidget` ID if
-    /// not found.
-    pub fn get_widget_id_for_name(&mut self, name: &str) -> i32 {
-        match self
-            .widgets
-            .iter_mut()
-            .find(|x| x.widget_name == String::from(name))
-        {
-            Some(x) => x.widget_id,
-            None => 0,
+    /// Removes a `Widget` by ID from the store, along with all of its children.  The `Widget`s are
+    /// also removed from any layout managers that position them, and the remaining `Widget`s in
+    /// those managers are laid out again.  If a removed `Widget` has keyboard focus, it is sent a
+    /// `WidgetFocusLost` event before it is removed, and the focus is cleared, as with
+    /// `set_focused_widget_id`.  The area that the removed `Widget`s covered is redrawn.
+    ///
+    /// The IDs of the other `Widget`s do not change.  The IDs of the removed `Widget`s no longer
+    /// refer to any `Widget`, even once their slots are reused, and `is_valid_widget_id` returns
+    /// `false` for them.  Returns `false` if the ID is not valid, or refers to the top-level
+    /// `Widget` (`WidgetId::ROOT`), which cannot be removed.
+    pub fn remove_widget(&mut self, widget_id: WidgetId) -> bool {
+        if widget_id == WidgetId::ROOT || !self.is_valid_widget_id(widget_id) {
+            return false;
         }
+
+        let removed_ids = self.get_subtree_ids(widget_id);
+
+        if let Some(focused_widget_id) = self.focused_widget_id {
+            if removed_ids.contains(&focused_widget_id) {
+                let event = CallbackEvent::WidgetFocusLost {
+                    widget_id: focused_widget_id,
+                };
+
+                // The `Widget` is gone by the time the run loop dispatches the event, so it
+                // receives the event now.
+                self.widgets[focused_widget_id.index()]
+                    .widget
+                    .borrow_mut()
+                    .handle_event(false, event, Some(&self.widgets));
+                self.set_focused_widget_id(None);
+            }
+        }
+
+        let manager_ids = self.remove_from_layout_managers(&removed_ids);
+
+        for removed_id in removed_ids.iter().cloned() {
+            let mut placeholder = CanvasWidget::new();
+
+            placeholder.set_toggle(CONFIG_WIDGET_HIDDEN, true);
+            placeholder.set_toggle(CONFIG_WIDGET_DISABLED, true);
+
+            let container = &mut self.widgets[removed_id.index()];
+
+            // Removed `Widget`s are their own parents, like the top-level `Widget`, so they are
+            // no longer part of the `Widget` tree.  Their slots are reused by `add_widget`.
+            container.widget = RefCell::new(Box::new(placeholder));
+            container.widget_name = String::new();
+            container.parent_id = removed_id;
+            container.z_index = 0;
+            container.removed = true;
+
+            if let Some(drawn_bounds) = container.drawn_bounds.take() {
+                self.damage_area(drawn_bounds);
+            }
+        }
+
+        self.run_layout_pass(manager_ids.into_iter().map(LayoutTask::Manager).collect());
+
+        true
     }
 
-    /// Retrieves a reference to a `Widget` by its name.  Returns the top-level `CanvasWidget`
-    /// object if not found.
-    pub fn get_widget_for_name(&mut self, name: &str) -> &RefCell<Box<dyn Widget>> {
-        let widget_id = match self
-            .widgets
-            .iter_mut()
-            .find(|x| x.widget_name == String::from(name))
+    /// Moves a `Widget` by ID, along with all of its children, to a new parent by ID.  As the
+    /// origin of a `Widget` is relative to its parent, the `Widget` keeps its origin within the
+    /// new parent.  It is removed from any layout manager that positions it, and is stacked
+    /// above its new siblings.  Returns `false` if either ID is not valid, if the `Widget` is the
+    /// top-level `Widget` (`WidgetId::ROOT`), or if the new parent is the `Widget` itself or one of
+    /// its children.
+    pub fn reparent_widget(&mut self, widget_id: WidgetId, parent_id: WidgetId) -> bool {
+        if widget_id == WidgetId::ROOT
+            || !self.is_valid_widget_id(widget_id)
+            || !self.is_valid_widget_id(parent_id)
+            || self.get_subtree_ids(widget_id).contains(&parent_id)
         {
-            Some(x) => x.widget_id,
-            None => 0,
+            return false;
+        }
+
+        let manager_ids = self.remove_from_layout_managers(&[widget_id]);
+        let z_index = self
+            .get_children_of(parent_id)
+            .iter()
+            .filter(|x| **x != parent_id && **x != widget_id)
+            .map(|x| self.widgets[x.index()].z_index + 1)
+            .max()
+            .unwrap_or(0);
+
+        self.set_parent_for_widget(widget_id, parent_id);
+        self.widgets[widget_id.index()].z_index = z_index;
+        self.invalidate_widget_tree(widget_id);
+
+        self.run_layout_pass(manager_ids.into_iter().map(LayoutTask::Manager).collect());
+
+        true
+    }
+
+    /// Replaces the `Widget` stored at an ID with a new `Widget`, returning the `Widget` that was
+    /// replaced.  The new `Widget` takes over the ID, name, parent, children, stacking order, and
+    /// layout manager position of the old `Widget`.  If the old `Widget` had keyboard focus, and
+    /// the new `Widget` cannot receive it, the focus is cleared.  Returns `None` if the ID is not
+    /// valid, or refers to the top-level `Widget` (`WidgetId::ROOT`), in which case the new
+    /// `Widget` is dropped.
+    pub fn replace_widget(
+        &mut self,
+        widget_id: WidgetId,
+        mut widget: Box<dyn Widget>,
+    ) -> Option<Box<dyn Widget>> {
+        if widget_id == WidgetId::ROOT || !self.is_valid_widget_id(widget_id) {
+            return None;
+        }
+
+        widget.set_widget_id(widget_id);
+        widget.invalidate();
+
+        let old_widget = self.widgets[widget_id.index()].widget.replace(widget);
+
+        if self.focused_widget_id == Some(widget_id) && !self.can_focus_widget(widget_id) {
+            self.set_focused_widget_id(None);
+        }
+
+        let tasks = self
+            .layout_managers
+            .iter()
+            .filter(|x| x.widget_ids.borrow().contains(&widget_id))
+            .map(|x| LayoutTask::Manager(x.container_id))
+            .collect();
+
+        self.run_layout_pass(tasks);
+
+        Some(old_widget)
+    }
+
+    // --- Quit Routines ---
+
+    /// Requests that the run loop quit after the current event has been handled.  The exit status
+    /// is returned from `Pushrod::run`.
+    pub fn quit(&self, exit_code: i32) {
+        self.quit_handle.quit(exit_code);
+    }
+
+    /// Cancels a request to quit the run loop.  This is used to veto a `WindowCloseRequested`
+    /// event.
+    pub fn cancel_quit(&self) {
+        self.quit_handle.cancel();
+    }
+
+    /// Retrieves a `QuitHandle` that can be used to quit the run loop from a `Widget` callback.
+    pub fn get_quit_handle(&self) -> QuitHandle {
+        self.quit_handle.clone()
+    }
+
+    // --- Resource Routines ---
+
+    /// Retrieves the `ResourceLoader` used to locate fonts and images.  This is the loader shared
+    /// by all `Widget`s on the current thread, so search roots and in-memory resources added to it
+    /// apply to `Widget`s created afterward.
+    pub fn get_resource_loader(&self) -> ResourceLoader {
+        self.resource_loader.clone()
+    }
+
+    /// Retrieves the `FontRegistry` that `Widget`s load their fonts from.  This is the registry
+    /// shared by all `Widget`s on the current thread, and can be used to measure text.
+    pub fn get_font_registry(&self) -> FontRegistry {
+        self.font_registry.clone()
+    }
+
+    /// Retrieves the `TextureCache` that `Widget`s load their images from.  This is the cache
+    /// shared by all `Widget`s on the current thread, and can be used to reload images that have
+    /// changed on disk.
+    pub fn get_texture_cache(&self) -> TextureCache {
+        self.texture_cache.clone()
+    }
+
+    // --- Focus Routines ---
+
+    /// Retrieves the ID of the `Widget` that currently has keyboard focus, or `None` if no `Widget`
+    /// has focus.
+    pub fn get_focused_widget_id(&self) -> Option<WidgetId> {
+        self.focused_widget_id
+    }
+
+    /// Indicates whether or not a `Widget` can currently receive keyboard focus: it must accept
+    /// focus, and must be neither hidden nor disabled.
+    fn can_focus_widget(&self, widget_id: WidgetId) -> bool {
+        let container = match get_widget_container(&self.widgets, widget_id) {
+            Some(container) => container,
+            None => return false,
         };
+        let mut widget = container.widget.borrow_mut();
 
-        self.get_widget_for_id(widget_id)
+        widget.accepts_focus()
+            && !widget.config().get_toggle(CONFIG_WIDGET_HIDDEN)
+            && !widget.config().get_toggle(CONFIG_WIDGET_DISABLED)
     }
 
-    /// Retrieves a `Widget` by its ID.
-    pub fn get_widget_for_id(&mut self, id: i32) -> &RefCell<Box<dyn Widget>> {
-        &self.widgets[id as usize].widget
+    /// Moves keyboard focus to the `Widget` specified by ID.  If the `Widget` cannot receive
+    /// focus (or `None` is specified), the focus is cleared.  A `WidgetFocusLost` event is
+    /// generated for the `Widget` that previously had focus, and a `WidgetFocusGained` event
+    /// is generated for the newly focused `Widget`; these are dispatched by the run loop.
+    pub fn set_focused_widget_id(&mut self, widget_id: Option<WidgetId>) {
+        let widget_id = widget_id.filter(|x| self.can_focus_widget(*x));
+
+        if widget_id == self.focused_widget_id {
+            return;
+        }
+
+        if let Some(focused_widget_id) = self.focused_widget_id {
+            self.pending_events.push(CallbackEvent::WidgetFocusLost {
+                widget_id: focused_widget_id,
+            });
+        }
+
+        self.focused_widget_id = widget_id;
+
+        if let Some(widget_id) = widget_id {
+            self.pending_events
+                .push(CallbackEvent::WidgetFocusGained { widget_id });
+        }
+    }
+
+    /// Moves keyboard focus to the next `Widget` (by its slot in the store) that can receive
+    /// focus, wrapping around to the first.  If `reverse` is set, focus moves to the previous
+    /// `Widget` instead.  This is used for `Tab` and `Shift-Tab` keyboard traversal.
+    pub fn focus_next_widget(&mut self, reverse: bool) {
+        let widget_count = self.widgets.len() as i32;
+        let start_index = match self.focused_widget_id {
+            Some(widget_id) => widget_id.index() as i32,
+            None if reverse => 0,
+            None => widget_count - 1,
+        };
+
+        for offset in 1..=widget_count {
+            let next_index = if reverse {
+                (start_index - offset).rem_euclid(widget_count)
+            } else {
+                (start_index + offset).rem_euclid(widget_count)
+            };
+            let next_id = self.widgets[next_index as usize].widget_id;
+
+            if self.can_focus_widget(next_id) {
+                self.set_focused_widget_id(Some(next_id));
+                return;
+            }
+        }
+    }
+
+    /// Takes the list of events that have been generated by the `WidgetStore` itself (such as
+    /// focus changes), so that they can be dispatched by the run loop.
+    pub(crate) fn take_pending_events(&mut self) -> Vec<CallbackEvent> {
+        std::mem::take(&mut self.pending_events)
     }
 
     // --- Layout Manager Routines ---
 
+    /// Adds a layout manager to the store, returning its ID.  The manager lays out its `Widget`s
+    /// within the container `Widget` that it was created with, and is run again whenever that
+    /// `Widget` is resized.
     pub fn add_layout_manager(&mut self, manager: Box<dyn LayoutManager>) -> i32 {
         let managers_size = self.layout_managers.len() as i32;
 
+        self.layout_containers
+            .entry(manager.get_widget_id())
+            .or_default()
+            .push(managers_size);
         self.layout_managers.push(LayoutManagerContainer {
             container_id: managers_size,
             widget_ids: RefCell::new(Vec::new()),
             widget_positions: RefCell::new(Vec::new()),
             layout_manager: RefCell::new(manager),
+            layout_size: None,
         });
 
         managers_size
     }
 
-    fn get_widget_origins(&mut self, manager_id: i32) -> Vec<Point> {
-        let mut widget_ids_copy = self.layout_managers[manager_id as usize]
+    /// Retrieves the IDs of the layout managers whose container is the specified `Widget`.
+    pub fn get_layout_managers_for(&self, widget_id: WidgetId) -> Vec<i32> {
+        self.layout_containers
+            .get(&widget_id)
+            .cloned()
+            .unwrap_or_default()
+    }
+
+    /// Collects the current origins, sizes and positions of the `Widget`s in a layout, along with
+    /// their preferred, minimum and maximum sizes.
+    fn get_layout_coordinates(&mut self, manager_id: i32) -> LayoutManagerCoordinates {
+        let widget_ids = self.layout_managers[manager_id as usize]
             .widget_ids
-            .borrow_mut()
+            .borrow()
             .clone();
+        let mut coordinates = LayoutManagerCoordinates {
+            widget_positions: self.layout_managers[manager_id as usize]
+                .widget_positions
+                .borrow()
+                .clone(),
+            ..LayoutManagerCoordinates::default()
+        };
 
-        widget_ids_copy
-            .iter_mut()
-            .map(|x| {
-                self.get_widget_for_id(*x)
-                    .borrow_mut()
-                    .config()
-                    .get_point(CONFIG_ORIGIN)
-            })
-            .collect()
+        for widget_id in widget_ids {
+            let preferred_size = self.get_preferred_size(widget_id);
+            let mut widget = self.widgets[widget_id.index()].widget.borrow_mut();
+
+            coordinates
+                .widget_origins
+                .push(widget.config().get_point(CONFIG_ORIGIN));
+            coordinates
+                .widget_sizes
+                .push(widget.config().get_size(CONFIG_BODY_SIZE));
+            coordinates.widget_preferred_sizes.push(preferred_size);
+            coordinates
+                .widget_min_sizes
+                .push(widget.config().get_size(CONFIG_MIN_SIZE));
+            coordinates
+                .widget_max_sizes
+                .push(widget.config().get_size(CONFIG_MAX_SIZE));
+        }
+
+        coordinates
     }
 
-    fn get_widget_sizes(&mut self, manager_id: i32) -> Vec<Size> {
-        let mut widget_ids_copy = self.layout_managers[manager_id as usize]
-            .widget_ids
-            .borrow_mut()
-            .clone();
+    /// Retrieves the preferred size of a `Widget` by ID (see `Widget::preferred_size`), without
+    /// its minimum or maximum size applied.  If the `Widget` is the container of a layout
+    /// manager, this is the size the layout needs for all of its `Widget`s to be their preferred
+    /// size, unless the layout manager has no preferred size.  Returns a zero size if the ID is
+    /// not valid.
+    pub fn get_preferred_size(&mut self, widget_id: WidgetId) -> Size {
+        if !self.is_valid_widget_id(widget_id) {
+            return Size::default();
+        }
 
-        widget_ids_copy
-            .iter_mut()
-            .map(|x| {
-                self.get_widget_for_id(*x)
-                    .borrow_mut()
-                    .config()
-                    .get_size(CONFIG_BODY_SIZE)
-            })
-            .collect()
+        if let Some(manager_id) = self.get_layout_managers_for(widget_id).first().cloned() {
+            let coordinates = self.get_layout_coordinates(manager_id);
+            let preferred_size = self.layout_managers[manager_id as usize]
+                .layout_manager
+                .borrow()
+                .get_preferred_size(&coordinates);
+
+            if let Some(preferred_size) = preferred_size {
+                return preferred_size;
+            }
+        }
+
+        self.widgets[widget_id.index()]
+            .widget
+            .borrow_mut()
+            .preferred_size()
     }
 
+    /// Lays out the `Widget`s of a layout manager by ID.  Any `Widget` in the layout that
+    /// changes size adapts its children (see `resize_widget`), so layouts nested inside the
+    /// layout are run again as well.
     pub fn do_layout_for_manager(&mut self, manager_id: i32) {
-        let widget_origins = self.get_widget_origins(manager_id);
-        let widget_sizes = self.get_widget_sizes(manager_id);
-        let widget_positions = self.layout_managers[manager_id as usize]
-            .widget_positions
-            .borrow()
-            .clone();
+        self.run_layout_pass(vec![LayoutTask::Manager(manager_id)]);
+    }
+
+    pub fn adjust_layout_manager(&mut self, manager_id: i32, coordinates: LayoutManagerPadding) {
+        self.layout_managers[manager_id as usize]
+            .layout_manager
+            .borrow_mut()
+            .adjust_layout(coordinates.clone());
+        self.do_layout_for_manager(manager_id);
+    }
+
+    /// Resizes a `Widget` by ID, and adapts its children to the new size: children that are
+    /// positioned by a layout manager are laid out again, and the other children are moved and
+    /// resized according to their anchor (see `CONFIG_ANCHOR`).  Children that change size adapt
+    /// their own children the same way.  Resizing the top-level `Widget` (`WidgetId::ROOT`) to
+    /// the size of the window adapts the whole GUI to it.  Returns `false` if the ID is not
+    /// valid.
+    ///
+    /// A size of zero in either direction, such as the size of a minimized window, is ignored:
+    /// the `Widget` and its children keep their size, so that they are restored unchanged when
+    /// the `Widget` is given a usable size again.
+    pub fn resize_widget(&mut self, widget_id: WidgetId, size: Size) -> bool {
+        if !self.is_valid_widget_id(widget_id) {
+            return false;
+        }
+
+        if size.w <= 0 || size.h <= 0 {
+            return true;
+        }
+
+        let old_size = {
+            let mut widget = self.widgets[widget_id.index()].widget.borrow_mut();
+            let old_size = widget.config().get_size(CONFIG_BODY_SIZE);
+
+            if old_size != size {
+                widget.set_size(CONFIG_BODY_SIZE, size.w, size.h);
+            }
+
+            old_size
+        };
+
+        if old_size != size {
+            self.run_layout_pass(vec![LayoutTask::Resized(widget_id, old_size)]);
+        }
+
+        true
+    }
+
+    /// Lays out again every layout manager whose container `Widget` has changed size since the
+    /// layout was last run, such as a container that was resized with `set_size` in a callback.
+    /// The other children of those containers are adapted to the new size by their anchor.
+    /// Containers with a size of zero in either direction are skipped until they are given a
+    /// usable size again.  This is called by the run loop after events have been handled.
+    pub fn update_layouts(&mut self) {
+        let mut tasks: Vec<LayoutTask> = Vec::new();
+
+        for (widget_id, manager_ids) in &self.layout_containers {
+            let size = self.widgets[widget_id.index()]
+                .widget
+                .borrow_mut()
+                .config()
+                .get_size(CONFIG_BODY_SIZE);
+
+            if size.w <= 0 || size.h <= 0 {
+                continue;
+            }
+
+            let old_size = manager_ids
+                .iter()
+                .filter_map(|x| self.layout_managers[*x as usize].layout_size.clone())
+                .find(|x| *x != size);
+
+            if let Some(old_size) = old_size {
+                tasks.push(LayoutTask::Resized(*widget_id, old_size));
+            }
+        }
+
+        if !tasks.is_empty() {
+            self.run_layout_pass(tasks);
+        }
+    }
+
+    /// Runs a batch of layout tasks, along with the tasks that they cause, in a single pass.
+    /// Tasks are always run from the top of the `Widget` tree down, and a resized container is
+    /// adapted before the layouts it contains are run, so the size of a container is final by the
+    /// time its layout is run.  Each layout is run at most once per pass.
+    fn run_layout_pass(&mut self, initial_tasks: Vec<LayoutTask>) {
+        let mut tasks: Vec<LayoutTask> = Vec::new();
+        let mut queued_managers: HashSet<i32> = HashSet::new();
+
+        for task in initial_tasks {
+            queue_layout_task(&mut tasks, &mut queued_managers, task);
+        }
+
+        while !tasks.is_empty() {
+            let pos = (0..tasks.len())
+                .min_by_key(|x| {
+                    let is_manager = match tasks[*x] {
+                        LayoutTask::Manager(_) => 1,
+                        LayoutTask::Resized(..) => 0,
+                    };
+
+                    (self.get_layout_task_depth(&tasks[*x]), is_manager)
+                })
+                .unwrap();
+            let resized = match tasks.remove(pos) {
+                LayoutTask::Manager(manager_id) => self.run_layout_manager(manager_id),
+                LayoutTask::Resized(widget_id, old_size) => {
+                    for manager_id in self.get_layout_managers_for(widget_id) {
+                        queue_layout_task(
+                            &mut tasks,
+                            &mut queued_managers,
+                            LayoutTask::Manager(manager_id),
+                        );
+                    }
+
+                    self.apply_anchors(widget_id, &old_size)
+                }
+            };
+
+            for (widget_id, old_size) in resized {
+                queue_layout_task(
+                    &mut tasks,
+                    &mut queued_managers,
+                    LayoutTask::Resized(widget_id, old_size),
+                );
+            }
+        }
+    }
+
+    /// Retrieves the depth in the `Widget` tree of the `Widget` that a layout task works on.
+    fn get_layout_task_depth(&self, task: &LayoutTask) -> usize {
+        let mut widget_id = match task {
+            LayoutTask::Manager(manager_id) => self.layout_managers[*manager_id as usize]
+                .layout_manager
+                .borrow()
+                .get_widget_id(),
+            LayoutTask::Resized(widget_id, _) => *widget_id,
+        };
+        let mut depth = 0;
+
+        while widget_id != WidgetId::ROOT && self.is_valid_widget_id(widget_id) {
+            widget_id = self.widgets[widget_id.index()].parent_id;
+            depth += 1;
+        }
+
+        depth
+    }
+
+    /// Runs a layout manager by ID, and returns the IDs of the `Widget`s that it resized, along
+    /// with their previous size.
+    fn run_layout_manager(&mut self, manager_id: i32) -> Vec<(WidgetId, Size)> {
+        let coordinates = self.get_layout_coordinates(manager_id);
         let container_widget_id = self.layout_managers[manager_id as usize]
             .layout_manager
             .borrow_mut()
             .get_widget_id();
-        let master_container_origin = self
-            .get_widget_for_id(container_widget_id)
-            .borrow_mut()
-            .config()
-            .get_point(CONFIG_ORIGIN);
-        let master_container_size = self
-            .get_widget_for_id(container_widget_id)
-            .borrow_mut()
-            .config()
-            .get_size(CONFIG_BODY_SIZE);
+        // The `Widget`s in a layout are children of the container, so they are positioned
+        // relative to its origin.
+        let master_container_origin = make_origin_point();
+        let master_container_size = match self.get_widget_for_id(container_widget_id) {
+            Some(widget) => widget.borrow_mut().config().get_size(CONFIG_BODY_SIZE),
+            None => return Vec::new(),
+        };
         let adjusted_sizes = self.layout_managers[manager_id as usize]
             .layout_manager
             .borrow_mut()
             .do_layout(
                 master_container_origin,
-                master_container_size,
-                LayoutManagerCoordinates {
-                    widget_origins,
-                    widget_sizes,
-                    widget_positions,
-                },
+                master_container_size.clone(),
+                coordinates,
             );
 
         self.layout_managers[manager_id as usize].widget_positions =
             RefCell::new(adjusted_sizes.widget_positions.clone());
+        self.layout_managers[manager_id as usize].layout_size = Some(master_container_size);
 
         let num_widgets = adjusted_sizes.widget_positions.len();
+        let mut resized = Vec::new();
 
         for x in 0..num_widgets {
             let widget_id = self.layout_managers[manager_id as usize]
@@ -361,60 +1215,169 @@ impl WidgetStore {
                 .borrow_mut()[x as usize];
             let point: Point = adjusted_sizes.widget_origins[x].clone();
             let size: Size = adjusted_sizes.widget_sizes[x].clone();
-            let mut widget = self.get_widget_for_id(widget_id).borrow_mut();
-
-            eprintln!("Resizing widget: id={}", widget.get_widget_id());
+            let mut widget = self.widgets[widget_id.index()].widget.borrow_mut();
+            let old_size = widget.config().get_size(CONFIG_BODY_SIZE);
 
             widget.set_point(CONFIG_ORIGIN, point.x, point.y);
-            widget.set_size(CONFIG_BODY_SIZE, size.w, size.h);
+
+            if old_size != size {
+                widget.set_size(CONFIG_BODY_SIZE, size.w, size.h);
+                resized.push((widget_id, old_size));
+            }
         }
 
-        eprintln!("Doing manager layout.");
+        resized
     }
 
-    pub fn adjust_layout_manager(&mut self, manager_id: i32, coordinates: LayoutManagerPadding) {
-        self.layout_managers[manager_id as usize]
-            .layout_manager
+    /// Moves and resizes the children of a `Widget` that has been resized from `old_size`
+    /// according to their anchor, skipping children that are positioned by a layout manager.
+    /// Returns the IDs of the children that changed size, along with their previous size.
+    fn apply_anchors(&mut self, widget_id: WidgetId, old_size: &Size) -> Vec<(WidgetId, Size)> {
+        let new_size = self.widgets[widget_id.index()]
+            .widget
             .borrow_mut()
-            .adjust_layout(coordinates.clone());
-        self.do_layout_for_manager(manager_id);
+            .config()
+            .get_size(CONFIG_BODY_SIZE);
+        let child_ids: Vec<WidgetId> = self
+            .get_children_of(widget_id)
+            .into_iter()
+            .filter(|x| *x != widget_id && !self.is_in_layout_manager(*x))
+            .collect();
+        let mut resized = Vec::new();
+
+        for child_id in child_ids {
+            let mut child = self.widgets[child_id.index()].widget.borrow_mut();
+            let origin = child.config().get_point(CONFIG_ORIGIN);
+            let size = child.config().get_size(CONFIG_BODY_SIZE);
+            let (new_origin, new_child_size) = child
+                .config()
+                .get_anchor(CONFIG_ANCHOR)
+                .apply(&origin, &size, old_size, &new_size);
+
+            if new_origin != origin {
+                child.set_point(CONFIG_ORIGIN, new_origin.x, new_origin.y);
+            }
+
+            if new_child_size != size {
+                child.set_size(CONFIG_BODY_SIZE, new_child_size.w, new_child_size.h);
+                resized.push((child_id, size));
+            }
+        }
+
+        resized
     }
 
-    pub fn resize_layout_managers(&mut self, _w: u32, _h: u32) {
-        let num_layout_managers = self.layout_managers.len();
+    /// Indicates whether or not a `Widget` is positioned by a layout manager.
+    fn is_in_layout_manager(&self, widget_id: WidgetId) -> bool {
+        self.layout_managers
+            .iter()
+            .any(|x| x.widget_ids.borrow().contains(&widget_id))
+    }
 
-        for pos in 0..num_layout_managers {
-            let _layout_manager = self.layout_managers[pos as usize]
-                .layout_manager
-                .borrow_mut();
+    /// Retrieves the shortest amount of time until a `Widget` in the store needs to be woken up.
+    /// Returns `None` if no `Widget` needs to be woken up.  (See `Widget::get_next_wakeup`.)
+    pub fn get_next_wakeup(&mut self) -> Option<Duration> {
+        self.widgets
+            .iter()
+            .filter_map(|x| x.widget.borrow_mut().get_next_wakeup())
+            .min()
+    }
 
-            eprintln!("WARNING: Unimplemented resize_layout_managers");
+    // --- Stacking Order Routines ---
+
+    /// Retrieves the z-index of a `Widget` by ID, which determines its stacking order among its
+    /// siblings.  Returns `None` if the ID is not valid.
+    pub fn get_z_index(&self, widget_id: WidgetId) -> Option<i32> {
+        get_widget_container(&self.widgets, widget_id).map(|x| x.z_index)
+    }
 
-            //            let widget_ids = self.layout_managers[pos as usize].widget_ids.clone();
-            //            let widget_positions = self.layout_managers[pos as usize].widget_positions.clone();
-            //
-            //            layout_manager.resize(
-            //                Size {
-            //                    w: w as i32,
-            //                    h: h as i32,
-            //                },
-            //                widget_ids.borrow().clone(),
-            //                widget_positions.borrow().clone(),
-            //                &self.widgets,
-            //            );
+    /// Sets the z-index of a `Widget` by ID.  `Widget`s with a higher z-index are drawn above
+    /// their siblings, and receive mouse events first.  Siblings with the same z-index are stacked
+    /// in the order in which they were added.  Defaults to 0.
+    pub fn set_z_index(&mut self, widget_id: WidgetId, z_index: i32) {
+        if !self.is_valid_widget_id(widget_id) {
+            return;
+        }
+
+        self.widgets[widget_id.index()].z_index = z_index;
+        self.invalidate_widget_tree(widget_id);
+    }
+
+    /// Moves a `Widget` by ID above all of its siblings.
+    pub fn bring_to_front(&mut self, widget_id: WidgetId) {
+        self.restack_widget(widget_id, |_, last| last);
+    }
+
+    /// Moves a `Widget` by ID below all of its siblings.
+    pub fn send_to_back(&mut self, widget_id: WidgetId) {
+        self.restack_widget(widget_id, |_, _| 0);
+    }
+
+    /// Moves a `Widget` by ID one step up in the stacking order of its siblings.
+    pub fn raise(&mut self, widget_id: WidgetId) {
+        self.restack_widget(widget_id, |pos, last| (pos + 1).min(last));
+    }
+
+    /// Moves a `Widget` by ID one step down in the stacking order of its siblings.
+    pub fn lower(&mut self, widget_id: WidgetId) {
+        self.restack_widget(widget_id, |pos, _| pos.saturating_sub(1));
+    }
+
+    /// Moves a `Widget` by ID to a new position among its siblings, in stacking order.  The
+    /// position is computed from the current position and the last position.  The z-indexes of
+    /// the siblings are renumbered to match their new order.
+    fn restack_widget<F>(&mut self, widget_id: WidgetId, position: F)
+    where
+        F: Fn(usize, usize) -> usize,
+    {
+        if widget_id == WidgetId::ROOT || !self.is_valid_widget_id(widget_id) {
+            return;
+        }
+
+        let parent_id = self.widgets[widget_id.index()].parent_id;
+        let mut siblings = self.get_child_map().swap_remove(parent_id.index());
+        let current_pos = siblings.iter().position(|x| *x == widget_id).unwrap();
+        let new_pos = position(current_pos, siblings.len() - 1);
+
+        if new_pos == current_pos {
+            return;
+        }
+
+        siblings.remove(current_pos);
+        siblings.insert(new_pos, widget_id);
+
+        for (z_index, sibling_id) in siblings.iter().enumerate() {
+            self.widgets[sibling_id.index()].z_index = z_index as i32;
+        }
+
+        self.invalidate_widget_tree(widget_id);
+    }
+
+    /// Invalidates a `Widget` by ID, along with all of its children, so that the area they cover
+    /// is redrawn.
+    fn invalidate_widget_tree(&mut self, widget_id: WidgetId) {
+        self.widgets[widget_id.index()]
+            .widget
+            .borrow_mut()
+            .invalidate();
+
+        for child_id in self.get_children_of(widget_id) {
+            if child_id != widget_id {
+                self.invalidate_widget_tree(child_id);
+            }
         }
     }
 
     // -- Display-related routines --
 
     /// Sets the hidden toggle for a parent, and all of its children.
-    pub fn set_hidden(&mut self, widget_id: i32, state: bool) {
-        if widget_id != 0 {
+    pub fn set_hidden(&mut self, widget_id: WidgetId, state: bool) {
+        if widget_id != WidgetId::ROOT && self.is_valid_widget_id(widget_id) {
             let children = self.get_children_of(widget_id);
 
             children.iter().for_each(|w_id| {
-                if *w_id != 0 && *w_id != widget_id {
-                    &self.widgets[*w_id as usize]
+                if *w_id != WidgetId::ROOT && *w_id != widget_id {
+                    &self.widgets[w_id.index()]
                         .widget
                         .borrow_mut()
                         .set_toggle(CONFIG_WIDGET_HIDDEN, state);
@@ -422,91 +1385,232 @@ impl WidgetStore {
                 }
             });
 
-            &self.widgets[widget_id as usize]
+            &self.widgets[widget_id.index()]
                 .widget
                 .borrow_mut()
                 .set_toggle(CONFIG_WIDGET_HIDDEN, state);
         }
     }
 
-    /// Draws a `Widget` by ID, and any children contained in that `Widget`.  Submitting a draw
-    /// request from ID 0 will redraw the entire screen.
-    pub fn draw(&mut self, widget_id: i32, c: Context, g: &mut GlGraphics, original_fbo: GLuint) {
-        let parents_of_widget = self.get_children_of(widget_id);
+    /// Takes a snapshot of the `Widget` tree, used for drawing and hit testing.
+    fn get_widget_tree(&self) -> WidgetTree {
+        let child_map = self.get_child_map();
+        let window_origins = self.get_window_origins(&child_map);
 
-        if parents_of_widget.len() == 0 {
+        WidgetTree {
+            child_map,
+            window_origins,
+        }
+    }
+
+    /// Returns the list of children for each `Widget`, indexed by the parent's slot.  Each list is
+    /// in stacking order: the order in which the children are drawn, from the bottom up.
+    fn get_child_map(&self) -> Vec<Vec<WidgetId>> {
+        let mut child_map: Vec<Vec<WidgetId>> = vec![Vec::new(); self.widgets.len()];
+
+        for container in &self.widgets {
+            if container.widget_id != container.parent_id {
+                child_map[container.parent_id.index()].push(container.widget_id);
+            }
+        }
+
+        for children in child_map.iter_mut() {
+            children.sort_by_key(|x| {
+                let container = &self.widgets[x.index()];
+
+                (container.z_index, container.sequence)
+            });
+        }
+
+        child_map
+    }
+
+    /// Draws the damaged areas of the window, starting with the `Widget` specified by ID, and any
+    /// children contained in that `Widget`, in stacking order.  Invalidated `Widget`s damage both
+    /// the area in which they were last drawn and their current bounds, so a `Widget` that is
+    /// moved or hidden does not leave anything behind.  Each damaged area is then redrawn by
+    /// drawing only the `Widget`s that overlap it, in drawing order, clipped to the damaged area.
+    /// Submitting a draw request from `WidgetId::ROOT` will redraw every damaged area of the
+    /// screen.
+    pub fn draw(
+        &mut self,
+        widget_id: WidgetId,
+        c: Context,
+        g: &mut GlGraphics,
+        _original_fbo: GLuint,
+    ) {
+        if !self.is_valid_widget_id(widget_id) {
             return;
         }
 
-        for pos in 0..parents_of_widget.len() {
-            c.reset();
+        let widget_tree = self.get_widget_tree();
+        let invalidated_ids = self.damage_invalidated_widgets(&widget_tree);
+        let damaged_areas = std::mem::take(&mut self.damaged_areas);
 
-            let paint_id = parents_of_widget[pos];
-            let paint_widget = &mut self.widgets[paint_id as usize];
+        for area in damaged_areas {
+            self.draw_area(widget_id, &widget_tree, &area, &area, c, g);
+        }
 
-            if paint_widget.widget.borrow_mut().is_drawable() {
-                let is_hidden = paint_widget
-                    .widget
-                    .borrow_mut()
-                    .config()
-                    .get_toggle(CONFIG_WIDGET_HIDDEN);
-
-                // If the object is hidden, do not traverse objects underneath the hidden object, as
-                // it stops drawing any children at that point.
-                if !is_hidden {
-                    let is_invalidated = paint_widget.widget.borrow_mut().is_invalidated();
-
-                    if is_invalidated {
-                        let origin: Point = paint_widget
-                            .widget
-                            .borrow_mut()
-                            .config()
-                            .get_point(CONFIG_ORIGIN);
-
-                        let new_context: Context = Context {
-                            viewport: c.viewport,
-                            view: c.view,
-                            transform: c.transform.trans(origin.x as f64, origin.y as f64),
-                            draw_state: c.draw_state,
-                        };
-
-                        &paint_widget.widget.borrow_mut().get_drawable().draw(
-                            new_context,
-                            g,
-                            &c.draw_state,
-                        );
+        for invalidated_id in invalidated_ids {
+            self.widgets[invalidated_id.index()]
+                .widget
+                .borrow_mut()
+                .clear_invalidate();
+        }
+    }
 
-                        if paint_widget
-                            .widget
-                            .borrow_mut()
-                            .config()
-                            .get_toggle(CONFIG_WIDGET_DISABLED)
-                        {
-                            let size: Size = paint_widget
-                                .widget
-                                .borrow_mut()
-                                .config()
-                                .get_size(CONFIG_BODY_SIZE);
-
-                            g.rectangle(
-                                &Rectangle::new([0.0, 0.0, 0.0, 0.8]),
-                                [
-                                    origin.x as f64,
-                                    origin.y as f64,
-                                    size.w as f64,
-                                    size.h as f64,
-                                ],
-                                &c.draw_state,
-                                c.transform,
-                            );
-                        }
-                    }
+    /// Draws a `Widget` by ID and its children, in stacking order, where they overlap a damaged
+    /// area.  Drawing is clipped to the clipping area set by the parents of the `Widget`, which is
+    /// always within the damaged area.
+    fn draw_area(
+        &mut self,
+        widget_id: WidgetId,
+        widget_tree: &WidgetTree,
+        damaged_area: &Rect,
+        clip_area: &Rect,
+        c: Context,
+        g: &mut GlGraphics,
+    ) {
+        let paint_widget = &mut self.widgets[widget_id.index()];
+        let mut widget = paint_widget.widget.borrow_mut();
+
+        // If the object is hidden, do not traverse objects underneath the hidden object, as it
+        // stops drawing any children at that point.
+        if !widget.is_drawable() || widget.config().get_toggle(CONFIG_WIDGET_HIDDEN) {
+            return;
+        }
 
-                    if parents_of_widget[pos] != widget_id {
-                        self.draw(paint_id, c, g, original_fbo);
-                    }
-                }
+        let clip_area = if widget.config().get_toggle(CONFIG_NO_CLIP) {
+            damaged_area
+        } else {
+            clip_area
+        };
+        let origin: Point = widget_tree.window_origins[widget_id.index()].clone();
+        let size: Size = widget.config().get_size(CONFIG_BODY_SIZE);
+        let bounds = make_rect(&origin, &size);
+
+        if bounds.intersects(clip_area) {
+            let clip = clip_to_size(
+                &c.trans(clip_area.x as f64, clip_area.y as f64),
+                &c.draw_state,
+                Size {
+                    w: clip_area.w,
+                    h: clip_area.h,
+                },
+            );
+            let new_context: Context = Context {
+                viewport: c.viewport,
+                view: c.view,
+                transform: c.transform.trans(origin.x as f64, origin.y as f64),
+                draw_state: clip,
+            };
+
+            widget.get_drawable().draw(new_context, g, &clip);
+
+            if widget.config().get_toggle(CONFIG_WIDGET_DISABLED) {
+                g.rectangle(
+                    &Rectangle::new([0.0, 0.0, 0.0, 0.8]),
+                    [
+                        origin.x as f64,
+                        origin.y as f64,
+                        size.w as f64,
+                        size.h as f64,
+                    ],
+                    &clip,
+                    c.transform,
+                );
             }
+
+            paint_widget.drawn_bounds = Some(bounds.clone());
+        }
+
+        drop(widget);
+
+        let child_clip_area =
+            get_child_clip_area(widget_id, &bounds, Some(clip_area)).unwrap_or_default();
+
+        for &child_id in &widget_tree.child_map[widget_id.index()] {
+            self.draw_area(child_id, widget_tree, damaged_area, &child_clip_area, c, g);
         }
     }
 }
+
+/// Computes the clipping area for the children of a `Widget`: its own bounds, intersected with
+/// the clipping area set by its parents, if any.  The top-level `Widget` (`WidgetId::ROOT`)
+/// represents the window itself, so its children are not clipped to its bounds.
+fn get_child_clip_area(
+    widget_id: WidgetId,
+    bounds: &Rect,
+    clip_area: Option<&Rect>,
+) -> Option<Rect> {
+    if widget_id == WidgetId::ROOT {
+        clip_area.cloned()
+    } else {
+        match clip_area {
+            Some(clip_area) => Some(clip_area.intersection(bounds)),
+            None => Some(bounds.clone()),
+        }
+    }
+}
+
+/// Retrieves the container of a `Widget` by ID from a list of `Widget`s, such as the list passed
+/// to `Widget::handle_event`.  Returns `None` if the ID is not valid: if it is `WidgetId::NONE`, or
+/// the `Widget` it was issued for has been removed.
+pub fn get_widget_container(
+    widgets: &[WidgetContainer],
+    widget_id: WidgetId,
+) -> Option<&WidgetContainer> {
+    widgets
+        .get(widget_id.index())
+        .filter(|x| x.widget_id == widget_id && !x.removed)
+}
+
+/// Converts a `Point` relative to the origin of a `Widget` by ID into window coordinates, by
+/// adding the origin of the `Widget` and the origins of each of its parents.  As
+/// `CONFIG_ORIGIN` is relative to the parent of a `Widget`, this is how the position of a
+/// `Widget` on the screen is computed: for instance, the window position of the upper left-hand
+/// corner of a `Widget` is `to_window_coords(widgets, widget_id, make_origin_point())`.  Returns
+/// `None` if the ID is not valid.
+///
+/// This borrows each of the `Widget`s along the way, so a `Widget` cannot use this with its own
+/// ID while it is handling an event.  (See `Widget::to_local_coords`.)
+pub fn to_window_coords(
+    widgets: &[WidgetContainer],
+    widget_id: WidgetId,
+    point: Point,
+) -> Option<Point> {
+    let mut point = point;
+    let mut container = get_widget_container(widgets, widget_id)?;
+
+    loop {
+        let origin = container
+            .widget
+            .borrow_mut()
+            .config()
+            .get_point(CONFIG_ORIGIN);
+
+        point.x += origin.x;
+        point.y += origin.y;
+
+        if container.parent_id == container.widget_id {
+            return Some(point);
+        }
+
+        container = &widgets[container.parent_id.index()];
+    }
+}
+
+/// Converts a `Point` in window coordinates into a `Point` relative to the origin of a `Widget`
+/// by ID.  This is the reverse of `to_window_coords`.  Returns `None` if the ID is not valid.
+pub fn to_widget_coords(
+    widgets: &[WidgetContainer],
+    widget_id: WidgetId,
+    point: Point,
+) -> Option<Point> {
+    let origin = to_window_coords(widgets, widget_id, make_origin_point())?;
+
+    Some(Point {
+        x: point.x - origin.x,
+        y: point.y - origin.y,
+    })
+}
@@ -27,6 +27,8 @@
 //! piston2d-graphics = "^0.30"
 //! gl = "^0.11"
 //! find_folder = "^0.3"
+//! image = "^0.22"
+//! rusttype = "^0.7"
 //! ```
 //!
 //! To use the crate in your project, add the following dependencies:
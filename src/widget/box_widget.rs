@@ -19,6 +19,7 @@ use piston::input::*;
 
 use crate::core::callbacks::*;
 use crate::core::point::{Point, Size};
+use crate::core::widget_id::WidgetId;
 use crate::core::widget_store::WidgetContainer;
 use crate::widget::config::*;
 use crate::widget::widget::*;
@@ -29,7 +30,7 @@ use crate::widget::widget::*;
 pub struct BoxWidget {
     config: Configurable,
     event_list: Vec<CallbackEvent>,
-    widget_id: i32,
+    widget_id: WidgetId,
     callbacks: DefaultWidgetCallbacks,
 }
 
@@ -39,7 +40,7 @@ impl BoxWidget {
         Self {
             config: Configurable::new(),
             event_list: vec![],
-            widget_id: 0,
+            widget_id: WidgetId::NONE,
             callbacks: DefaultWidgetCallbacks::new(),
         }
     }
@@ -104,7 +105,7 @@ impl Widget for BoxWidget {
     fn set_size(&mut self, config: u8, w: i32, h: i32) {
         self.set_config(config, Config::Size(Size { w, h }));
 
-        if self.widget_id != 0 {
+        if !self.widget_id.is_none() {
             self.event_list.push(CallbackEvent::WidgetResized {
                 widget_id: self.widget_id,
                 size: Size { w, h },
@@ -117,7 +118,7 @@ impl Widget for BoxWidget {
     fn set_point(&mut self, config: u8, x: i32, y: i32) {
         self.set_config(config, Config::Point(Point { x, y }));
 
-        if self.widget_id != 0 {
+        if !self.widget_id.is_none() {
             self.event_list.push(CallbackEvent::WidgetMoved {
                 widget_id: self.widget_id,
                 point: Point { x, y },
@@ -144,11 +145,11 @@ impl Widget for BoxWidget {
         true
     }
 
-    fn set_widget_id(&mut self, widget_id: i32) {
+    fn set_widget_id(&mut self, widget_id: WidgetId) {
         self.widget_id = widget_id;
     }
 
-    fn get_widget_id(&mut self) -> i32 {
+    fn get_widget_id(&mut self) -> WidgetId {
         self.widget_id
     }
 
@@ -17,6 +17,7 @@ use graphics::types::Color;
 
 use std::collections::HashMap;
 
+use crate::core::anchor::Anchor;
 use crate::core::point::Point;
 use crate::core::point::Size;
 
@@ -30,12 +31,14 @@ pub enum Config {
     Numeric(u64),
     Text(String),
     Toggle(bool),
+    Anchor(Anchor),
 }
 
 /// Indicates whether or not a `Widget` needs to be redrawn, set by `Config::Toggle`.
 pub const CONFIG_INVALIDATE: u8 = 2;
 
-/// Origin of a `Widget`, set by `Config::Point`.
+/// Origin of a `Widget`, relative to the origin of its parent, set by `Config::Point`.  Moving a
+/// `Widget` moves all of its children along with it.
 pub const CONFIG_ORIGIN: u8 = 3;
 
 /// `Size` of a `Widget`, set by `Config::Size`.
@@ -85,6 +88,32 @@ pub const CONFIG_SELECTED: u8 = 17;
 /// to store that as Base64 or some other encoded form.
 pub const CONFIG_PAYLOAD: u8 = 18;
 
+/// Placeholder text that is displayed when an editable `Widget` has no text, set by
+/// `Config::Text`.
+pub const CONFIG_PLACEHOLDER_TEXT: u8 = 19;
+
+/// Indicates whether or not the text of an editable `Widget` is read-only, set by
+/// `Config::Toggle`.
+pub const CONFIG_READ_ONLY: u8 = 20;
+
+/// Indicates that a `Widget` is not clipped to the bounds of its parent, so that it can be drawn
+/// and clicked outside of them (for instance, a popup menu), set by `Config::Toggle`.  Its own
+/// children are still clipped to its bounds.
+pub const CONFIG_NO_CLIP: u8 = 21;
+
+/// Minimum size of a `Widget` when it is arranged by a `LayoutManager`, set by `Config::Size`.
+/// A layout gives the `Widget` at least this size, even if it then overflows the layout.
+pub const CONFIG_MIN_SIZE: u8 = 22;
+
+/// Maximum size of a `Widget` when it is arranged by a `LayoutManager`, set by `Config::Size`.
+/// A width or height of 0 leaves that dimension unlimited.  Space that a `Widget` cannot take
+/// is given to the other `Widget`s in the layout.
+pub const CONFIG_MAX_SIZE: u8 = 23;
+
+/// How a `Widget` is moved and resized when its parent is resized, set by `Config::Anchor`.
+/// `Widget`s without an anchor keep their origin and size (see `Anchor`).
+pub const CONFIG_ANCHOR: u8 = 24;
+
 /// Structure containing the configuration `HashMap`.
 pub struct Configurable {
     configs: HashMap<u8, Config>,
@@ -148,6 +177,11 @@ impl Configurable {
         self.set(config, Config::Toggle(flag));
     }
 
+    /// Sets an anchor for a configuration key.
+    pub fn set_anchor(&mut self, config: u8, anchor: Anchor) {
+        self.set(config, Config::Anchor(anchor));
+    }
+
     /// Retrieves a `Point` for a configuration key.  Returns `Point::default` if not set.
     pub fn get_point(&self, config: u8) -> Point {
         match self.configs.get(&config) {
@@ -195,4 +229,12 @@ impl Configurable {
             _ => false,
         }
     }
+
+    /// Retrieves an `Anchor` for a configuration key.  Returns `Anchor::default` if not set.
+    pub fn get_anchor(&self, config: u8) -> Anchor {
+        match self.configs.get(&config) {
+            Some(Config::Anchor(anchor)) => *anchor,
+            _ => Anchor::default(),
+        }
+    }
 }
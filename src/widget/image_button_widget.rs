@@ -20,11 +20,14 @@ use piston::input::*;
 
 use crate::core::callbacks::CallbackEvent::WidgetClicked;
 use crate::core::callbacks::*;
-use crate::core::point::Point;
+use crate::core::error::PushrodError;
+use crate::core::point::{Point, Size};
+use crate::core::widget_id::WidgetId;
 use crate::core::widget_store::*;
 use crate::widget::box_widget::*;
 use crate::widget::config::*;
 use crate::widget::image_widget::*;
+use crate::widget::push_button_widget::BUTTON_TEXT_PADDING;
 use crate::widget::text_widget::*;
 use crate::widget::widget::*;
 
@@ -35,14 +38,15 @@ pub struct ImageButtonWidget {
     text_widget: TextWidget,
     image_widget: ImageWidget,
     active: bool,
-    widget_id: i32,
+    widget_id: WidgetId,
     callbacks: DefaultWidgetCallbacks,
 }
 
 impl ImageButtonWidget {
     /// Constructor.  Requires the name of the font, the text to display, the image name to display, the size of the font,
-    /// and the font justification when rendered.  Images are loaded from the `assets/`
-    /// directory.
+    /// and the font justification when rendered.  Fonts and images are located by the shared
+    /// `ResourceLoader`.  Panics if the font cannot be loaded, and draws a placeholder if the image
+    /// cannot be loaded; use `try_new` to handle either case.
     pub fn new(
         font_name: String,
         text: String,
@@ -50,10 +54,27 @@ impl ImageButtonWidget {
         font_size: u32,
         justify: TextJustify,
     ) -> Self {
-        let mut image_widget = ImageWidget::new(image_name.to_string());
-        let mut text_widget =
-            TextWidget::new(font_name.to_string(), text.to_string(), font_size, justify);
+        let text_widget = TextWidget::new(font_name, text, font_size, justify);
 
+        Self::with_widgets(ImageWidget::new(image_name), text_widget)
+    }
+
+    /// Fallible constructor.  Takes the same parameters as `new`, but returns a `PushrodError`
+    /// if either the font or the image cannot be loaded.
+    pub fn try_new(
+        font_name: String,
+        text: String,
+        image_name: String,
+        font_size: u32,
+        justify: TextJustify,
+    ) -> Result<Self, PushrodError> {
+        let image_widget = ImageWidget::try_new(image_name)?;
+        let text_widget = TextWidget::try_new(font_name, text, font_size, justify)?;
+
+        Ok(Self::with_widgets(image_widget, text_widget))
+    }
+
+    fn with_widgets(mut image_widget: ImageWidget, mut text_widget: TextWidget) -> Self {
         text_widget.set_color(CONFIG_MAIN_COLOR, [1.0, 1.0, 1.0, 0.0]);
         image_widget.set_point(CONFIG_ORIGIN, 2, 2);
 
@@ -63,7 +84,7 @@ impl ImageButtonWidget {
             text_widget,
             image_widget,
             active: false,
-            widget_id: 0,
+            widget_id: WidgetId::NONE,
             callbacks: DefaultWidgetCallbacks::new(),
         }
     }
@@ -127,6 +148,15 @@ impl Widget for ImageButtonWidget {
         &mut self.config
     }
 
+    /// Invalidates the `Widget` when its image has been reloaded.
+    fn is_invalidated(&mut self) -> bool {
+        if self.image_widget.check_texture_reloaded() {
+            self.config().set_toggle(CONFIG_INVALIDATE, true);
+        }
+
+        self.config().contains(CONFIG_INVALIDATE)
+    }
+
     fn set_config(&mut self, config: u8, config_value: Config) {
         self.config().set(config, config_value.clone());
 
@@ -209,14 +239,26 @@ impl Widget for ImageButtonWidget {
         true
     }
 
-    fn set_widget_id(&mut self, widget_id: i32) {
+    fn set_widget_id(&mut self, widget_id: WidgetId) {
         self.widget_id = widget_id;
     }
 
-    fn get_widget_id(&mut self) -> i32 {
+    fn get_widget_id(&mut self) -> WidgetId {
         self.widget_id
     }
 
+    fn preferred_size(&mut self) -> Size {
+        // The image is a square that fills the height of the button, with the text to its right.
+        let text_size = self.text_widget.preferred_size();
+        let border = self.config().get_numeric(CONFIG_BORDER_WIDTH) as i32 * 2;
+        let h = text_size.h + border + 4;
+
+        Size {
+            w: h + border + 4 + text_size.w + BUTTON_TEXT_PADDING,
+            h,
+        }
+    }
+
     fn get_injectable_custom_events(&mut self) -> &mut dyn InjectableCustomEvents {
         self
     }
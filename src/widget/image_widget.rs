@@ -13,60 +13,126 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use ::image::{Rgba, RgbaImage};
 use graphics::*;
-use opengl_graphics::{GlGraphics, Texture, TextureSettings};
+use opengl_graphics::{Filter, GlGraphics, TextureSettings};
 use piston::input::*;
 
 use crate::core::callbacks::*;
+use crate::core::error::PushrodError;
+use crate::core::point::Size;
+use crate::core::texture_cache::{SharedTexture, TextureCache};
+use crate::core::widget_id::WidgetId;
 use crate::core::widget_store::*;
 use crate::widget::config::*;
 use crate::widget::widget::*;
 
-/// Draws an image.
+// Width and height of the checkerboard pattern drawn by a placeholder image, in squares.
+const PLACEHOLDER_SQUARES: u32 = 8;
+
+/// The ID of the placeholder image in the `TextureCache`.
+pub const PLACEHOLDER_TEXTURE_ID: &str = "_ImageWidgetPlaceholder";
+
+/// Draws an image.  Images are shared through the `TextureCache`, so `ImageWidget`s that draw the
+/// same image share a single texture.
 pub struct ImageWidget {
     config: Configurable,
-    image: Texture,
-    image_size: crate::core::point::Size,
-    widget_id: i32,
+    image: SharedTexture,
+    image_generation: u64,
+    placeholder: bool,
+    widget_id: WidgetId,
     callbacks: DefaultWidgetCallbacks,
 }
 
 impl ImageWidget {
-    /// Constructor.  Requires the name of the image to be drawn.  The image is loaded locally from
-    /// the `assets/` directory of your application.
+    /// Constructor.  Requires the name of the image to be drawn.  The image is located by the
+    /// shared `ResourceLoader` (see `ResourceLoader::shared`).  If the image cannot be loaded, the
+    /// error is printed, and a placeholder image is drawn in its place; use `try_new` to handle
+    /// the error yourself.
     pub fn new(image_name: String) -> Self {
-        let assets = find_folder::Search::ParentsThenKids(3, 3)
-            .for_folder("assets")
-            .unwrap();
-        let texture =
-            Texture::from_path(&assets.join(image_name), &TextureSettings::new()).unwrap();
-        let texture_width = texture.get_width() as i32;
-        let texture_height = texture.get_height() as i32;
+        Self::try_new(image_name).unwrap_or_else(|error| {
+            eprintln!("{}; drawing a placeholder image instead.", error);
+            Self::placeholder()
+        })
+    }
+
+    /// Fallible constructor.  Takes the same parameters as `new`, but returns a
+    /// `PushrodError::ResourceNotFound` if the image cannot be found, or a
+    /// `PushrodError::ImageLoad` naming the image path if the image cannot be loaded.
+    pub fn try_new(image_name: String) -> Result<Self, PushrodError> {
+        let texture = TextureCache::shared().get_texture(&image_name)?;
 
+        Ok(Self::with_texture(texture, false))
+    }
+
+    /// Creates an `ImageWidget` that draws a gray checkerboard placeholder image, which can be
+    /// used in place of an image that could not be loaded.
+    pub fn placeholder() -> Self {
+        let texture = TextureCache::shared().get_or_insert_image_with(
+            PLACEHOLDER_TEXTURE_ID,
+            TextureSettings::new().filter(Filter::Nearest),
+            || {
+                RgbaImage::from_fn(PLACEHOLDER_SQUARES, PLACEHOLDER_SQUARES, |x, y| {
+                    let alpha = if (x + y) & 1 == 0 { 255 } else { 96 };
+
+                    Rgba([255, 255, 255, alpha])
+                })
+            },
+        );
+
+        Self::with_texture(texture, true)
+    }
+
+    fn with_texture(texture: SharedTexture, placeholder: bool) -> Self {
         Self {
             config: Configurable::new(),
+            image_generation: texture.get_generation(),
             image: texture,
-            image_size: crate::core::point::Size {
-                w: texture_width,
-                h: texture_height,
-            },
-            widget_id: 0,
+            placeholder,
+            widget_id: WidgetId::NONE,
             callbacks: DefaultWidgetCallbacks::new(),
         }
     }
 
+    /// Retrieves the shared texture of the image that this `ImageWidget` draws.
+    pub fn get_texture(&self) -> SharedTexture {
+        self.image.clone()
+    }
+
+    /// Indicates whether or not the texture has been reloaded (see `TextureCache::reload`) since
+    /// this was last called, in which case the image needs to be redrawn.
+    pub fn check_texture_reloaded(&mut self) -> bool {
+        let generation = self.image.get_generation();
+        let reloaded = generation != self.image_generation;
+
+        self.image_generation = generation;
+        reloaded
+    }
+
+    /// Indicates whether or not this `ImageWidget` draws a placeholder image, because its image
+    /// could not be loaded.
+    pub fn is_placeholder(&self) -> bool {
+        self.placeholder
+    }
+
     inject_event_handler!();
 }
 
 impl Drawable for ImageWidget {
     fn draw(&mut self, c: Context, g: &mut GlGraphics, clip: &DrawState) {
         let size = self.config().get_size(CONFIG_BODY_SIZE);
+        let image_size = self.image.get_size();
         let transform = c.transform.scale(
-            size.w as f64 / self.image_size.w as f64,
-            size.h as f64 / self.image_size.h as f64,
+            size.w as f64 / image_size.w as f64,
+            size.h as f64 / image_size.h as f64,
         );
+        let image = if self.placeholder {
+            Image::new_color([0.5, 0.5, 0.5, 1.0])
+        } else {
+            Image::new()
+        };
 
-        Image::new().draw(&self.image, clip, transform, g);
+        image.draw(&*self.image.get_texture(), clip, transform, g);
 
         // Then clear invalidation.
         self.clear_invalidate();
@@ -82,14 +148,27 @@ impl Widget for ImageWidget {
         &mut self.config
     }
 
-    fn set_widget_id(&mut self, widget_id: i32) {
+    /// Invalidates the `Widget` when its texture has been reloaded.
+    fn is_invalidated(&mut self) -> bool {
+        if self.check_texture_reloaded() {
+            self.config().set_toggle(CONFIG_INVALIDATE, true);
+        }
+
+        self.config().contains(CONFIG_INVALIDATE)
+    }
+
+    fn set_widget_id(&mut self, widget_id: WidgetId) {
         self.widget_id = widget_id;
     }
 
-    fn get_widget_id(&mut self) -> i32 {
+    fn get_widget_id(&mut self) -> WidgetId {
         self.widget_id
     }
 
+    fn preferred_size(&mut self) -> Size {
+        self.image.get_size()
+    }
+
     fn handle_event(
         &mut self,
         injected: bool,
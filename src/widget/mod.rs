@@ -50,5 +50,11 @@ pub mod radio_button_widget;
 /// Image button component: draws an image inside a push button widget.
 pub mod image_button_widget;
 
+/// Text input component: draws an editable single line of text with a caret and selection.
+pub mod text_input_widget;
+
+/// Text area component: draws editable, word-wrapped multi-line text with vertical scrolling.
+pub mod text_area_widget;
+
 /// `Configurable` definition, used by `Widget` objects to store configuration settings.
 pub mod config;
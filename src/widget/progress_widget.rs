@@ -18,6 +18,7 @@ use opengl_graphics::GlGraphics;
 use piston::input::*;
 
 use crate::core::callbacks::*;
+use crate::core::widget_id::WidgetId;
 use crate::core::widget_store::*;
 use crate::widget::box_widget::*;
 use crate::widget::config::*;
@@ -32,7 +33,7 @@ use crate::widget::widget::*;
 pub struct ProgressWidget {
     config: Configurable,
     base_widget: BoxWidget,
-    widget_id: i32,
+    widget_id: WidgetId,
     callbacks: DefaultWidgetCallbacks,
 }
 
@@ -49,7 +50,7 @@ impl ProgressWidget {
         Self {
             config: Configurable::new(),
             base_widget: base,
-            widget_id: 0,
+            widget_id: WidgetId::NONE,
             callbacks: DefaultWidgetCallbacks::new(),
         }
     }
@@ -107,11 +108,11 @@ impl Widget for ProgressWidget {
         self.invalidate();
     }
 
-    fn set_widget_id(&mut self, widget_id: i32) {
+    fn set_widget_id(&mut self, widget_id: WidgetId) {
         self.widget_id = widget_id;
     }
 
-    fn get_widget_id(&mut self) -> i32 {
+    fn get_widget_id(&mut self) -> WidgetId {
         self.widget_id
     }
 
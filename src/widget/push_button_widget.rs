@@ -20,39 +20,58 @@ use piston::input::*;
 
 use crate::core::callbacks::CallbackEvent::WidgetClicked;
 use crate::core::callbacks::*;
+use crate::core::error::PushrodError;
+use crate::core::point::Size;
+use crate::core::widget_id::WidgetId;
 use crate::core::widget_store::*;
 use crate::widget::box_widget::*;
 use crate::widget::config::*;
 use crate::widget::text_widget::*;
 use crate::widget::widget::*;
 
+/// The number of pixels kept between the text of a button and its border, when the button is
+/// sized to its text.
+pub const BUTTON_TEXT_PADDING: i32 = 6;
+
 /// Draws a push button that triggers a `WidgetClicked` event when activated.
 pub struct PushButtonWidget {
     config: Configurable,
     base_widget: BoxWidget,
     text_widget: TextWidget,
     active: bool,
-    widget_id: i32,
+    widget_id: WidgetId,
     callbacks: DefaultWidgetCallbacks,
 }
 
 impl PushButtonWidget {
     /// Constructor.  Requires the name of the font, the text to display, the size of the font,
-    /// and the font justification when rendered.
+    /// and the font justification when rendered.  Panics if the font cannot be loaded; use
+    /// `try_new` to handle that case.
     pub fn new(font_name: String, text: String, font_size: u32, justify: TextJustify) -> Self {
-        let mut text_widget =
-            TextWidget::new(font_name.to_string(), text.to_string(), font_size, justify);
+        Self::try_new(font_name, text, font_size, justify)
+            .unwrap_or_else(|error| panic!("{}", error))
+    }
+
+    /// Fallible constructor.  Takes the same parameters as `new`, but returns a
+    /// `PushrodError` if the font cannot be found or loaded.
+    pub fn try_new(
+        font_name: String,
+        text: String,
+        font_size: u32,
+        justify: TextJustify,
+    ) -> Result<Self, PushrodError> {
+        let mut text_widget = TextWidget::try_new(font_name, text, font_size, justify)?;
 
         text_widget.set_color(CONFIG_MAIN_COLOR, [1.0, 1.0, 1.0, 0.0]);
 
-        Self {
+        Ok(Self {
             config: Configurable::new(),
             base_widget: BoxWidget::new(),
             text_widget,
             active: false,
-            widget_id: 0,
+            widget_id: WidgetId::NONE,
             callbacks: DefaultWidgetCallbacks::new(),
-        }
+        })
     }
 
     fn draw_hovered(&mut self) {
@@ -173,14 +192,25 @@ impl Widget for PushButtonWidget {
         true
     }
 
-    fn set_widget_id(&mut self, widget_id: i32) {
+    fn set_widget_id(&mut self, widget_id: WidgetId) {
         self.widget_id = widget_id;
     }
 
-    fn get_widget_id(&mut self) -> i32 {
+    fn get_widget_id(&mut self) -> WidgetId {
         self.widget_id
     }
 
+    fn preferred_size(&mut self) -> Size {
+        let text_size = self.text_widget.preferred_size();
+        let padding =
+            (self.config().get_numeric(CONFIG_BORDER_WIDTH) as i32 + BUTTON_TEXT_PADDING) * 2;
+
+        Size {
+            w: text_size.w + padding,
+            h: text_size.h + padding,
+        }
+    }
+
     fn get_injectable_custom_events(&mut self) -> &mut dyn InjectableCustomEvents {
         self
     }
@@ -19,7 +19,9 @@ use opengl_graphics::GlGraphics;
 use piston::input::*;
 
 use crate::core::callbacks::*;
-use crate::core::point::Point;
+use crate::core::error::PushrodError;
+use crate::core::point::{Point, Size};
+use crate::core::widget_id::WidgetId;
 use crate::core::widget_store::*;
 use crate::widget::box_widget::*;
 use crate::widget::config::*;
@@ -27,6 +29,15 @@ use crate::widget::image_widget::*;
 use crate::widget::text_widget::*;
 use crate::widget::widget::*;
 
+/// The name of the image resource drawn by a selected `RadioButtonWidget`.
+pub const RADIO_SELECTED_IMAGE: &str = "radio_selected.png";
+
+/// The name of the image resource drawn by an unselected `RadioButtonWidget`.
+pub const RADIO_UNSELECTED_IMAGE: &str = "radio_unselected.png";
+
+/// The horizontal offset at which the text is drawn, to the right of the image.
+const TEXT_OFFSET: i32 = 38;
+
 /// Draws a radio button with only one object that can be selected at any one time within a group.
 /// Generates a `WidgetSelected` event when an object has been selected within the group.  Also
 /// generates an internal `UnselectRadioButtons` event, which should not be handled - this is only
@@ -39,14 +50,15 @@ pub struct RadioButtonWidget {
     selected_widget: ImageWidget,
     unselected_widget: ImageWidget,
     inject_event: bool,
-    widget_id: i32,
+    widget_id: WidgetId,
     callbacks: DefaultWidgetCallbacks,
 }
 
 impl RadioButtonWidget {
     /// Constructor.  Requires the name of the font, the text to display, the image name to display, the size of the font,
-    /// the font justification when rendered, and a selected pre-set state.  Images are
-    /// loaded from the `assets/` directory.
+    /// the font justification when rendered, and a selected pre-set state.  Fonts and images are
+    /// located by the shared `ResourceLoader`.  Panics if the font cannot be loaded, and draws
+    /// placeholders for images that cannot be loaded; use `try_new` to handle either case.
     pub fn new(
         font_name: String,
         text: String,
@@ -54,16 +66,43 @@ impl RadioButtonWidget {
         justify: TextJustify,
         selected: bool,
     ) -> Self {
-        let mut selected_widget = ImageWidget::new("radio_selected.png".to_string());
+        Self::with_widgets(
+            ImageWidget::new(RADIO_SELECTED_IMAGE.to_string()),
+            ImageWidget::new(RADIO_UNSELECTED_IMAGE.to_string()),
+            TextWidget::new(font_name, text, font_size, justify),
+            selected,
+        )
+    }
+
+    /// Fallible constructor.  Takes the same parameters as `new`, but returns a `PushrodError`
+    /// if either the font or one of the images cannot be loaded.
+    pub fn try_new(
+        font_name: String,
+        text: String,
+        font_size: u32,
+        justify: TextJustify,
+        selected: bool,
+    ) -> Result<Self, PushrodError> {
+        Ok(Self::with_widgets(
+            ImageWidget::try_new(RADIO_SELECTED_IMAGE.to_string())?,
+            ImageWidget::try_new(RADIO_UNSELECTED_IMAGE.to_string())?,
+            TextWidget::try_new(font_name, text, font_size, justify)?,
+            selected,
+        ))
+    }
+
+    fn with_widgets(
+        mut selected_widget: ImageWidget,
+        mut unselected_widget: ImageWidget,
+        mut text_widget: TextWidget,
+        selected: bool,
+    ) -> Self {
         selected_widget.set_point(CONFIG_ORIGIN, 2, 2);
         selected_widget.set_toggle(CONFIG_WIDGET_HIDDEN, true);
 
-        let mut unselected_widget = ImageWidget::new("radio_unselected.png".to_string());
         unselected_widget.set_point(CONFIG_ORIGIN, 2, 2);
         unselected_widget.set_toggle(CONFIG_WIDGET_HIDDEN, false);
 
-        let mut text_widget =
-            TextWidget::new(font_name.to_string(), text.to_string(), font_size, justify);
         text_widget.set_point(CONFIG_ORIGIN, 36, 0);
 
         Self {
@@ -74,7 +113,7 @@ impl RadioButtonWidget {
             selected_widget,
             unselected_widget,
             inject_event: false,
-            widget_id: 0,
+            widget_id: WidgetId::NONE,
             callbacks: DefaultWidgetCallbacks::new(),
         }
     }
@@ -106,8 +145,15 @@ impl Drawable for RadioButtonWidget {
                 .draw_with_offset(c, g, &clip, Point { x: 0, y: 0 });
         }
 
-        self.text_widget
-            .draw_with_offset(c, g, &clip, Point { x: 38, y: 0 });
+        self.text_widget.draw_with_offset(
+            c,
+            g,
+            &clip,
+            Point {
+                x: TEXT_OFFSET,
+                y: 0,
+            },
+        );
 
         // Then clear invalidation.
         self.clear_invalidate();
@@ -117,7 +163,7 @@ impl Drawable for RadioButtonWidget {
 impl InjectableSystemEvents for RadioButtonWidget {}
 
 impl InjectableCustomEvents for RadioButtonWidget {
-    fn inject_custom_event(&mut self, widget_id: i32) -> Option<CallbackEvent> {
+    fn inject_custom_event(&mut self, widget_id: WidgetId) -> Option<CallbackEvent> {
         if self.inject_event {
             self.inject_event = false;
 
@@ -136,6 +182,18 @@ impl Widget for RadioButtonWidget {
         &mut self.config
     }
 
+    /// Invalidates the `Widget` when one of its images has been reloaded.
+    fn is_invalidated(&mut self) -> bool {
+        let reloaded = self.selected_widget.check_texture_reloaded()
+            | self.unselected_widget.check_texture_reloaded();
+
+        if reloaded {
+            self.config().set_toggle(CONFIG_INVALIDATE, true);
+        }
+
+        self.config().contains(CONFIG_INVALIDATE)
+    }
+
     fn set_config(&mut self, config: u8, config_value: Config) {
         self.config().set(config, config_value.clone());
         self.base_widget.set_config(config, config_value.clone());
@@ -231,14 +289,23 @@ impl Widget for RadioButtonWidget {
         true
     }
 
-    fn set_widget_id(&mut self, widget_id: i32) {
+    fn set_widget_id(&mut self, widget_id: WidgetId) {
         self.widget_id = widget_id;
     }
 
-    fn get_widget_id(&mut self) -> i32 {
+    fn get_widget_id(&mut self) -> WidgetId {
         self.widget_id
     }
 
+    fn preferred_size(&mut self) -> Size {
+        let text_size = self.text_widget.preferred_size();
+
+        Size {
+            w: TEXT_OFFSET + text_size.w,
+            h: text_size.h,
+        }
+    }
+
     fn get_injectable_custom_events(&mut self) -> &mut dyn InjectableCustomEvents {
         self
     }
@@ -0,0 +1,841 @@
+// Text Area Widget
+// Draws editable multi-line text, with word wrapping and vertical scrolling.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use graphics::draw_state::DrawState;
+use graphics::*;
+use opengl_graphics::GlGraphics;
+use piston::input::*;
+use std::time::{Duration, Instant};
+
+use crate::core::callbacks::*;
+use crate::core::error::PushrodError;
+use crate::core::font_registry::{FontRegistry, SharedFont};
+use crate::core::point::{Point, Size};
+use crate::core::widget_id::WidgetId;
+use crate::core::widget_store::*;
+use crate::widget::config::*;
+use crate::widget::widget::*;
+
+/// Number of milliseconds that the caret stays visible (or hidden) while blinking.
+const CARET_BLINK_MS: u128 = 500;
+
+/// Number of pixels between the border and the text.
+const TEXT_PADDING: f64 = 3.0;
+
+/// Draws editable multi-line text.  The text is stored in `CONFIG_DISPLAY_TEXT`, with lines
+/// separated by `\n`.  Lines that are wider than the `Widget` are word wrapped, and text that is
+/// taller than the `Widget` can be scrolled with the mouse wheel.  When the `Widget` has keyboard
+/// focus, a blinking caret is drawn, and typed text is inserted at the caret.
+///
+/// - The caret can be moved with the mouse, the arrow keys, `Home` and `End`.  `Up` and `Down`
+///   move the caret between lines, keeping its horizontal position.
+/// - Text can be selected across lines by dragging the mouse, or by holding `Shift` while moving
+///   the caret.
+/// - `Backspace` and `Delete` remove the selection, or the character before or after the caret.
+///   `Enter` starts a new line.
+///
+/// Every change made by the user triggers the `on_text_changed` callback and generates a
+/// `WidgetTextChanged` event.
+///
+/// Takes the following additional options:
+/// * `CONFIG_READ_ONLY` prevents the text from being edited.  It can still be scrolled and
+///   selected.
+/// * `CONFIG_PLACEHOLDER_TEXT` specifies the text to display when there is no text entered.
+/// * `CONFIG_BORDER_WIDTH` and `CONFIG_BORDER_COLOR` specify the border drawn around the text.
+/// * `CONFIG_SECONDARY_COLOR` specifies the color of the selection highlight.
+pub struct TextAreaWidget {
+    config: Configurable,
+    font: SharedFont,
+    font_size: u32,
+    char_offsets: Vec<f64>,
+    lines: Vec<(usize, usize)>,
+    need_layout: bool,
+    measured_text: String,
+    measured_width: f64,
+    caret: usize,
+    anchor: usize,
+    preferred_x: Option<f64>,
+    scroll_offset: f64,
+    need_caret_scroll: bool,
+    focused: bool,
+    shift_pressed: bool,
+    mouse_selecting: bool,
+    mouse_point: Point,
+    blink_start: Instant,
+    caret_drawn: bool,
+    widget_id: WidgetId,
+    callbacks: DefaultWidgetCallbacks,
+}
+
+impl TextAreaWidget {
+    /// Constructor.  Requires the name of the font, the initial text, and the size of the font.
+    /// The font is located by the shared `ResourceLoader`.  Panics if the font cannot be loaded;
+    /// use `try_new` to handle that case.
+    pub fn new(font_name: String, text: String, font_size: u32) -> Self {
+        Self::try_new(font_name, text, font_size).unwrap_or_else(|error| panic!("{}", error))
+    }
+
+    /// Fallible constructor.  Takes the same parameters as `new`, but returns a
+    /// `PushrodError` if the font cannot be found or loaded.
+    pub fn try_new(font_name: String, text: String, font_size: u32) -> Result<Self, PushrodError> {
+        let mut configurable = Configurable::new();
+        let font = FontRegistry::shared().get_font(&font_name)?;
+
+        configurable.set(CONFIG_DISPLAY_TEXT, Config::Text(text.clone()));
+        configurable.set(CONFIG_MAIN_COLOR, Config::Color([1.0; 4]));
+        configurable.set(CONFIG_TEXT_COLOR, Config::Color([0.0, 0.0, 0.0, 1.0]));
+        configurable.set(CONFIG_BORDER_COLOR, Config::Color([0.0, 0.0, 0.0, 1.0]));
+        configurable.set(CONFIG_BORDER_WIDTH, Config::Numeric(1));
+        configurable.set(CONFIG_SECONDARY_COLOR, Config::Color([0.6, 0.8, 1.0, 1.0]));
+
+        let mut widget = Self {
+            config: configurable,
+            font,
+            font_size,
+            char_offsets: vec![0.0],
+            lines: vec![(0, 0)],
+            need_layout: true,
+            measured_text: String::new(),
+            measured_width: 0.0,
+            caret: 0,
+            anchor: 0,
+            preferred_x: None,
+            scroll_offset: 0.0,
+            need_caret_scroll: false,
+            focused: false,
+            shift_pressed: false,
+            mouse_selecting: false,
+            mouse_point: Point { x: 0, y: 0 },
+            blink_start: Instant::now(),
+            caret_drawn: false,
+            widget_id: WidgetId::NONE,
+            callbacks: DefaultWidgetCallbacks::new(),
+        };
+
+        widget.caret = widget.char_count();
+        widget.anchor = widget.caret;
+        Ok(widget)
+    }
+
+    /// Retrieves the position of the caret, as a character index into the text.
+    pub fn get_caret_position(&self) -> usize {
+        self.caret.min(self.char_count())
+    }
+
+    /// Retrieves the selected range of characters as `(start, end)`, where `end` is exclusive.
+    /// Returns `None` if no text is selected.
+    pub fn get_selection(&self) -> Option<(usize, usize)> {
+        let char_count = self.char_count();
+        let caret = self.caret.min(char_count);
+        let anchor = self.anchor.min(char_count);
+
+        if caret == anchor {
+            None
+        } else {
+            Some((caret.min(anchor), caret.max(anchor)))
+        }
+    }
+
+    fn char_count(&self) -> usize {
+        self.config.get_text(CONFIG_DISPLAY_TEXT).chars().count()
+    }
+
+    fn is_read_only(&self) -> bool {
+        self.config.get_toggle(CONFIG_READ_ONLY)
+    }
+
+    fn padding(&self) -> f64 {
+        self.config.get_numeric(CONFIG_BORDER_WIDTH) as f64 + TEXT_PADDING
+    }
+
+    fn line_height(&self) -> f64 {
+        (self.font_size as f64 * 1.25).ceil()
+    }
+
+    /// Retrieves the `(width, height)` of the area inside of the padding.
+    fn visible_size(&self) -> (f64, f64) {
+        let size = self.config.get_size(CONFIG_BODY_SIZE);
+        let padding = self.padding();
+
+        (
+            (size.w as f64 - padding * 2.0).max(0.0),
+            (size.h as f64 - padding * 2.0).max(0.0),
+        )
+    }
+
+    /// Measures each character of the text the same way that `TextWidget` measures its text, and
+    /// breaks the text into lines.  Lines are broken at each `\n`, and wrapped before the first
+    /// word that does not fit in the width of the `Widget`.  Words that do not fit on a line by
+    /// themselves are broken at the last character that fits.  This is only done when the lines
+    /// are needed for drawing, or for caret movement that depends on the position of the text.
+    fn recalculate_layout(&mut self) {
+        let display_text = self.config.get_text(CONFIG_DISPLAY_TEXT);
+        let text: Vec<char> = display_text.chars().collect();
+        let (max_width, _) = self.visible_size();
+        let mut widths = Vec::with_capacity(text.len());
+
+        self.char_offsets = vec![0.0];
+
+        for ch in text.iter() {
+            let width = if *ch == '\n' {
+                0.0
+            } else {
+                self.font.get_advance_width(self.font_size, *ch)
+            };
+
+            widths.push(width);
+            self.char_offsets
+                .push(self.char_offsets[self.char_offsets.len() - 1] + width);
+        }
+
+        let mut lines = vec![];
+        let mut line_start = 0;
+        let mut line_width = 0.0;
+        let mut last_break: Option<usize> = None;
+        let mut pos = 0;
+
+        while pos < text.len() {
+            if text[pos] == '\n' {
+                lines.push((line_start, pos));
+                line_start = pos + 1;
+                line_width = 0.0;
+                last_break = None;
+                pos += 1;
+                continue;
+            }
+
+            // Spaces are allowed to overflow the end of a line, so wrapped lines never start
+            // with a space.
+            if line_width + widths[pos] > max_width && pos > line_start && text[pos] != ' ' {
+                match last_break {
+                    Some(break_pos) if break_pos > line_start => {
+                        lines.push((line_start, break_pos));
+                        line_start = break_pos;
+                        line_width = widths[break_pos..pos].iter().sum();
+                    }
+
+                    _ => {
+                        lines.push((line_start, pos));
+                        line_start = pos;
+                        line_width = 0.0;
+                    }
+                }
+
+                last_break = None;
+                continue;
+            }
+
+            line_width += widths[pos];
+
+            if text[pos] == ' ' {
+                last_break = Some(pos + 1);
+            }
+
+            pos += 1;
+        }
+
+        lines.push((line_start, text.len()));
+
+        self.lines = lines;
+        self.need_layout = false;
+        self.measured_text = display_text;
+        self.measured_width = max_width;
+    }
+
+    /// Wraps the text again if it has changed since it was last measured, including changes made
+    /// directly through `config()`, which bypass `set_config`.  The caret and selection are kept
+    /// within the text.
+    fn ensure_layout(&mut self) {
+        let (max_width, _) = self.visible_size();
+
+        if self.need_layout
+            || self.measured_width != max_width
+            || self.measured_text != self.config.get_text(CONFIG_DISPLAY_TEXT)
+        {
+            self.recalculate_layout();
+        }
+
+        let char_count = self.char_offsets.len() - 1;
+
+        self.caret = self.caret.min(char_count);
+        self.anchor = self.anchor.min(char_count);
+    }
+
+    /// Retrieves the line on which a character index is displayed.  A character index at the
+    /// point where a line is wrapped is displayed at the start of the next line.
+    fn line_for_index(&self, char_index: usize) -> usize {
+        self.lines
+            .iter()
+            .rposition(|(start, _)| *start <= char_index)
+            .unwrap_or(0)
+    }
+
+    /// Retrieves the last character index on a line at which the caret can be placed.
+    fn line_end(&self, line: usize) -> usize {
+        let (start, end) = self.lines[line];
+
+        // If the line is wrapped, the end of the line is displayed as the start of the next line.
+        if line + 1 < self.lines.len() && self.lines[line + 1].0 == end {
+            (end - 1).max(start)
+        } else {
+            end
+        }
+    }
+
+    fn x_for_index(&self, char_index: usize) -> f64 {
+        let (start, _) = self.lines[self.line_for_index(char_index)];
+
+        self.char_offsets[char_index] - self.char_offsets[start]
+    }
+
+    /// Finds the character index on a line that is closest to an X coordinate in the text.
+    fn index_for_line_x(&self, line: usize, x: f64) -> usize {
+        let start = self.lines[line].0;
+
+        (start..=self.line_end(line))
+            .min_by(|a, b| {
+                let distance_a = (self.char_offsets[*a] - self.char_offsets[start] - x).abs();
+                let distance_b = (self.char_offsets[*b] - self.char_offsets[start] - x).abs();
+
+                distance_a.partial_cmp(&distance_b).unwrap()
+            })
+            .unwrap_or(start)
+    }
+
+    /// Finds the character index closest to a point relative to the `Widget`.
+    fn index_for_point(&mut self, point: Point) -> usize {
+        self.ensure_layout();
+
+        let padding = self.padding();
+        let line = ((point.y as f64 - padding + self.scroll_offset) / self.line_height()).floor();
+        let line = (line.max(0.0) as usize).min(self.lines.len() - 1);
+
+        self.index_for_line_x(line, point.x as f64 - padding)
+    }
+
+    /// Limits the scroll offset to the height of the text.
+    fn clamp_scroll(&mut self) {
+        let (_, visible_height) = self.visible_size();
+        let max_scroll = (self.lines.len() as f64 * self.line_height() - visible_height).max(0.0);
+
+        self.scroll_offset = self.scroll_offset.min(max_scroll).max(0.0);
+    }
+
+    /// Adjusts the scroll offset so that the line containing the caret is visible.
+    fn scroll_to_caret(&mut self) {
+        let (_, visible_height) = self.visible_size();
+        let line_height = self.line_height();
+        let caret_top = self.line_for_index(self.caret) as f64 * line_height;
+
+        if caret_top < self.scroll_offset {
+            self.scroll_offset = caret_top;
+        } else if caret_top + line_height > self.scroll_offset + visible_height {
+            self.scroll_offset = caret_top + line_height - visible_height;
+        }
+    }
+
+    /// Moves the caret to a character index.  If `extend` is set, the selection is extended to
+    /// the new position, otherwise the selection is cleared.
+    fn move_caret(&mut self, char_index: usize, extend: bool) {
+        self.caret = char_index.min(self.char_count());
+
+        if !extend {
+            self.anchor = self.caret;
+        }
+
+        self.preferred_x = None;
+        self.blink_start = Instant::now();
+        self.need_caret_scroll = true;
+        self.invalidate();
+    }
+
+    /// Moves the caret up or down by a number of lines, keeping its horizontal position.  Moving
+    /// above the first line or below the last line moves the caret to the start or end of the
+    /// text.
+    fn move_caret_lines(&mut self, lines: i32, extend: bool) {
+        self.ensure_layout();
+
+        let x = self
+            .preferred_x
+            .unwrap_or_else(|| self.x_for_index(self.caret));
+        let target_line = self.line_for_index(self.caret) as i32 + lines;
+        let char_index = if target_line < 0 {
+            0
+        } else if target_line as usize >= self.lines.len() {
+            self.char_count()
+        } else {
+            self.index_for_line_x(target_line as usize, x)
+        };
+
+        self.move_caret(char_index, extend);
+        self.preferred_x = Some(x);
+    }
+
+    /// Replaces the selected text (if any) with `text`, placing the caret after the inserted text.
+    fn replace_selection(&mut self, text: &str) {
+        let (start, end) = self.get_selection().unwrap_or((self.caret, self.caret));
+        let mut contents: Vec<char> = self.config.get_text(CONFIG_DISPLAY_TEXT).chars().collect();
+
+        contents.splice(start..end, text.chars());
+        self.config()
+            .set_text(CONFIG_DISPLAY_TEXT, contents.into_iter().collect());
+        self.need_layout = true;
+        self.move_caret(start + text.chars().count(), false);
+    }
+
+    /// Deletes the selection, or if there is none, the character before the caret (or after it,
+    /// if `forward` is set.)  Returns `true` if any text was removed.
+    fn delete_text(&mut self, forward: bool) -> bool {
+        if self.get_selection().is_none() {
+            if forward && self.caret < self.char_count() {
+                self.anchor = self.caret + 1;
+            } else if !forward && self.caret > 0 {
+                self.anchor = self.caret - 1;
+            } else {
+                return false;
+            }
+        }
+
+        self.replace_selection("");
+        true
+    }
+
+    /// Triggers the `on_text_changed` callback, and returns the `WidgetTextChanged` event.
+    fn text_changed(
+        &mut self,
+        widget_store: Option<&Vec<WidgetContainer>>,
+    ) -> Option<CallbackEvent> {
+        let text = self.config().get_text(CONFIG_DISPLAY_TEXT);
+
+        if let Some(widgets) = widget_store {
+            if let Some(mut cb) = self.get_callbacks().on_text_changed.take() {
+                cb(self, text.clone(), widgets);
+                self.get_callbacks().on_text_changed = Some(cb);
+            }
+        }
+
+        Some(CallbackEvent::WidgetTextChanged {
+            widget_id: self.widget_id,
+            text,
+        })
+    }
+
+    fn handle_key(
+        &mut self,
+        key: Key,
+        state: ButtonState,
+        widget_store: Option<&Vec<WidgetContainer>>,
+    ) -> Option<CallbackEvent> {
+        if key == Key::LShift || key == Key::RShift {
+            self.shift_pressed = state == ButtonState::Press;
+            return None;
+        }
+
+        if state != ButtonState::Press {
+            return None;
+        }
+
+        let extend = self.shift_pressed;
+        let editable = !self.is_read_only();
+
+        match key {
+            Key::Left => match self.get_selection() {
+                Some((start, _)) if !extend => self.move_caret(start, false),
+                _ => self.move_caret(self.caret.saturating_sub(1), extend),
+            },
+
+            Key::Right => match self.get_selection() {
+                Some((_, end)) if !extend => self.move_caret(end, false),
+                _ => self.move_caret(self.caret + 1, extend),
+            },
+
+            Key::Up => self.move_caret_lines(-1, extend),
+
+            Key::Down => self.move_caret_lines(1, extend),
+
+            Key::Home => {
+                self.ensure_layout();
+
+                let (start, _) = self.lines[self.line_for_index(self.caret)];
+
+                self.move_caret(start, extend);
+            }
+
+            Key::End => {
+                self.ensure_layout();
+
+                let end = self.line_end(self.line_for_index(self.caret));
+
+                self.move_caret(end, extend);
+            }
+
+            Key::Backspace if editable => {
+                if self.delete_text(false) {
+                    return self.text_changed(widget_store);
+                }
+            }
+
+            Key::Delete if editable => {
+                if self.delete_text(true) {
+                    return self.text_changed(widget_store);
+                }
+            }
+
+            Key::Return | Key::NumPadEnter if editable => {
+                self.replace_selection("\n");
+                return self.text_changed(widget_store);
+            }
+
+            _ => (),
+        }
+
+        None
+    }
+
+    fn is_caret_blinking(&self) -> bool {
+        self.focused && !self.is_read_only()
+    }
+
+    fn is_caret_visible(&self) -> bool {
+        self.is_caret_blinking()
+            && (self.blink_start.elapsed().as_millis() / CARET_BLINK_MS) & 1 == 0
+    }
+
+    inject_event_handler!();
+}
+
+impl Drawable for TextAreaWidget {
+    fn draw(&mut self, c: Context, g: &mut GlGraphics, clip: &DrawState) {
+        let size = self.config().get_size(CONFIG_BODY_SIZE);
+        let border = self.config().get_numeric(CONFIG_BORDER_WIDTH) as f64;
+        let padding = self.padding();
+        let line_height = self.line_height();
+        let (visible_width, visible_height) = self.visible_size();
+
+        self.ensure_layout();
+
+        if self.need_caret_scroll {
+            self.scroll_to_caret();
+            self.need_caret_scroll = false;
+        }
+
+        self.clamp_scroll();
+
+        // Clear the drawing backing, and draw the border.
+        g.rectangle(
+            &Rectangle::new(self.config().get_color(CONFIG_MAIN_COLOR)),
+            [0.0f64, 0.0f64, size.w as f64, size.h as f64],
+            clip,
+            c.transform,
+        );
+
+        if border > 0.0 {
+            Rectangle::new_border(self.config().get_color(CONFIG_BORDER_COLOR), border / 2.0).draw(
+                [
+                    border / 2.0,
+                    border / 2.0,
+                    size.w as f64 - border,
+                    size.h as f64 - border,
+                ],
+                clip,
+                c.transform,
+                g,
+            );
+        }
+
+        // Everything inside of the border is clipped, so that scrolled text does not overflow.
+        let text_clip = clip_to_size(
+            &c.trans(padding, padding),
+            clip,
+            Size {
+                w: visible_width as i32,
+                h: visible_height as i32,
+            },
+        );
+        let text: Vec<char> = self
+            .config()
+            .get_text(CONFIG_DISPLAY_TEXT)
+            .chars()
+            .collect();
+        let text_color = self.config().get_color(CONFIG_TEXT_COLOR);
+        let selection = if self.focused {
+            self.get_selection()
+        } else {
+            None
+        };
+
+        if text.is_empty() {
+            let mut placeholder_color = text_color;
+
+            // Placeholder text is drawn at half of the text color's opacity.
+            placeholder_color[3] /= 2.0;
+
+            text::Text::new_color(placeholder_color, self.font_size)
+                .draw(
+                    self.config().get_text(CONFIG_PLACEHOLDER_TEXT).as_str(),
+                    &mut *self.font.get_glyph_cache(),
+                    &text_clip,
+                    c.transform.trans(padding, padding + self.font_size as f64),
+                    g,
+                )
+                .unwrap();
+        }
+
+        for (line, (start, end)) in self.lines.clone().into_iter().enumerate() {
+            let line_top = padding + line as f64 * line_height - self.scroll_offset;
+
+            if line_top + line_height < padding || line_top > padding + visible_height {
+                continue;
+            }
+
+            if let Some((selection_start, selection_end)) = selection {
+                let from = selection_start.max(start);
+                let to = selection_end.min(end);
+
+                if from < to {
+                    g.rectangle(
+                        &Rectangle::new(self.config().get_color(CONFIG_SECONDARY_COLOR)),
+                        [
+                            padding + self.char_offsets[from] - self.char_offsets[start],
+                            line_top,
+                            self.char_offsets[to] - self.char_offsets[from],
+                            line_height,
+                        ],
+                        &text_clip,
+                        c.transform,
+                    );
+                }
+            }
+
+            // The Y coordinate used for drawing text is its baseline, so the size of the font is
+            // added to the top of the line.
+            let line_text: String = text[start..end].iter().collect();
+
+            text::Text::new_color(text_color, self.font_size)
+                .draw(
+                    line_text.as_str(),
+                    &mut *self.font.get_glyph_cache(),
+                    &text_clip,
+                    c.transform.trans(padding, line_top + self.font_size as f64),
+                    g,
+                )
+                .unwrap();
+        }
+
+        self.caret_drawn = self.is_caret_visible();
+
+        if self.caret_drawn {
+            let caret_line = self.line_for_index(self.caret);
+
+            g.rectangle(
+                &Rectangle::new(text_color),
+                [
+                    padding + self.x_for_index(self.caret),
+                    padding + caret_line as f64 * line_height - self.scroll_offset,
+                    1.0,
+                    line_height,
+                ],
+                &text_clip,
+                c.transform,
+            );
+        }
+
+        // Then clear invalidation.
+        self.clear_invalidate();
+    }
+}
+
+impl InjectableSystemEvents for TextAreaWidget {}
+
+impl InjectableCustomEvents for TextAreaWidget {}
+
+impl Widget for TextAreaWidget {
+    fn config(&mut self) -> &mut Configurable {
+        &mut self.config
+    }
+
+    /// Invalidates the `Widget` each time the caret blinks, while it has focus.
+    fn is_invalidated(&mut self) -> bool {
+        if self.focused && self.is_caret_visible() != self.caret_drawn {
+            self.config().set_toggle(CONFIG_INVALIDATE, true);
+        }
+
+        self.config().contains(CONFIG_INVALIDATE)
+    }
+
+    /// Setting the `CONFIG_DISPLAY_TEXT` moves the caret to the end of the new text.  Changing the
+    /// size or border of the `Widget` causes the text to be wrapped again.
+    fn set_config(&mut self, config: u8, config_value: Config) {
+        self.config().set(config, config_value.clone());
+        self.invalidate();
+
+        match config {
+            CONFIG_DISPLAY_TEXT => {
+                self.need_layout = true;
+                self.move_caret(self.char_count(), false);
+            }
+
+            CONFIG_BODY_SIZE | CONFIG_BORDER_WIDTH => self.need_layout = true,
+
+            _ => (),
+        }
+    }
+
+    fn set_widget_id(&mut self, widget_id: WidgetId) {
+        self.widget_id = widget_id;
+    }
+
+    fn get_widget_id(&mut self) -> WidgetId {
+        self.widget_id
+    }
+
+    fn accepts_focus(&mut self) -> bool {
+        true
+    }
+
+    /// Wakes up the run loop each time the caret blinks, while it has focus.
+    fn get_next_wakeup(&mut self) -> Option<Duration> {
+        if self.is_caret_blinking() {
+            let elapsed = self.blink_start.elapsed().as_millis();
+
+            Some(Duration::from_millis(
+                (CARET_BLINK_MS - elapsed % CARET_BLINK_MS) as u64,
+            ))
+        } else {
+            None
+        }
+    }
+
+    fn handle_event(
+        &mut self,
+        injected: bool,
+        event: CallbackEvent,
+        widget_store: Option<&Vec<WidgetContainer>>,
+    ) -> Option<CallbackEvent> {
+        if !injected {
+            // The text may have been changed through `config()` since the last event.
+            self.ensure_layout();
+
+            match event {
+                CallbackEvent::WidgetFocusGained { widget_id: _ } => {
+                    self.focused = true;
+                    self.blink_start = Instant::now();
+                    self.invalidate();
+                }
+
+                CallbackEvent::WidgetFocusLost { widget_id: _ } => {
+                    self.focused = false;
+                    self.shift_pressed = false;
+                    self.mouse_selecting = false;
+                    self.invalidate();
+                }
+
+                CallbackEvent::MouseScrolled {
+                    widget_id: _,
+                    ref point,
+                } => {
+                    self.ensure_layout();
+                    self.scroll_offset -= point.y as f64 * self.line_height();
+                    self.clamp_scroll();
+                    self.invalidate();
+                    self.handle_event_callbacks(event, widget_store);
+                }
+
+                CallbackEvent::MouseMoved {
+                    widget_id: _,
+                    ref point,
+                } => {
+                    self.mouse_point = self.to_local_coords(widget_store, point);
+
+                    if self.mouse_selecting {
+                        let char_index = self.index_for_point(self.mouse_point.clone());
+
+                        self.move_caret(char_index, true);
+                    }
+
+                    self.handle_event_callbacks(event, widget_store);
+                }
+
+                CallbackEvent::MouseButtonDown {
+                    widget_id: _,
+                    button: Button::Mouse(MouseButton::Left),
+                } => {
+                    let char_index = self.index_for_point(self.mouse_point.clone());
+
+                    self.move_caret(char_index, self.shift_pressed);
+                    self.mouse_selecting = true;
+                }
+
+                CallbackEvent::MouseButtonUpInside {
+                    widget_id: _,
+                    button: Button::Mouse(MouseButton::Left),
+                }
+                | CallbackEvent::MouseButtonUpOutside {
+                    widget_id: _,
+                    button: Button::Mouse(MouseButton::Left),
+                } => {
+                    self.mouse_selecting = false;
+                    self.handle_event_callbacks(event, widget_store);
+                }
+
+                CallbackEvent::KeyPressed {
+                    widget_id: _,
+                    key,
+                    state,
+                } => return self.handle_key(key, state, widget_store),
+
+                CallbackEvent::TextEntered {
+                    widget_id: _,
+                    ref text,
+                } => {
+                    // Control characters are handled as key presses, and are not inserted.
+                    let filtered: String = text.chars().filter(|ch| !ch.is_control()).collect();
+
+                    self.handle_event_callbacks(event.clone(), widget_store);
+
+                    if !filtered.is_empty() && !self.is_read_only() {
+                        self.replace_selection(&filtered);
+
+                        return self.text_changed(widget_store);
+                    }
+                }
+
+                _ => self.handle_event_callbacks(event, widget_store),
+            }
+        }
+
+        None
+    }
+
+    fn handles_events(&mut self) -> bool {
+        true
+    }
+
+    fn get_injectable_custom_events(&mut self) -> &mut dyn InjectableCustomEvents {
+        self
+    }
+
+    fn get_injectable_system_events(&mut self) -> &mut dyn InjectableSystemEvents {
+        self
+    }
+
+    fn get_drawable(&mut self) -> &mut dyn Drawable {
+        self
+    }
+
+    fn get_callbacks(&mut self) -> &mut DefaultWidgetCallbacks {
+        &mut self.callbacks
+    }
+}
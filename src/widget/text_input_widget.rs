@@ -0,0 +1,625 @@
+// Text Input Widget
+// Draws an editable single line of text, with a caret and a selection.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use graphics::draw_state::DrawState;
+use graphics::*;
+use opengl_graphics::GlGraphics;
+use piston::input::*;
+use std::time::{Duration, Instant};
+
+use crate::core::callbacks::*;
+use crate::core::error::PushrodError;
+use crate::core::font_registry::{FontRegistry, SharedFont};
+use crate::core::point::Size;
+use crate::core::widget_id::WidgetId;
+use crate::core::widget_store::*;
+use crate::widget::config::*;
+use crate::widget::widget::*;
+
+/// Number of milliseconds that the caret stays visible (or hidden) while blinking.
+const CARET_BLINK_MS: u128 = 500;
+
+/// Number of pixels between the border and the text.
+const TEXT_PADDING: f64 = 3.0;
+
+/// Draws an editable single line of text.  The text is stored in `CONFIG_DISPLAY_TEXT`.  When the
+/// `Widget` has keyboard focus, a blinking caret is drawn, and typed text is inserted at the caret.
+///
+/// - The caret can be moved with the mouse, the `Left` and `Right` arrow keys, `Home` and `End`.
+/// - Text can be selected by dragging the mouse, or by holding `Shift` while moving the caret.
+/// - `Backspace` and `Delete` remove the selection, or the character before or after the caret.
+/// - Text that is wider than the `Widget` is scrolled horizontally to keep the caret visible.
+///
+/// Every change made by the user triggers the `on_text_changed` callback and generates a
+/// `WidgetTextChanged` event.  Pressing `Enter` triggers the `on_text_submitted` callback and
+/// generates a `WidgetTextSubmitted` event.
+///
+/// Takes the following additional options:
+/// * `CONFIG_PLACEHOLDER_TEXT` specifies the text to display when there is no text entered.
+/// * `CONFIG_BORDER_WIDTH` and `CONFIG_BORDER_COLOR` specify the border drawn around the text.
+/// * `CONFIG_SECONDARY_COLOR` specifies the color of the selection highlight.
+pub struct TextInputWidget {
+    config: Configurable,
+    font: SharedFont,
+    font_size: u32,
+    char_offsets: Vec<f64>,
+    need_text_measure: bool,
+    caret: usize,
+    anchor: usize,
+    scroll_offset: f64,
+    focused: bool,
+    shift_pressed: bool,
+    mouse_selecting: bool,
+    mouse_x: f64,
+    blink_start: Instant,
+    caret_drawn: bool,
+    widget_id: WidgetId,
+    callbacks: DefaultWidgetCallbacks,
+}
+
+impl TextInputWidget {
+    /// Constructor.  Requires the name of the font, the initial text, and the size of the font.
+    /// The font is located by the shared `ResourceLoader`.  Panics if the font cannot be loaded;
+    /// use `try_new` to handle that case.
+    pub fn new(font_name: String, text: String, font_size: u32) -> Self {
+        Self::try_new(font_name, text, font_size).unwrap_or_else(|error| panic!("{}", error))
+    }
+
+    /// Fallible constructor.  Takes the same parameters as `new`, but returns a
+    /// `PushrodError` if the font cannot be found or loaded.
+    pub fn try_new(font_name: String, text: String, font_size: u32) -> Result<Self, PushrodError> {
+        let mut configurable = Configurable::new();
+        let font = FontRegistry::shared().get_font(&font_name)?;
+
+        configurable.set(CONFIG_DISPLAY_TEXT, Config::Text(text.clone()));
+        configurable.set(CONFIG_MAIN_COLOR, Config::Color([1.0; 4]));
+        configurable.set(CONFIG_TEXT_COLOR, Config::Color([0.0, 0.0, 0.0, 1.0]));
+        configurable.set(CONFIG_BORDER_COLOR, Config::Color([0.0, 0.0, 0.0, 1.0]));
+        configurable.set(CONFIG_BORDER_WIDTH, Config::Numeric(1));
+        configurable.set(CONFIG_SECONDARY_COLOR, Config::Color([0.6, 0.8, 1.0, 1.0]));
+
+        let mut widget = Self {
+            config: configurable,
+            font,
+            font_size,
+            char_offsets: vec![0.0],
+            need_text_measure: true,
+            caret: 0,
+            anchor: 0,
+            scroll_offset: 0.0,
+            focused: false,
+            shift_pressed: false,
+            mouse_selecting: false,
+            mouse_x: 0.0,
+            blink_start: Instant::now(),
+            caret_drawn: false,
+            widget_id: WidgetId::NONE,
+            callbacks: DefaultWidgetCallbacks::new(),
+        };
+
+        widget.caret = widget.char_count();
+        widget.anchor = widget.caret;
+        Ok(widget)
+    }
+
+    /// Retrieves the position of the caret, as a character index into the text.
+    pub fn get_caret_position(&self) -> usize {
+        self.caret
+    }
+
+    /// Retrieves the selected range of characters as `(start, end)`, where `end` is exclusive.
+    /// Returns `None` if no text is selected.
+    pub fn get_selection(&self) -> Option<(usize, usize)> {
+        if self.caret == self.anchor {
+            None
+        } else {
+            Some((self.caret.min(self.anchor), self.caret.max(self.anchor)))
+        }
+    }
+
+    fn char_count(&self) -> usize {
+        self.config.get_text(CONFIG_DISPLAY_TEXT).chars().count()
+    }
+
+    fn padding(&mut self) -> f64 {
+        self.config().get_numeric(CONFIG_BORDER_WIDTH) as f64 + TEXT_PADDING
+    }
+
+    /// Measures the horizontal offset of each character boundary in the text, the same way that
+    /// `TextWidget` measures its text.  This is only done when the text is drawn, or when a mouse
+    /// position needs to be converted to a character.
+    fn recalculate_char_offsets(&mut self) {
+        let text = self.config().get_text(CONFIG_DISPLAY_TEXT);
+        let mut width = 0.0;
+
+        self.char_offsets = vec![0.0];
+
+        for ch in text.chars() {
+            width += self.font.get_advance_width(self.font_size, ch);
+            self.char_offsets.push(width);
+        }
+
+        self.need_text_measure = false;
+    }
+
+    /// Converts a character index into a byte index in the text.
+    fn byte_index(text: &str, char_index: usize) -> usize {
+        text.char_indices()
+            .nth(char_index)
+            .map(|(pos, _)| pos)
+            .unwrap_or_else(|| text.len())
+    }
+
+    /// Finds the character boundary closest to an X coordinate relative to the `Widget`.
+    fn char_index_for_x(&mut self, x: f64) -> usize {
+        if self.need_text_measure {
+            self.recalculate_char_offsets();
+        }
+
+        let text_x = x - self.padding() + self.scroll_offset;
+
+        (0..self.char_offsets.len())
+            .min_by(|a, b| {
+                let distance_a = (self.char_offsets[*a] - text_x).abs();
+                let distance_b = (self.char_offsets[*b] - text_x).abs();
+
+                distance_a.partial_cmp(&distance_b).unwrap()
+            })
+            .unwrap_or(0)
+    }
+
+    /// Adjusts the horizontal scroll offset so that the caret stays visible.
+    fn scroll_to_caret(&mut self) {
+        let size = self.config().get_size(CONFIG_BODY_SIZE);
+        let visible_width = (size.w as f64 - self.padding() * 2.0).max(0.0);
+        let caret_x = self.char_offsets[self.caret];
+        let max_scroll = (self.char_offsets[self.char_offsets.len() - 1] - visible_width).max(0.0);
+
+        if caret_x < self.scroll_offset {
+            self.scroll_offset = caret_x;
+        } else if caret_x > self.scroll_offset + visible_width {
+            self.scroll_offset = caret_x - visible_width;
+        }
+
+        self.scroll_offset = self.scroll_offset.min(max_scroll).max(0.0);
+    }
+
+    /// Moves the caret to a character index.  If `extend` is set, the selection is extended to
+    /// the new position, otherwise the selection is cleared.
+    fn move_caret(&mut self, char_index: usize, extend: bool) {
+        self.caret = char_index.min(self.char_count());
+
+        if !extend {
+            self.anchor = self.caret;
+        }
+
+        self.blink_start = Instant::now();
+        self.invalidate();
+    }
+
+    /// Replaces the selected text (if any) with `text`, placing the caret after the inserted text.
+    fn replace_selection(&mut self, text: &str) {
+        let (start, end) = self.get_selection().unwrap_or((self.caret, self.caret));
+        let mut contents = self.config().get_text(CONFIG_DISPLAY_TEXT);
+        let start_byte = TextInputWidget::byte_index(&contents, start);
+        let end_byte = TextInputWidget::byte_index(&contents, end);
+
+        contents.replace_range(start_byte..end_byte, text);
+        self.config().set_text(CONFIG_DISPLAY_TEXT, contents);
+        self.need_text_measure = true;
+        self.move_caret(start + text.chars().count(), false);
+    }
+
+    /// Deletes the selection, or if there is none, the character before the caret (or after it,
+    /// if `forward` is set.)  Returns `true` if any text was removed.
+    fn delete_text(&mut self, forward: bool) -> bool {
+        if self.get_selection().is_none() {
+            if forward && self.caret < self.char_count() {
+                self.anchor = self.caret + 1;
+            } else if !forward && self.caret > 0 {
+                self.anchor = self.caret - 1;
+            } else {
+                return false;
+            }
+        }
+
+        self.replace_selection("");
+        true
+    }
+
+    /// Calls a text callback, if one is set.  Used for the `on_text_changed` and
+    /// `on_text_submitted` callbacks.
+    fn call_text_callback(&mut self, submitted: bool, widget_store: Option<&Vec<WidgetContainer>>) {
+        let widgets = match widget_store {
+            Some(widgets) => widgets,
+            None => return,
+        };
+        let text = self.config().get_text(CONFIG_DISPLAY_TEXT);
+
+        if submitted {
+            if let Some(mut cb) = self.get_callbacks().on_text_submitted.take() {
+                cb(self, text, widgets);
+                self.get_callbacks().on_text_submitted = Some(cb);
+            }
+        } else if let Some(mut cb) = self.get_callbacks().on_text_changed.take() {
+            cb(self, text, widgets);
+            self.get_callbacks().on_text_changed = Some(cb);
+        }
+    }
+
+    /// Triggers the `on_text_changed` callback, and returns the `WidgetTextChanged` event.
+    fn text_changed(
+        &mut self,
+        widget_store: Option<&Vec<WidgetContainer>>,
+    ) -> Option<CallbackEvent> {
+        self.call_text_callback(false, widget_store);
+
+        Some(CallbackEvent::WidgetTextChanged {
+            widget_id: self.widget_id,
+            text: self.config().get_text(CONFIG_DISPLAY_TEXT),
+        })
+    }
+
+    fn handle_key(
+        &mut self,
+        key: Key,
+        state: ButtonState,
+        widget_store: Option<&Vec<WidgetContainer>>,
+    ) -> Option<CallbackEvent> {
+        if key == Key::LShift || key == Key::RShift {
+            self.shift_pressed = state == ButtonState::Press;
+            return None;
+        }
+
+        if state != ButtonState::Press {
+            return None;
+        }
+
+        let extend = self.shift_pressed;
+
+        match key {
+            Key::Left => match self.get_selection() {
+                Some((start, _)) if !extend => self.move_caret(start, false),
+                _ => self.move_caret(self.caret.saturating_sub(1), extend),
+            },
+
+            Key::Right => match self.get_selection() {
+                Some((_, end)) if !extend => self.move_caret(end, false),
+                _ => self.move_caret(self.caret + 1, extend),
+            },
+
+            Key::Home => self.move_caret(0, extend),
+
+            Key::End => self.move_caret(self.char_count(), extend),
+
+            Key::Backspace => {
+                if self.delete_text(false) {
+                    return self.text_changed(widget_store);
+                }
+            }
+
+            Key::Delete => {
+                if self.delete_text(true) {
+                    return self.text_changed(widget_store);
+                }
+            }
+
+            Key::Return | Key::NumPadEnter => {
+                self.call_text_callback(true, widget_store);
+
+                return Some(CallbackEvent::WidgetTextSubmitted {
+                    widget_id: self.widget_id,
+                    text: self.config().get_text(CONFIG_DISPLAY_TEXT),
+                });
+            }
+
+            _ => (),
+        }
+
+        None
+    }
+
+    fn is_caret_blinking(&self) -> bool {
+        self.focused
+    }
+
+    fn is_caret_visible(&self) -> bool {
+        self.is_caret_blinking()
+            && (self.blink_start.elapsed().as_millis() / CARET_BLINK_MS) & 1 == 0
+    }
+
+    inject_event_handler!();
+}
+
+impl Drawable for TextInputWidget {
+    fn draw(&mut self, c: Context, g: &mut GlGraphics, clip: &DrawState) {
+        let size = self.config().get_size(CONFIG_BODY_SIZE);
+        let border = self.config().get_numeric(CONFIG_BORDER_WIDTH) as f64;
+        let padding = self.padding();
+        let font_size = self.font_size as f64;
+        let text_top = ((size.h as f64 - font_size) / 2.0).floor();
+
+        if self.need_text_measure {
+            self.recalculate_char_offsets();
+        }
+
+        self.scroll_to_caret();
+
+        // Clear the drawing backing, and draw the border.
+        g.rectangle(
+            &Rectangle::new(self.config().get_color(CONFIG_MAIN_COLOR)),
+            [0.0f64, 0.0f64, size.w as f64, size.h as f64],
+            clip,
+            c.transform,
+        );
+
+        if border > 0.0 {
+            Rectangle::new_border(self.config().get_color(CONFIG_BORDER_COLOR), border / 2.0).draw(
+                [
+                    border / 2.0,
+                    border / 2.0,
+                    size.w as f64 - border,
+                    size.h as f64 - border,
+                ],
+                clip,
+                c.transform,
+                g,
+            );
+        }
+
+        // Everything inside of the border is clipped, so that scrolled text does not overflow.
+        let text_clip = clip_to_size(
+            &c.trans(padding, 0.0),
+            clip,
+            Size {
+                w: (size.w as f64 - padding * 2.0).max(0.0) as i32,
+                h: size.h,
+            },
+        );
+        let text_x = padding - self.scroll_offset;
+
+        if self.focused {
+            if let Some((start, end)) = self.get_selection() {
+                g.rectangle(
+                    &Rectangle::new(self.config().get_color(CONFIG_SECONDARY_COLOR)),
+                    [
+                        text_x + self.char_offsets[start],
+                        text_top,
+                        self.char_offsets[end] - self.char_offsets[start],
+                        font_size,
+                    ],
+                    &text_clip,
+                    c.transform,
+                );
+            }
+        }
+
+        // Vertically justify the text the same way as `TextWidget`, as the Y coordinate used for
+        // drawing text is its baseline.  This is computed in floating point, and never placed
+        // above the `Widget`, as the font may be tiny, and the height may be negative mid-layout.
+        let start_y = ((f64::from(self.font_size) - 2.0 + f64::from(size.h)) / 2.0 - 1.0)
+            .floor()
+            .max(0.0);
+        let mut display_text = self.config().get_text(CONFIG_DISPLAY_TEXT);
+        let mut text_color = self.config().get_color(CONFIG_TEXT_COLOR);
+
+        // Placeholder text is drawn at half of the text color's opacity.
+        if display_text.is_empty() {
+            display_text = self.config().get_text(CONFIG_PLACEHOLDER_TEXT);
+            text_color[3] /= 2.0;
+        }
+
+        text::Text::new_color(text_color, self.font_size)
+            .draw(
+                display_text.as_str(),
+                &mut *self.font.get_glyph_cache(),
+                &text_clip,
+                c.transform.trans(text_x, start_y),
+                g,
+            )
+            .unwrap();
+
+        self.caret_drawn = self.is_caret_visible();
+
+        if self.caret_drawn {
+            g.rectangle(
+                &Rectangle::new(self.config().get_color(CONFIG_TEXT_COLOR)),
+                [
+                    text_x + self.char_offsets[self.caret],
+                    text_top,
+                    1.0,
+                    font_size,
+                ],
+                &text_clip,
+                c.transform,
+            );
+        }
+
+        // Then clear invalidation.
+        self.clear_invalidate();
+    }
+}
+
+impl InjectableSystemEvents for TextInputWidget {}
+
+impl InjectableCustomEvents for TextInputWidget {}
+
+impl Widget for TextInputWidget {
+    fn config(&mut self) -> &mut Configurable {
+        &mut self.config
+    }
+
+    /// Invalidates the `Widget` each time the caret blinks, while it has focus.
+    fn is_invalidated(&mut self) -> bool {
+        if self.focused && self.is_caret_visible() != self.caret_drawn {
+            self.config().set_toggle(CONFIG_INVALIDATE, true);
+        }
+
+        self.config().contains(CONFIG_INVALIDATE)
+    }
+
+    /// Setting the `CONFIG_DISPLAY_TEXT` moves the caret to the end of the new text.
+    fn set_config(&mut self, config: u8, config_value: Config) {
+        self.config().set(config, config_value.clone());
+        self.invalidate();
+
+        if config == CONFIG_DISPLAY_TEXT {
+            self.need_text_measure = true;
+            self.move_caret(self.char_count(), false);
+        }
+    }
+
+    fn set_widget_id(&mut self, widget_id: WidgetId) {
+        self.widget_id = widget_id;
+    }
+
+    fn get_widget_id(&mut self) -> WidgetId {
+        self.widget_id
+    }
+
+    fn preferred_size(&mut self) -> Size {
+        // Text inputs are usually stretched to fill a layout, so only the height is measured.
+        let line_height = self.font.get_metrics(self.font_size).get_line_height();
+
+        Size {
+            w: self.config().get_size(CONFIG_BODY_SIZE).w,
+            h: (line_height + self.padding() * 2.0).ceil() as i32,
+        }
+    }
+
+    fn accepts_focus(&mut self) -> bool {
+        true
+    }
+
+    /// Wakes up the run loop each time the caret blinks, while it has focus.
+    fn get_next_wakeup(&mut self) -> Option<Duration> {
+        if self.is_caret_blinking() {
+            let elapsed = self.blink_start.elapsed().as_millis();
+
+            Some(Duration::from_millis(
+                (CARET_BLINK_MS - elapsed % CARET_BLINK_MS) as u64,
+            ))
+        } else {
+            None
+        }
+    }
+
+    fn handle_event(
+        &mut self,
+        injected: bool,
+        event: CallbackEvent,
+        widget_store: Option<&Vec<WidgetContainer>>,
+    ) -> Option<CallbackEvent> {
+        if !injected {
+            match event {
+                CallbackEvent::WidgetFocusGained { widget_id: _ } => {
+                    self.focused = true;
+                    self.blink_start = Instant::now();
+                    self.invalidate();
+                }
+
+                CallbackEvent::WidgetFocusLost { widget_id: _ } => {
+                    self.focused = false;
+                    self.shift_pressed = false;
+                    self.mouse_selecting = false;
+                    self.invalidate();
+                }
+
+                CallbackEvent::MouseMoved {
+                    widget_id: _,
+                    ref point,
+                } => {
+                    self.mouse_x = self.to_local_coords(widget_store, point).x as f64;
+
+                    if self.mouse_selecting {
+                        let char_index = self.char_index_for_x(self.mouse_x);
+
+                        self.move_caret(char_index, true);
+                    }
+
+                    self.handle_event_callbacks(event, widget_store);
+                }
+
+                CallbackEvent::MouseButtonDown {
+                    widget_id: _,
+                    button: Button::Mouse(MouseButton::Left),
+                } => {
+                    let char_index = self.char_index_for_x(self.mouse_x);
+
+                    self.move_caret(char_index, self.shift_pressed);
+                    self.mouse_selecting = true;
+                }
+
+                CallbackEvent::MouseButtonUpInside {
+                    widget_id: _,
+                    button: Button::Mouse(MouseButton::Left),
+                }
+                | CallbackEvent::MouseButtonUpOutside {
+                    widget_id: _,
+                    button: Button::Mouse(MouseButton::Left),
+                } => {
+                    self.mouse_selecting = false;
+                    self.handle_event_callbacks(event, widget_store);
+                }
+
+                CallbackEvent::KeyPressed {
+                    widget_id: _,
+                    key,
+                    state,
+                } => return self.handle_key(key, state, widget_store),
+
+                CallbackEvent::TextEntered {
+                    widget_id: _,
+                    ref text,
+                } => {
+                    // Control characters are handled as key presses, and are not inserted.
+                    let filtered: String = text.chars().filter(|ch| !ch.is_control()).collect();
+
+                    self.handle_event_callbacks(event.clone(), widget_store);
+
+                    if !filtered.is_empty() {
+                        self.replace_selection(&filtered);
+
+                        return self.text_changed(widget_store);
+                    }
+                }
+
+                _ => self.handle_event_callbacks(event, widget_store),
+            }
+        }
+
+        None
+    }
+
+    fn handles_events(&mut self) -> bool {
+        true
+    }
+
+    fn get_injectable_custom_events(&mut self) -> &mut dyn InjectableCustomEvents {
+        self
+    }
+
+    fn get_injectable_system_events(&mut self) -> &mut dyn InjectableSystemEvents {
+        self
+    }
+
+    fn get_drawable(&mut self) -> &mut dyn Drawable {
+        self
+    }
+
+    fn get_callbacks(&mut self) -> &mut DefaultWidgetCallbacks {
+        &mut self.callbacks
+    }
+}
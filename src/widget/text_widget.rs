@@ -16,13 +16,16 @@
 //extern crate graphics;
 
 use graphics;
-use graphics::character::CharacterCache;
 use graphics::draw_state::DrawState;
 use graphics::*;
-use opengl_graphics::{GlGraphics, GlyphCache, TextureSettings};
+use opengl_graphics::GlGraphics;
 use piston::input::*;
 
 use crate::core::callbacks::*;
+use crate::core::error::PushrodError;
+use crate::core::font_registry::{FontRegistry, SharedFont};
+use crate::core::point::Size;
+use crate::core::widget_id::WidgetId;
 use crate::core::widget_store::*;
 use crate::widget::config::*;
 use crate::widget::widget::*;
@@ -43,51 +46,58 @@ pub enum TextJustify {
 /// Draws a block of text.
 pub struct TextWidget {
     config: Configurable,
-    font_cache: GlyphCache<'static>, // YUCK - I do not like this!
+    font: SharedFont,
     font_size: u32,
     justify: TextJustify,
     pub desired_width: i32,
     need_text_resize: bool,
-    widget_id: i32,
+    widget_id: WidgetId,
     callbacks: DefaultWidgetCallbacks,
 }
 
 impl TextWidget {
     /// Constructor.  Requires the name of the font, the text to display, the size of the font,
-    /// and the font justification when rendered.
+    /// and the font justification when rendered.  The font is located by the shared
+    /// `ResourceLoader`.  Panics if the font cannot be loaded; use `try_new` to handle that case.
     pub fn new(font_name: String, text: String, font_size: u32, justify: TextJustify) -> Self {
+        Self::try_new(font_name, text, font_size, justify)
+            .unwrap_or_else(|error| panic!("{}", error))
+    }
+
+    /// Fallible constructor.  Takes the same parameters as `new`, but returns a
+    /// `PushrodError` if the font cannot be found or loaded.
+    pub fn try_new(
+        font_name: String,
+        text: String,
+        font_size: u32,
+        justify: TextJustify,
+    ) -> Result<Self, PushrodError> {
         let mut configurable = Configurable::new();
-        let cache = GlyphCache::new(font_name.clone(), (), TextureSettings::new()).unwrap();
+        let font = FontRegistry::shared().get_font(&font_name)?;
 
         configurable.set(CONFIG_DISPLAY_TEXT, Config::Text(text.clone()));
 
-        Self {
+        Ok(Self {
             config: configurable,
-            font_cache: cache,
+            font,
             font_size,
             justify,
             desired_width: 0 as i32,
             need_text_resize: true,
-            widget_id: 0,
+            widget_id: WidgetId::NONE,
             callbacks: DefaultWidgetCallbacks::new(),
-        }
+        })
     }
 
     fn recalculate_desired_size(&mut self) {
         let text = self.config().get_text(CONFIG_DISPLAY_TEXT).clone();
-        let mut width = 0.0;
-
-        for ch in text.chars() {
-            let character = self.font_cache.character(self.font_size, ch).unwrap();
-            width += character.advance_width();
-        }
 
-        self.desired_width = width as i32;
+        self.desired_width = self.font.get_text_width(self.font_size, &text) as i32;
         self.need_text_resize = false;
     }
 
     fn draw_text(&mut self, c: Context, g: &mut GlGraphics, clip: &DrawState) {
-        let size: crate::core::point::Size = self.config().get_size(CONFIG_BODY_SIZE);
+        let size: Size = self.config().get_size(CONFIG_BODY_SIZE);
 
         // Modify transform here based on the width of the text being drawn, which is element 0 of
         // self.desired_size
@@ -110,7 +120,7 @@ impl TextWidget {
         text::Text::new_color(self.config().get_color(CONFIG_TEXT_COLOR), self.font_size)
             .draw(
                 self.config().get_text(CONFIG_DISPLAY_TEXT).as_str(),
-                &mut self.font_cache,
+                &mut *self.font.get_glyph_cache(),
                 clip,
                 c.transform.trans(start_x, start_y as f64),
                 g,
@@ -165,14 +175,27 @@ impl Widget for TextWidget {
         self.invalidate();
     }
 
-    fn set_widget_id(&mut self, widget_id: i32) {
+    fn set_widget_id(&mut self, widget_id: WidgetId) {
         self.widget_id = widget_id;
     }
 
-    fn get_widget_id(&mut self) -> i32 {
+    fn get_widget_id(&mut self) -> WidgetId {
         self.widget_id
     }
 
+    fn preferred_size(&mut self) -> Size {
+        let text = self.config().get_text(CONFIG_DISPLAY_TEXT).clone();
+
+        Size {
+            w: self.font.get_text_width(self.font_size, &text).ceil() as i32,
+            h: self
+                .font
+                .get_metrics(self.font_size)
+                .get_line_height()
+                .ceil() as i32,
+        }
+    }
+
     fn handle_event(
         &mut self,
         injected: bool,
@@ -13,9 +13,10 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use std::time::{SystemTime, UNIX_EPOCH};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
 
 use crate::core::callbacks::CallbackEvent;
+use crate::core::widget_id::WidgetId;
 use crate::core::widget_store::*;
 use crate::widget::config::*;
 use crate::widget::widget::*;
@@ -24,15 +25,17 @@ use crate::widget::widget::*;
 /// has been reached, a `TimerTriggered` event is generated.  Set the timer timeout in
 /// milliseconds by setting `CONFIG_TIMER_TIMEOUT` values.
 ///
-/// Timer timeouts are triggered on a per-refresh basis, so if the FPS of the screen for the
+/// Timer timeouts are triggered on a per-frame basis, so if the FPS of the screen for the
 /// main window is set to 30 FPS, the timer will only receive a tick every 1/30th of a second.  If
-/// you need higher resolution timers, consider using threads.
+/// you need higher resolution timers, consider using threads.  Ticks are processed along with
+/// the custom event injection of each frame, so timers also run when nothing is being drawn.
+/// In lazy mode, the run loop is woken up when the timer is due.
 pub struct TimerWidget {
     config: Configurable,
     enabled: bool,
     initiated: u64,
     triggered: bool,
-    widget_id: i32,
+    widget_id: WidgetId,
     callbacks: DefaultWidgetCallbacks,
     on_tick: Option<Box<dyn FnMut(&mut TimerWidget)>>,
 }
@@ -51,7 +54,7 @@ impl TimerWidget {
             enabled: true,
             initiated: time_ms(),
             triggered: false,
-            widget_id: 0,
+            widget_id: WidgetId::NONE,
             callbacks: DefaultWidgetCallbacks::new(),
             on_tick: None,
         }
@@ -96,7 +99,9 @@ impl Drawable for TimerWidget {}
 impl InjectableSystemEvents for TimerWidget {}
 
 impl InjectableCustomEvents for TimerWidget {
-    fn inject_custom_event(&mut self, widget_id: i32) -> Option<CallbackEvent> {
+    fn inject_custom_event(&mut self, widget_id: WidgetId) -> Option<CallbackEvent> {
+        self.tick();
+
         if self.triggered {
             self.triggered = false;
             self.trigger_tick();
@@ -112,19 +117,26 @@ impl Widget for TimerWidget {
         &mut self.config
     }
 
-    fn is_invalidated(&mut self) -> bool {
+    fn injects_custom_events(&mut self) -> bool {
         true
     }
 
-    fn injects_custom_events(&mut self) -> bool {
-        true
+    /// Wakes up the run loop just after the timeout has been reached.
+    fn get_next_wakeup(&mut self) -> Option<Duration> {
+        if !self.enabled {
+            return None;
+        }
+
+        let due = self.initiated + self.config().get_numeric(CONFIG_TIMER_TIMEOUT) + 1;
+
+        Some(Duration::from_millis(due.saturating_sub(time_ms())))
     }
 
-    fn set_widget_id(&mut self, widget_id: i32) {
+    fn set_widget_id(&mut self, widget_id: WidgetId) {
         self.widget_id = widget_id;
     }
 
-    fn get_widget_id(&mut self) -> i32 {
+    fn get_widget_id(&mut self) -> WidgetId {
         self.widget_id
     }
 
@@ -156,7 +168,6 @@ impl Widget for TimerWidget {
     }
 
     fn is_drawable(&mut self) -> bool {
-        self.tick();
         false
     }
 
@@ -15,20 +15,24 @@
 use graphics::*;
 use opengl_graphics::GlGraphics;
 use piston::input::*;
+use std::any::Any;
 use std::cell::RefMut;
+use std::time::Duration;
 
+use crate::core::anchor::Anchor;
 use crate::core::callbacks::*;
 use crate::core::point::{Point, Size};
+use crate::core::widget_id::WidgetId;
 use crate::core::widget_store::*;
 use crate::widget::config::*;
 
 pub trait Drawable {
     /// Draws the `Widget`'s contents.  Only gets called if the `Widget` is in invalidated
-    /// state.  Provides a modified `Context` object that has an origin of `0x0` in drawing
-    /// space for the draw routine.  Also provides a `mut G2d` object against which to draw,
-    /// and a `clip`, which is automatically set to provide a clipping area for the `Widget`.  If
-    /// the `Widget` draws outside of the clipped bounds, that will not be drawn on the
-    /// screen.
+    /// state, or if it overlaps an area of the window that is being redrawn.  Provides a modified
+    /// `Context` object that has an origin of `0x0` in drawing space for the draw routine.  Also
+    /// provides a `mut G2d` object against which to draw, and a `clip`, which is automatically set
+    /// to provide a clipping area for the `Widget`.  If the `Widget` draws outside of the clipped
+    /// bounds, that will not be drawn on the screen.
     fn draw(&mut self, _c: Context, _g: &mut GlGraphics, _clip: &DrawState) {
         // Do nothing on the draw.
     }
@@ -67,14 +71,34 @@ pub trait InjectableCustomEvents {
     /// Injects an event into the run loop.  This can be a timer event, a refresh event, or
     /// whatever the `Widget` wants to inject.  These should be custom events, not system
     /// events.  This method only gets called if `injects_events` returns `true`.
-    fn inject_custom_event(&mut self, _widget_id: i32) -> Option<CallbackEvent> {
+    fn inject_custom_event(&mut self, _widget_id: WidgetId) -> Option<CallbackEvent> {
         None
     }
 }
 
+/// Provides access to a `Widget` as `Any`, so that it can be downcast to its concrete type.  This
+/// is implemented automatically for every `Widget`.  (See `WidgetStore::get_widget_as`.)
+pub trait AsAny: Any {
+    /// Retrieves this object as an `Any` reference.
+    fn as_any(&self) -> &dyn Any;
+
+    /// Retrieves this object as a mutable `Any` reference.
+    fn as_any_mut(&mut self) -> &mut dyn Any;
+}
+
+impl<T: Any> AsAny for T {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+}
+
 /// Master level trait object for describing a `Widget`.  A `Widget` is a GUI element that can
 /// be interacted with and can receive and generate events.
-pub trait Widget {
+pub trait Widget: AsAny {
     /// Retrieves the `Configurable` object for this `Widget`.  All `Widget` implementations
     /// must provide this.  (See the `CanvasWidget` implementation.)
     fn config(&mut self) -> &mut Configurable;
@@ -138,9 +162,47 @@ pub trait Widget {
         self.set_config(config, Config::Toggle(flag));
     }
 
-    fn set_widget_id(&mut self, widget_id: i32);
+    /// Sets how the `Widget` follows its parent when the parent is resized (`CONFIG_ANCHOR`).
+    fn set_anchor(&mut self, anchor: Anchor) {
+        self.set_config(CONFIG_ANCHOR, Config::Anchor(anchor));
+    }
+
+    /// Measures the size that the `Widget` would like to be, such as the size of its text, or of
+    /// its image.  Layout managers use this for `Widget`s that are sized to their content, and
+    /// to compute the preferred size of their container.  The default is the current size of
+    /// the `Widget`.  Minimum and maximum sizes (`CONFIG_MIN_SIZE` and `CONFIG_MAX_SIZE`) are
+    /// applied by the `WidgetStore`, and need not be applied here.
+    fn preferred_size(&mut self) -> Size {
+        self.config().get_size(CONFIG_BODY_SIZE)
+    }
+
+    fn set_widget_id(&mut self, widget_id: WidgetId);
 
-    fn get_widget_id(&mut self) -> i32;
+    fn get_widget_id(&mut self) -> WidgetId;
+
+    /// Converts a `Point` in window coordinates (such as the position of the mouse in a
+    /// `MouseMoved` event) into a `Point` relative to the origin of this `Widget`.  The list of
+    /// `Widget`s passed to `handle_event` is required, as the origin of a `Widget` is relative to
+    /// its parent.  If it is not available, the origin is treated as a window position.
+    fn to_local_coords(&mut self, widgets: Option<&Vec<WidgetContainer>>, point: &Point) -> Point {
+        let widget_id = self.get_widget_id();
+        let origin = self.config().get_point(CONFIG_ORIGIN);
+        let parent_id = widgets
+            .and_then(|widgets| get_widget_container(widgets, widget_id))
+            .map(|container| container.parent_id)
+            .filter(|parent_id| *parent_id != widget_id);
+        let origin = match (widgets, parent_id) {
+            (Some(widgets), Some(parent_id)) => {
+                to_window_coords(widgets, parent_id, origin.clone()).unwrap_or(origin)
+            }
+            _ => origin,
+        };
+
+        Point {
+            x: point.x - origin.x,
+            y: point.y - origin.y,
+        }
+    }
 
     /// Custom handler to receive an event.  Any `Widget` that implements this does so to handle
     /// top-level GUI events, such as a mouse entering or exiting the bounds of this `Widget`.
@@ -160,6 +222,13 @@ pub trait Widget {
         false
     }
 
+    /// Indicates whether or not the `Widget` can receive keyboard focus.  A focused `Widget`
+    /// receives all `KeyPressed` events, regardless of the position of the mouse.  Focus is
+    /// given to a `Widget` when it is clicked, or when it is reached through `Tab` traversal.
+    fn accepts_focus(&mut self) -> bool {
+        false
+    }
+
     /// Retrieves the `InjectableCustomEvents` trait of this class, which is responsible for
     /// injecting custom events when appropriate.  Injecting system events is used with the
     /// `InjectableSystemEvents`, and things like mouse clicks and widget clicks are used
@@ -185,6 +254,14 @@ pub trait Widget {
         true
     }
 
+    /// Indicates how long the run loop can sleep before this `Widget` needs to be woken up, when
+    /// the run loop is in lazy mode.  A `Widget` that changes on its own over time (such as a
+    /// timer, or a blinking caret) returns the time until its next change.  Returns `None` if the
+    /// `Widget` only changes in response to events, which is the default.
+    fn get_next_wakeup(&mut self) -> Option<Duration> {
+        None
+    }
+
     /// Retrieves the trait for injecting system events.  Only use this if your `Widget` injects
     /// custom system-level events that the top-level application needs to use.  Anything other
     /// than that should be ignored completely.
@@ -207,7 +284,7 @@ pub trait Widget {
 pub struct CanvasWidget {
     config: Configurable,
     event_list: Vec<CallbackEvent>,
-    widget_id: i32,
+    widget_id: WidgetId,
     callbacks: DefaultWidgetCallbacks,
 }
 
@@ -216,7 +293,7 @@ impl CanvasWidget {
         Self {
             config: Configurable::new(),
             event_list: vec![],
-            widget_id: 0,
+            widget_id: WidgetId::NONE,
             callbacks: DefaultWidgetCallbacks::new(),
         }
     }
@@ -253,7 +330,7 @@ impl Widget for CanvasWidget {
     fn set_size(&mut self, config: u8, w: i32, h: i32) {
         self.set_config(config, Config::Size(Size { w, h }));
 
-        if self.widget_id != 0 {
+        if !self.widget_id.is_none() {
             self.event_list.push(CallbackEvent::WidgetResized {
                 widget_id: self.widget_id,
                 size: Size { w, h },
@@ -264,7 +341,7 @@ impl Widget for CanvasWidget {
     fn set_point(&mut self, config: u8, x: i32, y: i32) {
         self.set_config(config, Config::Point(Point { x, y }));
 
-        if self.widget_id != 0 {
+        if !self.widget_id.is_none() {
             self.event_list.push(CallbackEvent::WidgetMoved {
                 widget_id: self.widget_id,
                 point: Point { x, y },
@@ -272,11 +349,11 @@ impl Widget for CanvasWidget {
         }
     }
 
-    fn set_widget_id(&mut self, widget_id: i32) {
+    fn set_widget_id(&mut self, widget_id: WidgetId) {
         self.widget_id = widget_id;
     }
 
-    fn get_widget_id(&mut self) -> i32 {
+    fn get_widget_id(&mut self) -> WidgetId {
         self.widget_id
     }
 
@@ -305,12 +382,18 @@ pub struct DefaultWidgetCallbacks {
         Option<Box<dyn FnMut(&mut dyn Widget, Button, bool, &Vec<WidgetContainer>)>>,
     pub on_tick: Option<Box<dyn FnMut(&mut dyn Widget, &Vec<WidgetContainer>)>>,
     pub on_mouse_bounds: Option<Box<dyn FnMut(&mut dyn Widget, bool, &Vec<WidgetContainer>)>>,
+    pub on_text_entered: Option<Box<dyn FnMut(&mut dyn Widget, String, &Vec<WidgetContainer>)>>,
+    pub on_text_changed: Option<Box<dyn FnMut(&mut dyn Widget, String, &Vec<WidgetContainer>)>>,
+    pub on_text_submitted: Option<Box<dyn FnMut(&mut dyn Widget, String, &Vec<WidgetContainer>)>>,
     on_click_populated: bool,
     on_toggle_populated: bool,
     on_mouse_move_populated: bool,
     on_mouse_button_populated: bool,
     on_tick_populated: bool,
     on_mouse_bounds_populated: bool,
+    on_text_entered_populated: bool,
+    on_text_changed_populated: bool,
+    on_text_submitted_populated: bool,
 }
 
 impl DefaultWidgetCallbacks {
@@ -322,12 +405,18 @@ impl DefaultWidgetCallbacks {
             on_mouse_button: None,
             on_tick: None,
             on_mouse_bounds: None,
+            on_text_entered: None,
+            on_text_changed: None,
+            on_text_submitted: None,
             on_click_populated: false,
             on_toggle_populated: false,
             on_mouse_move_populated: false,
             on_mouse_button_populated: false,
             on_tick_populated: false,
             on_mouse_bounds_populated: false,
+            on_text_entered_populated: false,
+            on_text_changed_populated: false,
+            on_text_submitted_populated: false,
         }
     }
 
@@ -402,21 +491,102 @@ impl DefaultWidgetCallbacks {
     pub fn has_on_mouse_bounds(&mut self) -> bool {
         self.on_mouse_bounds_populated
     }
+
+    pub fn on_text_entered<F>(&mut self, callback: F)
+    where
+        F: FnMut(&mut dyn Widget, String, &Vec<WidgetContainer>) + 'static,
+    {
+        self.on_text_entered = Some(Box::new(callback));
+        self.on_text_entered_populated = true;
+    }
+
+    pub fn has_on_text_entered(&mut self) -> bool {
+        self.on_text_entered_populated
+    }
+
+    pub fn on_text_changed<F>(&mut self, callback: F)
+    where
+        F: FnMut(&mut dyn Widget, String, &Vec<WidgetContainer>) + 'static,
+    {
+        self.on_text_changed = Some(Box::new(callback));
+        self.on_text_changed_populated = true;
+    }
+
+    pub fn has_on_text_changed(&mut self) -> bool {
+        self.on_text_changed_populated
+    }
+
+    pub fn on_text_submitted<F>(&mut self, callback: F)
+    where
+        F: FnMut(&mut dyn Widget, String, &Vec<WidgetContainer>) + 'static,
+    {
+        self.on_text_submitted = Some(Box::new(callback));
+        self.on_text_submitted_populated = true;
+    }
+
+    pub fn has_on_text_submitted(&mut self) -> bool {
+        self.on_text_submitted_populated
+    }
 }
 
-pub fn get_widget_by_name(widgets: &Vec<WidgetContainer>, name: String) -> RefMut<Box<dyn Widget>> {
-    let pos = match widgets
+/// Retrieves a `Widget` by name from the list of `Widget`s passed to `handle_event`, borrowing it
+/// mutably.  Returns `None` if no `Widget` has the name.
+pub fn get_widget_by_name(
+    widgets: &Vec<WidgetContainer>,
+    name: String,
+) -> Option<RefMut<Box<dyn Widget>>> {
+    widgets
         .iter()
-        .find(|x| x.widget_name == String::from(name.clone()))
-    {
-        Some(x) => x.widget_id as usize,
-        None => 0 as usize,
+        .find(|x| !x.removed && x.widget_name == name)
+        .map(|x| x.widget.borrow_mut())
+}
+
+/// Computes a clipping `DrawState` that restricts drawing to an area of `size`, starting at the
+/// origin of the `Context` that is supplied.  The area is intersected with any clipping already
+/// set in `clip`.  Use this when a `Widget` draws content that can overflow its own bounds, such
+/// as scrolled text.
+pub fn clip_to_size(c: &Context, clip: &DrawState, size: Size) -> DrawState {
+    let viewport = match c.viewport {
+        Some(viewport) => viewport,
+        None => return *clip,
     };
 
-    widgets[pos].widget.borrow_mut()
+    // The transform maps drawing coordinates to normalized device coordinates, which are then
+    // converted to pixels, as the scissor area is specified in framebuffer pixels.
+    let to_pixels = |x: f64, y: f64| {
+        let pos = math::transform_pos(c.transform, [x, y]);
+
+        [
+            (pos[0] + 1.0) / 2.0 * viewport.draw_size[0] as f64,
+            (1.0 - pos[1]) / 2.0 * viewport.draw_size[1] as f64,
+        ]
+    };
+
+    let top_left = to_pixels(0.0, 0.0);
+    let bottom_right = to_pixels(size.w as f64, size.h as f64);
+    let mut bounds = [
+        top_left[0].max(0.0),
+        top_left[1].max(0.0),
+        bottom_right[0].max(0.0),
+        bottom_right[1].max(0.0),
+    ];
+
+    if let Some(scissor) = clip.scissor {
+        bounds[0] = bounds[0].max(scissor[0] as f64);
+        bounds[1] = bounds[1].max(scissor[1] as f64);
+        bounds[2] = bounds[2].min((scissor[0] + scissor[2]) as f64);
+        bounds[3] = bounds[3].min((scissor[1] + scissor[3]) as f64);
+    }
+
+    clip.scissor([
+        bounds[0] as u32,
+        bounds[1] as u32,
+        (bounds[2] - bounds[0]).max(0.0) as u32,
+        (bounds[3] - bounds[1]).max(0.0) as u32,
+    ])
 }
 
-pub fn invalidate_all_widgets_except(widgets: &Vec<WidgetContainer>, skip_id: i32) {
+pub fn invalidate_all_widgets_except(widgets: &Vec<WidgetContainer>, skip_id: WidgetId) {
     widgets.iter().for_each(|x| {
         if x.widget_id != skip_id {
             x.widget.borrow_mut().invalidate()
@@ -479,6 +649,15 @@ macro_rules! inject_event_handler {
                     }
                 }
 
+                CallbackEvent::TextEntered { widget_id: _, text } => {
+                    if self.get_callbacks().has_on_text_entered() {
+                        if let Some(mut cb) = self.get_callbacks().on_text_entered.take() {
+                            cb(self, text, widgets);
+                            self.get_callbacks().on_text_entered = Some(cb);
+                        }
+                    }
+                }
+
                 _ => (),
             }
         }
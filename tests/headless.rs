@@ -0,0 +1,376 @@
+// Headless Tests
+// Drives a Pushrod run loop without a display, using scripted input
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use piston::input::*;
+use pushrod::core::anchor::*;
+use pushrod::core::backend::*;
+use pushrod::core::callbacks::*;
+use pushrod::core::error::PushrodError;
+use pushrod::core::main::*;
+use pushrod::core::point::*;
+use pushrod::core::widget_id::WidgetId;
+use pushrod::core::widget_store::*;
+use pushrod::widget::box_widget::*;
+use pushrod::widget::checkbox_widget::*;
+use pushrod::widget::config::*;
+use pushrod::widget::push_button_widget::*;
+use pushrod::widget::text_area_widget::*;
+use pushrod::widget::text_input_widget::*;
+use pushrod::widget::text_widget::*;
+use pushrod::widget::widget::*;
+
+/// Records the mouse button events that are received, in order.
+#[derive(Default)]
+struct RecordingHandler {
+    events: Vec<(&'static str, WidgetId)>,
+}
+
+impl PushrodCallbackEvents for RecordingHandler {
+    fn mouse_button_up_inside(
+        &mut self,
+        widget_id: WidgetId,
+        _button: Button,
+        _widget_store: &mut WidgetStore,
+    ) {
+        self.events.push(("up_inside", widget_id));
+    }
+
+    fn mouse_button_up_outside(
+        &mut self,
+        widget_id: WidgetId,
+        _button: Button,
+        _widget_store: &mut WidgetStore,
+    ) {
+        self.events.push(("up_outside", widget_id));
+    }
+
+    fn widget_clicked(
+        &mut self,
+        widget_id: WidgetId,
+        _button: Button,
+        _widget_store: &mut WidgetStore,
+    ) {
+        self.events.push(("clicked", widget_id));
+    }
+
+    fn widget_selected(
+        &mut self,
+        widget_id: WidgetId,
+        _button: Button,
+        selected: bool,
+        _widget_store: &mut WidgetStore,
+    ) {
+        self.events
+            .push((if selected { "selected" } else { "deselected" }, widget_id));
+    }
+
+    fn widget_focus_gained(&mut self, widget_id: WidgetId, _widget_store: &mut WidgetStore) {
+        self.events.push(("focus_gained", widget_id));
+    }
+
+    fn widget_focus_lost(&mut self, widget_id: WidgetId, _widget_store: &mut WidgetStore) {
+        self.events.push(("focus_lost", widget_id));
+    }
+}
+
+/// Creates a headless run loop with a single button at 100x100, sized 200x50.
+fn make_pushrod() -> (Pushrod<HeadlessWindow>, WidgetId) {
+    let mut pushrod = Pushrod::new(HeadlessWindow::new(800, 600));
+    let mut button = PushButtonWidget::new(
+        "assets/OpenSans-Regular.ttf".to_string(),
+        "Click".to_string(),
+        18,
+        TextJustify::Center,
+    );
+
+    button.set_point(CONFIG_ORIGIN, 100, 100);
+    button.set_size(CONFIG_BODY_SIZE, 200, 50);
+
+    let button_id = pushrod.add_widget("Button", Box::new(button));
+
+    (pushrod, button_id)
+}
+
+fn move_to(x: f64, y: f64) -> Input {
+    Input::Move(Motion::MouseCursor([x, y]))
+}
+
+fn left_button(state: ButtonState) -> Input {
+    Input::Button(ButtonArgs {
+        state,
+        button: Button::Mouse(MouseButton::Left),
+        scancode: None,
+    })
+}
+
+#[test]
+fn click_inside_widget() {
+    let (mut pushrod, button_id) = make_pushrod();
+    let mut handler = RecordingHandler::default();
+
+    pushrod.step(
+        &mut handler,
+        &[
+            move_to(150.0, 120.0),
+            left_button(ButtonState::Press),
+            left_button(ButtonState::Release),
+        ],
+    );
+
+    assert_eq!(
+        handler.events,
+        vec![("up_inside", button_id), ("clicked", button_id)]
+    );
+}
+
+#[test]
+fn release_outside_widget() {
+    let (mut pushrod, button_id) = make_pushrod();
+    let mut handler = RecordingHandler::default();
+
+    pushrod.step(
+        &mut handler,
+        &[
+            move_to(150.0, 120.0),
+            left_button(ButtonState::Press),
+            move_to(500.0, 400.0),
+            left_button(ButtonState::Release),
+        ],
+    );
+
+    assert_eq!(handler.events, vec![("up_outside", button_id)]);
+}
+
+#[test]
+fn toggle_checkbox() {
+    let (mut pushrod, _) = make_pushrod();
+    let mut handler = RecordingHandler::default();
+    let mut checkbox = CheckboxWidget::new(
+        "assets/OpenSans-Regular.ttf".to_string(),
+        "Check".to_string(),
+        18,
+        TextJustify::Left,
+        false,
+    );
+
+    checkbox.set_point(CONFIG_ORIGIN, 100, 400);
+    checkbox.set_size(CONFIG_BODY_SIZE, 200, 32);
+
+    let checkbox_id = pushrod.add_widget("Checkbox", Box::new(checkbox));
+
+    pushrod.step(
+        &mut handler,
+        &[
+            move_to(150.0, 410.0),
+            left_button(ButtonState::Press),
+            left_button(ButtonState::Release),
+        ],
+    );
+
+    assert_eq!(
+        handler.events,
+        vec![("up_inside", checkbox_id), ("selected", checkbox_id)]
+    );
+
+    // Frames of a headless run loop do not draw the images of the checkbox.
+    pushrod.get_window().set_max_frames(2);
+    assert_eq!(pushrod.run(&mut handler), 0);
+}
+
+#[test]
+fn remove_focused_widget() {
+    let (mut pushrod, _) = make_pushrod();
+    let mut handler = RecordingHandler::default();
+    let mut text_input = TextInputWidget::new(
+        "assets/OpenSans-Regular.ttf".to_string(),
+        "".to_string(),
+        18,
+    );
+
+    text_input.set_point(CONFIG_ORIGIN, 100, 300);
+    text_input.set_size(CONFIG_BODY_SIZE, 200, 32);
+
+    let text_input_id = pushrod.add_widget("TextInput", Box::new(text_input));
+
+    pushrod.step(
+        &mut handler,
+        &[
+            move_to(150.0, 310.0),
+            left_button(ButtonState::Press),
+            left_button(ButtonState::Release),
+        ],
+    );
+    handler.events.clear();
+
+    assert!(pushrod
+        .widget_store
+        .borrow_mut()
+        .remove_widget(text_input_id));
+    pushrod.step(&mut handler, &[]);
+
+    assert_eq!(handler.events, vec![("focus_lost", text_input_id)]);
+    assert_eq!(pushrod.widget_store.borrow().get_focused_widget_id(), None);
+}
+
+#[test]
+fn lazy_run_ends_when_idle() {
+    let (mut pushrod, _) = make_pushrod();
+    let mut handler = RecordingHandler::default();
+
+    pushrod.set_lazy(true);
+    pushrod.get_window().set_close_when_idle(true);
+    pushrod.get_window().push_input(Input::Button(ButtonArgs {
+        state: ButtonState::Press,
+        button: Button::Keyboard(Key::A),
+        scancode: None,
+    }));
+
+    assert_eq!(pushrod.run(&mut handler), 0);
+    assert!(handler.events.is_empty());
+}
+
+#[test]
+fn text_area_text_set_through_config() {
+    let (mut pushrod, _) = make_pushrod();
+    let mut handler = RecordingHandler::default();
+    let text_area = TextAreaWidget::new(
+        "assets/OpenSans-Regular.ttf".to_string(),
+        "A first line of text that is long enough to wrap\nA second line\nA third line".to_string(),
+        18,
+    );
+    let text_area_id = pushrod.add_widget("TextArea", Box::new(text_area));
+
+    pushrod
+        .widget_store
+        .borrow_mut()
+        .get_widget_as_mut::<TextAreaWidget>(text_area_id)
+        .unwrap()
+        .set_size(CONFIG_BODY_SIZE, 200, 200);
+    pushrod
+        .widget_store
+        .borrow_mut()
+        .set_focused_widget_id(Some(text_area_id));
+    pushrod
+        .widget_store
+        .borrow_mut()
+        .get_widget_as_mut::<TextAreaWidget>(text_area_id)
+        .unwrap()
+        .config()
+        .set_text(CONFIG_DISPLAY_TEXT, "Hi".to_string());
+
+    pushrod.step(
+        &mut handler,
+        &[Input::Button(ButtonArgs {
+            state: ButtonState::Press,
+            button: Button::Keyboard(Key::Up),
+            scancode: None,
+        })],
+    );
+
+    let widget_store = pushrod.widget_store.borrow();
+    let text_area = widget_store
+        .get_widget_as::<TextAreaWidget>(text_area_id)
+        .unwrap();
+
+    assert_eq!(text_area.get_caret_position(), 0);
+    assert_eq!(text_area.get_selection(), None);
+}
+
+#[test]
+fn add_widget_to_invalid_parent() {
+    let (mut pushrod, button_id) = make_pushrod();
+
+    assert!(pushrod.widget_store.borrow_mut().remove_widget(button_id));
+
+    let widget_count = pushrod.widget_store.borrow().widgets.len();
+
+    assert!(
+        match pushrod.add_widget_to_parent("Box", Box::new(BoxWidget::new()), button_id) {
+            Err(PushrodError::InvalidParent { parent_id }) => parent_id == button_id,
+            _ => false,
+        }
+    );
+    assert!(
+        match pushrod.add_widget_to_parent_by_name("Button", "Box", Box::new(BoxWidget::new())) {
+            Err(PushrodError::WidgetNotFound { name }) => name == "Button",
+            _ => false,
+        }
+    );
+    assert!(match pushrod.add_widget_to_layout_manager(
+        "Box",
+        Box::new(BoxWidget::new()),
+        3,
+        make_origin_point(),
+    ) {
+        Err(PushrodError::InvalidLayoutManager { manager_id }) => manager_id == 3,
+        _ => false,
+    });
+    assert_eq!(pushrod.widget_store.borrow().widgets.len(), widget_count);
+}
+
+#[test]
+fn minimize_and_restore_window() {
+    let (mut pushrod, _) = make_pushrod();
+    let mut handler = RecordingHandler::default();
+    let mut filled = BoxWidget::new();
+    let mut scaled = BoxWidget::new();
+
+    filled.set_point(CONFIG_ORIGIN, 10, 10);
+    filled.set_size(CONFIG_BODY_SIZE, 780, 580);
+    filled.set_anchor(Anchor::fill());
+    scaled.set_point(CONFIG_ORIGIN, 200, 150);
+    scaled.set_size(CONFIG_BODY_SIZE, 400, 300);
+    scaled.set_anchor(Anchor::proportional());
+
+    let filled_id = pushrod.add_widget("Filled", Box::new(filled));
+    let scaled_id = pushrod.add_widget("Scaled", Box::new(scaled));
+    let geometry = |pushrod: &Pushrod<HeadlessWindow>, widget_id: WidgetId| {
+        let widget_store = pushrod.widget_store.borrow();
+        let mut widget = widget_store
+            .get_widget_for_id(widget_id)
+            .unwrap()
+            .borrow_mut();
+
+        (
+            widget.config().get_point(CONFIG_ORIGIN),
+            widget.config().get_size(CONFIG_BODY_SIZE),
+        )
+    };
+    let resize_to = |w: u32, h: u32| {
+        Input::Resize(ResizeArgs {
+            window_size: [f64::from(w), f64::from(h)],
+            draw_size: [w, h],
+        })
+    };
+    let filled_geometry = geometry(&pushrod, filled_id);
+    let scaled_geometry = geometry(&pushrod, scaled_id);
+
+    pushrod.step(&mut handler, &[resize_to(0, 0)]);
+    pushrod.step(&mut handler, &[resize_to(800, 600)]);
+
+    assert_eq!(geometry(&pushrod, filled_id), filled_geometry);
+    assert_eq!(geometry(&pushrod, scaled_id), scaled_geometry);
+
+    pushrod.step(&mut handler, &[resize_to(1600, 1200)]);
+
+    assert_eq!(
+        geometry(&pushrod, filled_id),
+        (Point { x: 10, y: 10 }, Size { w: 1580, h: 1180 })
+    );
+    assert_eq!(
+        geometry(&pushrod, scaled_id),
+        (Point { x: 400, y: 300 }, Size { w: 800, h: 600 })
+    );
+}